@@ -0,0 +1,21 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+#[derive(Debug)]
+#[allow(dead_code)]
+enum Reasons {
+    FailToDoSomething { name: String },
+}
+
+fn err_new(c: &mut Criterion) {
+    c.bench_function("Err::new without handlers", |b| {
+        b.iter(|| {
+            black_box(errs::Err::new(Reasons::FailToDoSomething {
+                name: "foo".to_string(),
+            }))
+        })
+    });
+}
+
+criterion_group!(benches, err_new);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+#[derive(Debug)]
+#[allow(dead_code)]
+enum Reasons {
+    FailToDoSomething { name: String },
+}
+
+fn err_notify(c: &mut Criterion) {
+    errs::add_sync_err_handler(|err, _tm| {
+        black_box(err);
+    })
+    .unwrap();
+    errs::fix_err_handlers().unwrap();
+
+    c.bench_function("Err::new_silent with fixed handlers", |b| {
+        b.iter(|| {
+            black_box(errs::Err::new_silent(Reasons::FailToDoSomething {
+                name: "foo".to_string(),
+            }))
+        })
+    });
+
+    c.bench_function("Err::new with fixed handlers", |b| {
+        b.iter(|| {
+            black_box(errs::Err::new(Reasons::FailToDoSomething {
+                name: "foo".to_string(),
+            }))
+        })
+    });
+}
+
+criterion_group!(benches, err_notify);
+criterion_main!(benches);
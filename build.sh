@@ -28,6 +28,14 @@ compile() {
 }
 
 test() {
+  echo "### features: no_std"
+  cargo test --no-default-features -- --show-output
+  errcheck $?
+
+  echo "### features: no_std, core-error"
+  cargo test --no-default-features --features core-error -- --show-output
+  errcheck $?
+
   echo "### features: default"
   cargo test --features default -- --show-output
   errcheck $?
@@ -40,6 +48,30 @@ test() {
   cargo test --features notify-tokio -- --show-output
   errcheck $?
 
+  echo "### features: tracing"
+  cargo test --features tracing -- --show-output
+  errcheck $?
+
+  echo "### features: log"
+  cargo test --features log -- --show-output
+  errcheck $?
+
+  echo "### features: intern"
+  cargo test --features intern -- --show-output
+  errcheck $?
+
+  echo "### features: metrics"
+  cargo test --features metrics -- --show-output
+  errcheck $?
+
+  echo "### features: json"
+  cargo test --features json -- --show-output
+  errcheck $?
+
+  echo "### features: registry"
+  cargo test --features registry -- --show-output
+  errcheck $?
+
   echo "### features: full"
   cargo test --features full -- --show-output
   errcheck $?
@@ -60,7 +92,7 @@ cover() {
 }
 
 bench() {
-  cargo +nightly bench --quiet -- $1
+  cargo +nightly bench --quiet --all-features -- $1
   errcheck $?
 }
 
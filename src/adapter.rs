@@ -0,0 +1,159 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, RwLock};
+
+type AdapterFn<T> = Box<dyn Fn(&dyn any::Any) -> Option<&T> + Send + Sync>;
+
+pub(crate) type Adapters =
+    RwLock<HashMap<(any::TypeId, any::TypeId), Box<dyn any::Any + Send + Sync>>>;
+
+pub(crate) static ADAPTERS: LazyLock<Adapters> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Registers an adapter which converts a reason of type `R` into a reference of type `T`.
+///
+/// `T` is typically a trait object of a trait shared by multiple reason types. After an adapter
+/// is registered, the reason of an `Err` instance can be retrieved as `&T` with
+/// [`Err::reason_as`](crate::Err::reason_as) regardless of the concrete reason type.
+/// If an adapter for the same pair of `R` and `T` is already registered, it is replaced.
+///
+/// # Parameters
+/// - `adapter`: The function which converts a reference of the reason into a reference of `T`.
+///
+/// ```rust
+/// use errs::Err;
+///
+/// trait AppError {
+///     fn code(&self) -> u32;
+/// }
+///
+/// #[derive(Debug)]
+/// enum DbErr {
+///     ConnectionLost,
+/// }
+///
+/// impl AppError for DbErr {
+///     fn code(&self) -> u32 {
+///         500
+///     }
+/// }
+///
+/// errs::register_reason_adapter::<DbErr, dyn AppError>(|r| r);
+///
+/// let err = Err::new(DbErr::ConnectionLost);
+/// assert_eq!(err.reason_as::<dyn AppError>().unwrap().code(), 500);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn register_reason_adapter<R, T>(adapter: fn(&R) -> &T)
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    T: ?Sized + 'static,
+{
+    register(&ADAPTERS, adapter);
+}
+
+pub(crate) fn register<R, T>(adapters: &Adapters, adapter: fn(&R) -> &T)
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    T: ?Sized + 'static,
+{
+    let f: AdapterFn<T> = Box::new(move |any| any.downcast_ref::<R>().map(adapter));
+    let key = (any::TypeId::of::<R>(), any::TypeId::of::<T>());
+
+    let mut map = match adapters.write() {
+        Ok(map) => map,
+        Err(e) => e.into_inner(),
+    };
+    map.insert(key, Box::new(f));
+}
+
+pub(crate) fn adapt<'a, T>(adapters: &Adapters, reason: &'a dyn any::Any) -> Option<&'a T>
+where
+    T: ?Sized + 'static,
+{
+    let key = (reason.type_id(), any::TypeId::of::<T>());
+
+    let map = match adapters.read() {
+        Ok(map) => map,
+        Err(e) => e.into_inner(),
+    };
+    let f = map.get(&key)?.downcast_ref::<AdapterFn<T>>()?;
+    f(reason)
+}
+
+#[cfg(test)]
+mod tests_of_adapter {
+    use super::*;
+    use crate::Err;
+
+    trait AppError {
+        fn kind(&self) -> &'static str;
+    }
+
+    #[derive(Debug)]
+    enum DbErr {
+        ConnectionLost,
+    }
+
+    impl AppError for DbErr {
+        fn kind(&self) -> &'static str {
+            "db"
+        }
+    }
+
+    #[derive(Debug)]
+    enum NetErr {
+        Timeout,
+    }
+
+    impl AppError for NetErr {
+        fn kind(&self) -> &'static str {
+            "net"
+        }
+    }
+
+    #[derive(Debug)]
+    enum OtherErr {
+        Unknown,
+    }
+
+    #[test]
+    fn coerce_two_reason_types_to_one_trait_object() {
+        let adapters: Adapters = RwLock::new(HashMap::new());
+        register::<DbErr, dyn AppError>(&adapters, |r| r);
+        register::<NetErr, dyn AppError>(&adapters, |r| r);
+
+        let err = Err::new(DbErr::ConnectionLost);
+        let app_err = adapt::<dyn AppError>(&adapters, err.reason_any()).unwrap();
+        assert_eq!(app_err.kind(), "db");
+
+        let err = Err::new(NetErr::Timeout);
+        let app_err = adapt::<dyn AppError>(&adapters, err.reason_any()).unwrap();
+        assert_eq!(app_err.kind(), "net");
+
+        let err = Err::new(OtherErr::Unknown);
+        assert!(adapt::<dyn AppError>(&adapters, err.reason_any()).is_none());
+
+        let err = Err::new(DbErr::ConnectionLost);
+        assert!(adapt::<dyn fmt::Display>(&adapters, err.reason_any()).is_none());
+    }
+
+    #[test]
+    fn reason_as_with_global_registry() {
+        register_reason_adapter::<DbErr, dyn AppError>(|r| r);
+        register_reason_adapter::<NetErr, dyn AppError>(|r| r);
+
+        let err = Err::new(DbErr::ConnectionLost);
+        assert_eq!(err.reason_as::<dyn AppError>().unwrap().kind(), "db");
+
+        let err = Err::new(NetErr::Timeout);
+        assert_eq!(err.reason_as::<dyn AppError>().unwrap().kind(), "net");
+
+        let err = Err::new(OtherErr::Unknown);
+        assert!(err.reason_as::<dyn AppError>().is_none());
+    }
+}
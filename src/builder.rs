@@ -0,0 +1,172 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{err, Err, ErrBuilder, Severity};
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::panic::Location;
+
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use std::error;
+
+impl<R> ErrBuilder<R>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    /// Creates a new `ErrBuilder` with the given reason.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    pub fn new(reason: R) -> Self {
+        Self {
+            reason,
+            #[cfg(feature = "std")]
+            leak_fn: None,
+            severity: Severity::Error,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Sets the underlying source error of the error.
+    ///
+    /// # Parameters
+    /// - `source`: The underlying source error that caused the error.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn source<E>(mut self, source: E) -> Self
+    where
+        E: error::Error + Send + Sync + 'static,
+    {
+        self.leak_fn = Some(Box::new(move |reason| {
+            err::leak_reason_and_source(reason, source)
+        }));
+        self
+    }
+
+    /// Sets the severity of the error.
+    ///
+    /// # Parameters
+    /// - `severity`: The severity of the error.
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Adds a key-value field to the error.
+    ///
+    /// The value is stored as a string formatted with its `Display` implementation, as well as
+    /// [`Err::with_field`].
+    ///
+    /// # Parameters
+    /// - `key`: The key of the field.
+    /// - `value`: The value of the field.
+    pub fn field<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: fmt::Display,
+    {
+        self.fields.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Creates an `Err` instance with the reason, source, severity, and fields set to this
+    /// builder.
+    ///
+    /// The location of the created `Err` is where this method is called.
+    ///
+    /// # Returns
+    /// A new `Err` instance.
+    #[track_caller]
+    pub fn build(self) -> Err {
+        let loc = Location::caller();
+
+        #[cfg(feature = "std")]
+        let ptr = match self.leak_fn {
+            Some(leak_fn) => leak_fn(self.reason),
+            None => err::leak_reason(self.reason),
+        };
+        #[cfg(not(feature = "std"))]
+        let ptr = err::leak_reason(self.reason);
+
+        Err::create_with_fields(loc, ptr, false, self.severity, self.fields)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_err_builder {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToLoadConfig { path: String },
+    }
+
+    #[test]
+    fn build_with_reason_only() {
+        let line = line!() + 1;
+        let err = ErrBuilder::new(Reasons::FailToLoadConfig {
+            path: "/aaa".to_string(),
+        })
+        .build();
+
+        match err.reason::<Reasons>() {
+            Ok(Reasons::FailToLoadConfig { path }) => assert_eq!(path, "/aaa"),
+            Err(_) => panic!(),
+        }
+        assert_eq!(err.severity(), Severity::Error);
+        assert!(err.fields().is_empty());
+        #[cfg(unix)]
+        assert_eq!(err.file(), "src/builder.rs");
+        assert_eq!(err.line(), line + 3);
+    }
+
+    #[test]
+    fn build_with_severity_and_fields() {
+        let builder = ErrBuilder::new(Reasons::FailToLoadConfig {
+            path: "/aaa".to_string(),
+        })
+        .severity(Severity::Warning)
+        .field("user_id", 42)
+        .field("request_id", "abc");
+
+        let line = line!() + 1;
+        let err = builder.build();
+
+        assert!(err.reason::<Reasons>().is_ok());
+        assert_eq!(err.severity(), Severity::Warning);
+        assert_eq!(
+            err.fields(),
+            &[
+                ("user_id".to_string(), "42".to_string()),
+                ("request_id".to_string(), "abc".to_string()),
+            ]
+        );
+        #[cfg(unix)]
+        assert_eq!(err.file(), "src/builder.rs");
+        assert_eq!(err.line(), line);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn build_with_source() {
+        let err = ErrBuilder::new(Reasons::FailToLoadConfig {
+            path: "/aaa".to_string(),
+        })
+        .source(std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!"))
+        .severity(Severity::Fatal)
+        .field("k", "v")
+        .build();
+
+        assert!(err.reason::<Reasons>().is_ok());
+        let io_err = err.source().unwrap().downcast_ref::<std::io::Error>();
+        assert_eq!(io_err.unwrap().kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(err.severity(), Severity::Fatal);
+        assert_eq!(err.fields(), &[("k".to_string(), "v".to_string())]);
+        assert_eq!(err.to_string(), "FailToLoadConfig { path: \"/aaa\" } [k=v]");
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{Chain, DisplayChain, Err};
+
+use std::{error, fmt};
+
+impl<'a> Chain<'a> {
+    pub(crate) fn new(err: &'a (dyn error::Error + 'static)) -> Self {
+        Self { next: Some(err) }
+    }
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn error::Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.source();
+        Some(current)
+    }
+}
+
+impl<'a> DisplayChain<'a> {
+    pub(crate) fn new(err: &'a Err, sep: &'a str) -> Self {
+        Self { err, sep }
+    }
+}
+
+impl fmt::Display for DisplayChain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, e) in self.err.chain().enumerate() {
+            if i > 0 {
+                f.write_str(self.sep)?;
+            }
+            write!(f, "{e}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_of_chain {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Enum0 {
+        FailToDoSomething,
+    }
+
+    #[derive(Debug)]
+    enum Enum1 {
+        FailToCallSomething,
+    }
+
+    #[test]
+    fn test_chain() {
+        let io_err = std::io::Error::other("oh no!");
+        let err1 = Err::with_source(Enum1::FailToCallSomething, io_err);
+        let err0 = Err::with_source(Enum0::FailToDoSomething, err1);
+
+        let mut chain = err0.chain();
+        let e = chain.next().unwrap();
+        assert!(e.downcast_ref::<Err>().unwrap().reason::<Enum0>().is_ok());
+        let e = chain.next().unwrap();
+        assert!(e.downcast_ref::<Err>().unwrap().reason::<Enum1>().is_ok());
+        let e = chain.next().unwrap();
+        assert!(e.downcast_ref::<std::io::Error>().is_some());
+        assert!(chain.next().is_none());
+    }
+
+    #[test]
+    fn test_chain_of_err_without_source() {
+        let err = Err::new(Enum0::FailToDoSomething);
+        assert_eq!(err.chain().count(), 1);
+    }
+
+    #[test]
+    fn test_display_chain() {
+        let io_err = std::io::Error::other("oh no!");
+        let err1 = Err::with_source(Enum1::FailToCallSomething, io_err);
+        let err0 = Err::with_source(Enum0::FailToDoSomething, err1);
+
+        assert_eq!(
+            format!("{}", err0.display_chain()),
+            "FailToDoSomething: FailToCallSomething: oh no!"
+        );
+        assert_eq!(
+            format!("{}", err0.display_chain_with(" <- ")),
+            "FailToDoSomething <- FailToCallSomething <- oh no!"
+        );
+    }
+
+    #[test]
+    fn test_display_chain_of_err_without_source() {
+        let err = Err::new(Enum0::FailToDoSomething);
+        assert_eq!(format!("{}", err.display_chain()), "FailToDoSomething");
+    }
+}
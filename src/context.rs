@@ -0,0 +1,132 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{Context, Err};
+
+use std::{error, fmt};
+
+impl<T, E> Context<T> for Result<T, E>
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn context<C>(self, ctx: C) -> crate::Result<T>
+    where
+        C: fmt::Debug + Send + Sync + 'static,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(Err::with_source(ctx, e)),
+        }
+    }
+
+    #[track_caller]
+    fn with_context<C, F>(self, f: F) -> crate::Result<T>
+    where
+        C: fmt::Debug + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(Err::with_source(f(), e)),
+        }
+    }
+
+    #[track_caller]
+    fn locate(self) -> crate::Result<T> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(Err::here_with_source(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_of_context {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToLoadConfig { path: String },
+        FailToStart,
+    }
+
+    fn read_file(path: &str) -> Result<String, std::io::Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::NotFound, path))
+    }
+
+    #[test]
+    fn context_becomes_reason_and_original_becomes_source() {
+        let result = read_file("/aaa/bbb");
+        let line = line!() + 1;
+        let err = result.context("while loading config").unwrap_err();
+
+        match err.reason::<&str>() {
+            Ok(s) => assert_eq!(*s, "while loading config"),
+            Err(_) => panic!(),
+        }
+        let io_err = err.source().unwrap().downcast_ref::<std::io::Error>();
+        assert_eq!(io_err.unwrap().kind(), std::io::ErrorKind::NotFound);
+
+        #[cfg(unix)]
+        assert_eq!(err.file(), "src/context.rs");
+        assert_eq!(err.line(), line);
+    }
+
+    #[test]
+    fn with_context_creates_context_lazily() {
+        let err = read_file("/aaa/bbb")
+            .with_context(|| Reasons::FailToLoadConfig {
+                path: "/aaa/bbb".to_string(),
+            })
+            .unwrap_err();
+
+        match err.reason::<Reasons>() {
+            Ok(Reasons::FailToLoadConfig { path }) => assert_eq!(path, "/aaa/bbb"),
+            _ => panic!(),
+        }
+        assert!(err.source().unwrap().is::<std::io::Error>());
+
+        let result: Result<u32, std::io::Error> = Ok(1);
+        let v = result
+            .with_context(|| -> Reasons { panic!("should not be called") })
+            .unwrap();
+        assert_eq!(v, 1);
+    }
+
+    #[test]
+    fn locate_records_call_site() {
+        fn load_config(path: &str) -> crate::Result<String> {
+            let s = read_file(path).locate()?;
+            Ok(s)
+        }
+
+        let line = line!() - 4;
+        let err = load_config("/aaa/bbb").unwrap_err();
+
+        assert!(err.reason::<()>().is_err());
+        assert_eq!(
+            err.to_string(),
+            format!("{}:{}:{}", err.file(), err.line(), err.column())
+        );
+        let io_err = err.source().unwrap().downcast_ref::<std::io::Error>();
+        assert_eq!(io_err.unwrap().kind(), std::io::ErrorKind::NotFound);
+
+        #[cfg(unix)]
+        assert_eq!(err.file(), "src/context.rs");
+        assert_eq!(err.line(), line);
+
+        let result: Result<u32, std::io::Error> = Ok(1);
+        assert_eq!(result.locate().unwrap(), 1);
+    }
+
+    #[test]
+    fn context_on_errs_result() {
+        let result: crate::Result<()> = Err(Err::new(Reasons::FailToStart));
+        let err = result.context("while running").unwrap_err();
+
+        assert!(err.reason::<&str>().is_ok());
+        assert!(err.reason_in_chain::<Reasons>().is_some());
+    }
+}
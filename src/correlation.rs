@@ -0,0 +1,80 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+thread_local! {
+    static CORRELATION_ID: RefCell<Option<Arc<str>>> = const { RefCell::new(None) };
+}
+
+/// Sets the correlation ID of the current thread, which is attached to the errors created on
+/// this thread afterwards.
+///
+/// This is useful to propagate an ID of distributed tracing, e.g. a request ID, onto errors.
+/// The attached ID can be retrieved with [`Err::correlation_id`](crate::Err::correlation_id),
+/// so that error handlers can output it.
+///
+/// # Parameters
+/// - `id`: The correlation ID, or `None` to stop attaching a correlation ID.
+///
+/// ```rust
+/// use errs::Err;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// errs::set_correlation_id(Some("req-123".to_string()));
+/// let err = Err::new(Reasons::FailToDoSomething);
+/// assert_eq!(err.correlation_id(), Some("req-123"));
+///
+/// errs::set_correlation_id(None);
+/// let err = Err::new(Reasons::FailToDoSomething);
+/// assert_eq!(err.correlation_id(), None);
+/// ```
+pub fn set_correlation_id(id: Option<String>) {
+    CORRELATION_ID.with(|cell| *cell.borrow_mut() = id.map(Arc::from));
+}
+
+pub(crate) fn current_id() -> Option<Arc<str>> {
+    CORRELATION_ID.with(|cell| cell.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests_of_correlation {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn no_id_by_default() {
+        thread::spawn(|| assert!(current_id().is_none()))
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn set_and_unset_id() {
+        thread::spawn(|| {
+            set_correlation_id(Some("req-1".to_string()));
+            assert_eq!(current_id().as_deref(), Some("req-1"));
+
+            set_correlation_id(None);
+            assert!(current_id().is_none());
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn id_is_thread_local() {
+        set_correlation_id(Some("req-2".to_string()));
+        thread::spawn(|| assert!(current_id().is_none()))
+            .join()
+            .unwrap();
+        assert_eq!(current_id().as_deref(), Some("req-2"));
+        set_correlation_id(None);
+    }
+}
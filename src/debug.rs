@@ -0,0 +1,35 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use core::sync::atomic;
+
+static DEBUG_SHOWS_REASON_TYPE: atomic::AtomicBool = atomic::AtomicBool::new(true);
+
+/// Sets whether the `Debug` implementation of `Err` shows the type name of the reason.
+///
+/// If this is set to `false`, the verbose type path of the reason is omitted, and only the
+/// `Debug` output of the reason is shown. The default is `true`.
+///
+/// # Parameters
+/// - `shows`: `true` to show the type name of the reason, or `false` not to show it.
+///
+/// ```rust
+/// use errs::Err;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// errs::set_debug_show_reason_type(false);
+/// let err = Err::new(Reasons::FailToDoSomething);
+/// assert!(format!("{err:?}").starts_with("errs::Err { reason = FailToDoSomething, file = "));
+/// ```
+pub fn set_debug_show_reason_type(shows: bool) {
+    DEBUG_SHOWS_REASON_TYPE.store(shows, atomic::Ordering::Relaxed);
+}
+
+pub(crate) fn shows_reason_type() -> bool {
+    DEBUG_SHOWS_REASON_TYPE.load(atomic::Ordering::Relaxed)
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Err;
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::{atomic, OnceLock};
+
+type DisplayFormatterFn = fn(&Err, &mut fmt::Formatter<'_>) -> fmt::Result;
+
+static DISPLAY_FORMATTER: OnceLock<DisplayFormatterFn> = OnceLock::new();
+static DISPLAY_INCLUDES_SOURCE: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+thread_local! {
+    static IS_FORMATTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets the formatter which is used by the `Display` implementation of `Err`.
+///
+/// This is useful to output all errors in a uniform format, e.g. `[file:line] reason`.
+/// The formatter can be set only once, and the second and later calls are ignored.
+/// If no formatter is set, `Err` is displayed as its reason.
+///
+/// If an `Err` is displayed inside the formatter, it is displayed in the default format
+/// instead of calling the formatter recursively.
+///
+/// # Parameters
+/// - `formatter`: The function which writes an `Err` instance to a formatter.
+///
+/// # Returns
+/// - `true` if the formatter is set, or `false` if a formatter has already been set.
+///
+/// ```rust
+/// use errs::Err;
+///
+/// errs::set_display_formatter(|err, f| {
+///     // `err` inside this function is displayed in the default format.
+///     write!(f, "[{}:{}] {}", err.file(), err.line(), err)
+/// });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn set_display_formatter(formatter: DisplayFormatterFn) -> bool {
+    DISPLAY_FORMATTER.set(formatter).is_ok()
+}
+
+/// Sets whether the `Display` implementation of `Err` includes the sources of the error.
+///
+/// If this is set to `true`, the display of each source in the error chain is appended to
+/// the display of the reason, separated by `: `. The default is `false`.
+/// This setting is not applied to the output of the formatter set by
+/// [`set_display_formatter`].
+///
+/// # Parameters
+/// - `includes`: `true` to include the sources, or `false` not to include them.
+///
+/// ```rust
+/// use errs::Err;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToReadFile,
+/// }
+///
+/// errs::set_display_includes_source(true);
+///
+/// let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+/// let err = Err::with_source(Reasons::FailToReadFile, io_err);
+/// assert_eq!(format!("{err}"), "FailToReadFile: file not found");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn set_display_includes_source(includes: bool) {
+    DISPLAY_INCLUDES_SOURCE.store(includes, atomic::Ordering::Relaxed);
+}
+
+pub(crate) fn includes_source() -> bool {
+    DISPLAY_INCLUDES_SOURCE.load(atomic::Ordering::Relaxed)
+}
+
+// Formats an error with the formatter set by `set_display_formatter`, and returns None if no
+// formatter is set or this is called inside the formatter.
+pub(crate) fn fmt_with_formatter(err: &Err, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+    let formatter = DISPLAY_FORMATTER.get()?;
+    if IS_FORMATTING.with(|b| b.replace(true)) {
+        return None;
+    }
+    let result = formatter(err, f);
+    IS_FORMATTING.with(|b| b.set(false));
+    Some(result)
+}
@@ -2,15 +2,47 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
-use crate::{Err, ReasonAndSource, SendSyncNonNull};
+use crate::{
+    debug, Err, ErrBuilder, ErrMeta, Located, PanicReason, ReasonAndSource, ReasonFields,
+    ReasonMismatchError, Redacted, SendSyncNonNull, Severity,
+};
 
-#[cfg(any(feature = "notify", feature = "notify-tokio"))]
-use crate::notify;
+#[cfg(feature = "std")]
+use crate::{
+    adapter, correlation, display, BoxedSource, Chain, DisplayChain, LazySource, ReasonInfo,
+    Sources,
+};
 
-use std::{any, error, fmt, marker, panic, ptr};
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+use crate::{notify, RenderedError, RenderedReason, SharedReason};
 
 #[cfg(any(feature = "notify", feature = "notify-tokio"))]
-use std::sync::atomic;
+use chrono::{Duration, Utc};
+
+#[cfg(feature = "tracing")]
+use crate::trace;
+
+#[cfg(feature = "log")]
+use crate::logging;
+
+#[cfg(feature = "intern")]
+use crate::intern;
+
+#[cfg(feature = "metrics")]
+use crate::metrics;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::panic::Location;
+use core::{any, fmt, hash, marker, mem, ptr};
+
+#[cfg(feature = "std")]
+use std::{error, sync, sync::Arc};
+
+#[cfg(not(feature = "std"))]
+use crate::error;
 
 unsafe impl<T: Send + Sync> Send for SendSyncNonNull<T> {}
 unsafe impl<T: Send + Sync> Sync for SendSyncNonNull<T> {}
@@ -30,6 +62,9 @@ impl Err {
     /// The reason can be of any type, but typically it is an enum variant that uniquely
     /// identifies the error's nature.
     ///
+    /// The location where this function is called is recorded as the location of the error.
+    /// To specify the location explicitly, use [`Err::new_at`].
+    ///
     /// # Parameters
     /// - `reason`: The reason for the error.
     ///
@@ -51,36 +86,252 @@ impl Err {
     where
         R: fmt::Debug + Send + Sync + 'static,
     {
-        let loc = panic::Location::caller();
+        let loc = Location::caller();
 
         let boxed = Box::new(ReasonAndSource::<R>::new(reason));
         let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
 
-        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
-        {
-            let err_notified = Self {
-                file: loc.file(),
-                line: loc.line(),
-                reason_and_source: SendSyncNonNull::new(ptr),
-            };
-            if let Err(e) = notify::notify_err(err_notified) {
-                eprintln!("ERROR(errs): {e:?}");
-            }
+        Self::create(loc, ptr, false, Severity::Error)
+    }
 
-            Self {
-                file: loc.file(),
-                line: loc.line(),
-                reason_and_source: SendSyncNonNull::new(ptr),
-            }
-        }
-        #[cfg(not(any(feature = "notify", feature = "notify-tokio")))]
-        {
-            Self {
-                file: loc.file(),
-                line: loc.line(),
-                reason_and_source: SendSyncNonNull::new(ptr),
-            }
-        }
+    /// Creates a new `Err` instance with the given reason and the given location.
+    ///
+    /// [`Err::new`] records the location of its caller with `#[track_caller]`, but when an `Err`
+    /// is created in a deeply wrapped helper function, the true call site is lost. This function
+    /// does not record its caller, and uses the given location instead. A wrapper function
+    /// marked with `#[track_caller]` can pass the location of its own caller obtained with
+    /// `std::panic::Location::caller()`.
+    ///
+    /// Since the column number is not given, the column number of the created `Err` is `0`.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    /// - `file`: The name of the source file where the error occurred.
+    /// - `line`: The line number in the source file where the error occurred.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the given reason and location.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::panic::Location;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     InvalidValue { value: i32 },
+    /// }
+    ///
+    /// #[track_caller]
+    /// fn invalid_value(value: i32) -> Err {
+    ///     let loc = Location::caller();
+    ///     // ... deeply wrapped construction ...
+    ///     Err::new_at(Reasons::InvalidValue { value }, loc.file(), loc.line())
+    /// }
+    ///
+    /// let line = line!() + 1;
+    /// let err = invalid_value(-1);
+    /// assert_eq!(err.line(), line);
+    /// ```
+    pub fn new_at<R>(reason: R, file: &'static str, line: u32) -> Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        let boxed = Box::new(ReasonAndSource::<R>::new(reason));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create_at(file, line, 0, ptr, false, Severity::Error, Vec::new())
+    }
+
+    /// Creates a new `Err` instance from a panic payload, e.g. caught with
+    /// `std::panic::catch_unwind`.
+    ///
+    /// The reason of the created `Err` is a [`PanicReason`], whose message is extracted from the
+    /// payload if it is a `&str` or a `String`, which are the common panic payload types.
+    /// Otherwise, the message is `"unknown panic"`.
+    ///
+    /// # Parameters
+    /// - `payload`: The panic payload.
+    ///
+    /// # Returns
+    /// A new `Err` instance with a `PanicReason` as its reason.
+    ///
+    /// ```rust
+    /// use errs::{Err, PanicReason};
+    /// use std::panic;
+    ///
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// let payload = panic::catch_unwind(|| panic!("oh no!")).unwrap_err();
+    /// let err = Err::from_panic_payload(payload);
+    /// assert_eq!(err.reason::<PanicReason>().unwrap().message, "oh no!");
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn from_panic_payload(payload: Box<dyn any::Any + Send>) -> Self {
+        let message = match payload.downcast::<&'static str>() {
+            Ok(s) => s.to_string(),
+            Err(payload) => match payload.downcast::<String>() {
+                Ok(s) => *s,
+                Err(_) => "unknown panic".to_string(),
+            },
+        };
+        let loc = Location::caller();
+        let ptr = leak_reason(PanicReason { message });
+        Self::create(loc, ptr, false, Severity::Error)
+    }
+
+    /// Creates a new `Err` instance which represents that something went wrong at the location
+    /// where this function is called.
+    ///
+    /// This constructor is useful when the location itself is the whole story, and a reason is
+    /// just noise. The reason of the created `Err` is of a private type, and both its `Debug`
+    /// and `Display` formats are the location in the form of `file:line:column`.
+    ///
+    /// # Returns
+    /// A new `Err` instance which has the location of the caller as its reason.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// let err = Err::here();
+    /// assert_eq!(err.to_string(), format!("{}:{}:{}", err.file(), err.line(), err.column()));
+    /// ```
+    #[track_caller]
+    pub fn here() -> Self {
+        let loc = Location::caller();
+
+        let boxed = Box::new(ReasonAndSource::<Located>::new(Located(loc)));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, false, Severity::Error)
+    }
+
+    // Creates a new `Err` instance which has the location of the caller as its reason, like
+    // `Err::here`, and the given error as its source. This is used by `Context::locate`.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub(crate) fn here_with_source<E>(source: E) -> Self
+    where
+        E: error::Error + Send + Sync + 'static,
+    {
+        let loc = Location::caller();
+
+        let boxed = Box::new(ReasonAndSource::<Located, E>::with_source(
+            Located(loc),
+            source,
+        ));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, false, Severity::Error)
+    }
+
+    /// Creates a new `Err` instance with the given reason, which contributes its own fields.
+    ///
+    /// The fields returned by [`ReasonFields::fields`] of the reason are added to the fields of
+    /// the created `Err`, and they are also included in the error notified to error handlers.
+    /// For a reason type which does not implement `ReasonFields`, use [`Err::new`].
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the given reason and its fields.
+    ///
+    /// ```rust
+    /// use errs::{Err, ReasonFields};
+    ///
+    /// #[derive(Debug)]
+    /// struct HttpError {
+    ///     status: u16,
+    /// }
+    ///
+    /// impl ReasonFields for HttpError {
+    ///     fn fields(&self) -> Vec<(String, String)> {
+    ///         vec![("status".to_string(), self.status.to_string())]
+    ///     }
+    /// }
+    ///
+    /// let err = Err::new_with_fields(HttpError { status: 404 });
+    /// assert_eq!(err.fields(), &[("status".to_string(), "404".to_string())]);
+    /// assert_eq!(format!("{err}"), "HttpError { status: 404 } [status=404]");
+    /// ```
+    #[track_caller]
+    pub fn new_with_fields<R>(reason: R) -> Self
+    where
+        R: ReasonFields + fmt::Debug + Send + Sync + 'static,
+    {
+        let loc = Location::caller();
+        let fields = reason.fields();
+        let ptr = leak_reason(reason);
+        Self::create_with_fields(loc, ptr, false, Severity::Error, fields)
+    }
+
+    /// Creates a new `Err` instance with the given reason and severity.
+    ///
+    /// Unlike [`Err::with_severity`], the severity given to this constructor is also included
+    /// in the error notified to error handlers.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    /// - `severity`: The severity of the error.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the given reason and severity.
+    ///
+    /// ```rust
+    /// use errs::{Err, Severity};
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     OutOfMemory,
+    /// }
+    ///
+    /// let err = Err::new_with_severity(Reasons::OutOfMemory, Severity::Fatal);
+    /// assert_eq!(err.severity(), Severity::Fatal);
+    /// ```
+    #[track_caller]
+    pub fn new_with_severity<R>(reason: R, severity: Severity) -> Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        let loc = Location::caller();
+
+        let boxed = Box::new(ReasonAndSource::<R>::new(reason));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, false, severity)
+    }
+
+    /// Creates an [`ErrBuilder`] to create an `Err` instance with the given reason.
+    ///
+    /// The severity, fields, and source set to the builder are set before the error is created,
+    /// so they are included in the error notified to error handlers, unlike
+    /// [`Err::with_severity`] and [`Err::with_field`].
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    ///
+    /// # Returns
+    /// A new `ErrBuilder` instance with the given reason.
+    ///
+    /// ```rust
+    /// use errs::{Err, Severity};
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     CacheMiss,
+    /// }
+    ///
+    /// let err = Err::builder(Reasons::CacheMiss)
+    ///     .severity(Severity::Warning)
+    ///     .build();
+    /// assert_eq!(err.severity(), Severity::Warning);
+    /// ```
+    pub fn builder<R>(reason: R) -> ErrBuilder<R>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        ErrBuilder::new(reason)
     }
 
     /// Creates a new `Err` instance with the give reason and underlying source error.
@@ -108,755 +359,6474 @@ impl Err {
     ///
     /// let err = Err::with_source(Reasons::FailToDoSomething, io_error);
     /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     #[track_caller]
     pub fn with_source<R, E>(reason: R, source: E) -> Self
     where
         R: fmt::Debug + Send + Sync + 'static,
         E: error::Error + Send + Sync + 'static,
     {
-        let loc = panic::Location::caller();
+        let loc = Location::caller();
 
         let boxed = Box::new(ReasonAndSource::<R, E>::with_source(reason, source));
         let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
 
-        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
-        {
-            let err_notified = Self {
-                file: loc.file(),
-                line: loc.line(),
-                reason_and_source: SendSyncNonNull::new(ptr),
-            };
-            if let Err(e) = notify::notify_err(err_notified) {
-                eprintln!("ERROR(errs): {e:?}");
-            }
-
-            Self {
-                file: loc.file(),
-                line: loc.line(),
-                reason_and_source: SendSyncNonNull::new(ptr),
-            }
-        }
-        #[cfg(not(any(feature = "notify", feature = "notify-tokio")))]
-        {
-            Self {
-                file: loc.file(),
-                line: loc.line(),
-                reason_and_source: SendSyncNonNull::new(ptr),
-            }
-        }
+        Self::create(loc, ptr, false, Severity::Error)
     }
 
-    /// Gets the name of the source file where the error occurred.
-    #[inline]
-    pub fn file(&self) -> &'static str {
-        self.file
-    }
+    /// Creates a new `Err` instance with the given reason and multiple underlying source errors.
+    ///
+    /// This constructor is useful when the error is caused by more than one error, e.g. a
+    /// transaction which failed to both commit and roll back.
+    /// The sources can be retrieved with [`Err::sources`], and [`Err::source`] returns the first
+    /// one of them.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    /// - `sources`: The underlying source errors that caused the error.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the given reason and source errors.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::{error::Error, io};
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToCommitAndRollback,
+    /// }
+    ///
+    /// let sources: Vec<Box<dyn Error + Send + Sync>> = vec![
+    ///     Box::new(io::Error::other("fail to commit")),
+    ///     Box::new(io::Error::other("fail to rollback")),
+    /// ];
+    ///
+    /// let err = Err::with_sources(Reasons::FailToCommitAndRollback, sources);
+    /// assert_eq!(err.sources().len(), 2);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[track_caller]
+    pub fn with_sources<R, I>(reason: R, sources: I) -> Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+        I: IntoIterator<Item = Box<dyn error::Error + Send + Sync>>,
+    {
+        let loc = Location::caller();
 
-    /// Gets the line number in the source file where the error occurred.
-    #[inline]
-    pub fn line(&self) -> u32 {
-        self.line
-    }
+        let sources = Sources(sources.into_iter().collect());
+        let boxed = Box::new(ReasonAndSource::<R, Sources>::with_sources(reason, sources));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
 
-    /// Gets the source of the error, if any.
-    ///
-    /// This method is equivalent to the `source` method of the `std::error::Error` trait.
-    pub fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-        let source_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).source_fn };
-        source_fn(self.reason_and_source.non_null_ptr)
+        Self::create(loc, ptr, false, Severity::Error)
     }
 
-    /// Attempts to retrieve the error's reason as a specific type.
+    /// Creates a new `Err` instance with the given reason and a shared underlying source error.
     ///
-    /// This method checks whether the stored reason matches the specified type
-    /// and returns a reference to the reason if the type matches.
+    /// This constructor is useful when the source error is already shared, e.g. a cached
+    /// `Arc<io::Error>`. The `Arc` is stored as it is, so multiple `Err` instances can share one
+    /// source error without cloning it. [`Err::source`] returns the error inside the `Arc`.
     ///
     /// # Parameters
-    /// - `R`: The expected type of the reason.
+    /// - `reason`: The reason for the error.
+    /// - `source`: The shared underlying source error that caused the error.
     ///
     /// # Returns
-    /// - `Ok(&R)`: A reference to the reason if it is of the specified type.
-    /// - `Err(&self)`: A reference to this `Err` itself if the reason is not of the specified type.
-    ///
+    /// A new `Err` instance containing the given reason and source error.
     ///
     /// ```rust
     /// use errs::Err;
+    /// use std::{io, sync::Arc};
     ///
     /// #[derive(Debug)]
     /// enum Reasons {
-    ///     IllegalState { state: String },
+    ///     FailToDoSomething,
     /// }
     ///
-    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
-    /// match err.reason::<Reasons>() {
-    ///   Ok(r) => match r {
-    ///     Reasons::IllegalState { state } => println!("state = {state}"),
-    ///     _ => { /* ... */ }
-    ///   }
-    ///   Err(err) => match err.reason::<String>() {
-    ///      Ok(s) => println!("string reason = {s}"),
-    ///      Err(_err) => { /* ... */ }
-    ///   }
-    /// }
+    /// let io_error = Arc::new(io::Error::other("oh no!"));
+    ///
+    /// let err = Err::with_arc_source(Reasons::FailToDoSomething, io_error.clone());
+    /// assert!(err.source().unwrap().downcast_ref::<io::Error>().is_some());
     /// ```
-    pub fn reason<R>(&self) -> Result<&R, &Self>
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[track_caller]
+    pub fn with_arc_source<R>(reason: R, source: Arc<dyn error::Error + Send + Sync>) -> Self
     where
         R: fmt::Debug + Send + Sync + 'static,
     {
-        let type_id = any::TypeId::of::<R>();
-        let ptr = self.reason_and_source.non_null_ptr.as_ptr();
-        let is_fn = unsafe { (*ptr).is_fn };
-        if is_fn(type_id) {
-            let typed_ptr = ptr as *const ReasonAndSource<R>;
-            Ok(unsafe { &((*typed_ptr).reason_and_source.0) })
-        } else {
-            Err(self)
-        }
+        let loc = Location::caller();
+
+        let boxed = Box::new(ReasonAndSource::<R, ArcSource>::with_arc_source(
+            reason, source,
+        ));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, false, Severity::Error)
     }
 
-    /// Executes a function if the error's reason matches a specific type.
+    /// Creates a new `Err` instance with the given reason and a shared `Err` as its source.
     ///
-    /// This method allows you to perform actions based on the type of the error's reason.
-    /// If the reason matches the expected type, the provided function is called with
-    /// a reference to the reason.
+    /// Unlike [`Err::with_source`], the source `Err` is not moved into the created `Err`, so it
+    /// can also be reported independently or be the source of other errors. [`Err::source`]
+    /// returns the `Err` inside the `Arc`.
     ///
     /// # Parameters
-    /// - `R`: The expected type of the reason.
-    /// - `func`: The function to execute if the reason matches the type.
+    /// - `reason`: The reason for the error.
+    /// - `source`: The shared `Err` that caused the error.
     ///
     /// # Returns
-    /// A reference to the current `Err` instance.
-    ///
+    /// A new `Err` instance containing the given reason and source error.
     ///
     /// ```rust
     /// use errs::Err;
+    /// use std::sync::Arc;
     ///
     /// #[derive(Debug)]
     /// enum Reasons {
-    ///     IllegalState { state: String },
+    ///     FailToConnect,
+    ///     FailToLoadUser,
+    ///     FailToLoadItems,
     /// }
     ///
-    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
-    /// err.match_reason::<Reasons>(|r| match r {
-    ///     Reasons::IllegalState { state } => println!("state = {state}"),
-    ///     _ => { /* ... */ }
-    /// })
-    /// .match_reason::<String>(|s| {
-    ///     println!("string reason = {s}");
-    /// });
+    /// let source = Arc::new(Err::new(Reasons::FailToConnect));
+    ///
+    /// let err0 = Err::with_shared_source(Reasons::FailToLoadUser, source.clone());
+    /// let err1 = Err::with_shared_source(Reasons::FailToLoadItems, source.clone());
+    /// assert!(err0.source().unwrap().downcast_ref::<Err>().is_some());
+    /// assert!(err1.source().unwrap().downcast_ref::<Err>().is_some());
+    /// assert!(source.reason::<Reasons>().is_ok());
     /// ```
-    pub fn match_reason<R>(&self, func: fn(&R)) -> &Self
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[track_caller]
+    pub fn with_shared_source<R>(reason: R, source: Arc<Err>) -> Self
     where
         R: fmt::Debug + Send + Sync + 'static,
     {
-        let type_id = any::TypeId::of::<R>();
-        let ptr = self.reason_and_source.non_null_ptr.as_ptr();
-        let is_fn = unsafe { (*ptr).is_fn };
-        if is_fn(type_id) {
-            let typed_ptr = ptr as *const ReasonAndSource<R>;
-            func(unsafe { &((*typed_ptr).reason_and_source.0) });
-        }
-
-        self
-    }
-}
-
-impl Drop for Err {
-    fn drop(&mut self) {
-        let drop_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).drop_fn };
-        drop_fn(self.reason_and_source.non_null_ptr);
+        Self::with_arc_source(reason, source)
     }
-}
 
-impl fmt::Debug for Err {
+    /// Creates a new `Err` instance with the given reason and a closure which creates the
+    /// underlying source error.
+    ///
+    /// The closure is not called until the source error is accessed for the first time, e.g.
+    /// by [`Err::source`], [`Err::chain`], or the `Debug` format. This constructor is useful when
+    /// creating the source error is costly and the error is usually handled by its reason only.
+    ///
+    /// The closure is called at most once, even if the source error is accessed from multiple
+    /// threads at the same time. In that case, the other threads wait until the closure returns.
+    /// If the closure panics, the panic is propagated to the caller which accessed the source
+    /// error, and the source error cannot be created afterwards.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    /// - `source_fn`: The closure which creates the underlying source error.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the given reason and the closure.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::with_source_fn(Reasons::FailToDoSomething, || io::Error::other("oh no!"));
+    /// assert!(err.source().unwrap().downcast_ref::<io::Error>().is_some());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[track_caller]
+    pub fn with_source_fn<R, E, F>(reason: R, source_fn: F) -> Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+        E: error::Error + Send + Sync + 'static,
+        F: FnOnce() -> E + Send + 'static,
+    {
+        let loc = Location::caller();
+
+        let source = LazySource {
+            source: sync::OnceLock::new(),
+            source_fn: sync::Mutex::new(Some(Box::new(move || Box::new(source_fn()) as _))),
+        };
+        let boxed = Box::new(ReasonAndSource::<R, LazySource>::with_lazy_source(
+            reason, source,
+        ));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, false, Severity::Error)
+    }
+
+    /// Creates a new `Err` instance with the given reason, without notifying error handlers.
+    ///
+    /// This constructor is useful for an error which is expected or benign (e.g. a cache miss)
+    /// and should not trigger the registered error handlers.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    ///
+    /// # Returns
+    /// A new silent `Err` instance containing the given reason.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     CacheMiss { key: String },
+    /// }
+    ///
+    /// let err = Err::new_silent(Reasons::CacheMiss { key: "abc".to_string() });
+    /// assert!(err.is_silent());
+    /// ```
+    #[track_caller]
+    pub fn new_silent<R>(reason: R) -> Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        let loc = Location::caller();
+
+        let boxed = Box::new(ReasonAndSource::<R>::new(reason));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, true, Severity::Error)
+    }
+
+    /// Creates a new `Err` instance with the given reason and underlying source error, without
+    /// notifying error handlers.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    /// - `source`: The underlying source error that caused the error.
+    ///
+    /// # Returns
+    /// A new silent `Err` instance containing the given reason and source error.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     CacheMiss,
+    /// }
+    ///
+    /// let io_error = io::Error::other("not cached");
+    ///
+    /// let err = Err::with_source_silent(Reasons::CacheMiss, io_error);
+    /// assert!(err.is_silent());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[track_caller]
+    pub fn with_source_silent<R, E>(reason: R, source: E) -> Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+        E: error::Error + Send + Sync + 'static,
+    {
+        let loc = Location::caller();
+
+        let boxed = Box::new(ReasonAndSource::<R, E>::with_source(reason, source));
+        let ptr = ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, true, Severity::Error)
+    }
+
+    /// Creates a new `Err` instance with the given boxed reason.
+    ///
+    /// This is useful when the reason is a trait object, such as
+    /// `Box<dyn MyTrait + Send + Sync>`, and the concrete type of the reason cannot be named.
+    /// The reason can be retrieved with [`Err::boxed_reason`] by specifying the type inside
+    /// the box.
+    ///
+    /// # Parameters
+    /// - `reason`: The boxed reason for the error.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the given boxed reason.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::fmt;
+    ///
+    /// trait PluginReason: fmt::Debug {
+    ///     fn plugin_name(&self) -> &str;
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// struct Timeout;
+    ///
+    /// impl PluginReason for Timeout {
+    ///     fn plugin_name(&self) -> &str {
+    ///         "timer"
+    ///     }
+    /// }
+    ///
+    /// let reason: Box<dyn PluginReason + Send + Sync> = Box::new(Timeout);
+    /// let err = Err::new_boxed_reason(reason);
+    /// ```
+    #[track_caller]
+    pub fn new_boxed_reason<R>(reason: Box<R>) -> Self
+    where
+        R: ?Sized + Send + Sync + 'static,
+        Box<R>: fmt::Debug,
+    {
+        Self::new(reason)
+    }
+
+    /// Creates a new `Err` instance with the given string as an interned reason.
+    ///
+    /// The string is looked up in or inserted into a global pool, and the reason of the created
+    /// `Err` instance is an `Arc<str>` shared among all errors created with the same string.
+    /// This reduces memory usage when many errors with identical reason strings are created.
+    ///
+    /// The reason can be retrieved with `reason::<Arc<str>>()`.
+    ///
+    /// # Parameters
+    /// - `s`: The string used as the reason for the error.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the interned string as its reason.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::sync::Arc;
+    ///
+    /// let err0 = Err::new_interned("connection refused");
+    /// let err1 = Err::new_interned("connection refused");
+    ///
+    /// let r0 = err0.reason::<Arc<str>>().unwrap();
+    /// let r1 = err1.reason::<Arc<str>>().unwrap();
+    /// assert!(Arc::ptr_eq(r0, r1));
+    /// ```
+    #[cfg(feature = "intern")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "intern")))]
+    #[track_caller]
+    pub fn new_interned(s: &str) -> Self {
+        Self::new(intern::intern(&intern::POOL, s))
+    }
+
+    /// Creates a new `Err` instance with the given reason which implements `Display`.
+    ///
+    /// The `Display` output of an `Err` instance created with [`Err::new`] is the `Debug` output
+    /// of its reason. On the other hand, the `Display` output of an `Err` instance created with
+    /// this function is the `Display` output of its reason.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason for the error.
+    ///
+    /// # Returns
+    /// A new `Err` instance containing the given reason.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FileNotFound { path: String },
+    /// }
+    ///
+    /// impl fmt::Display for Reasons {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         match self {
+    ///             Reasons::FileNotFound { path } => write!(f, "file not found: {path}"),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let err = Err::new_display(Reasons::FileNotFound { path: "/aaa".to_string() });
+    /// assert_eq!(err.to_string(), "file not found: /aaa");
+    /// ```
+    #[track_caller]
+    pub fn new_display<R>(reason: R) -> Self
+    where
+        R: fmt::Debug + fmt::Display + Send + Sync + 'static,
+    {
+        let loc = Location::caller();
+
+        let mut ras = ReasonAndSource::<R>::new(reason);
+        ras.display_fn = display_reason_with_display::<R>;
+        let ptr = ptr::NonNull::from(Box::leak(Box::new(ras))).cast::<ReasonAndSource>();
+
+        Self::create(loc, ptr, false, Severity::Error)
+    }
+
+    fn create(
+        loc: &'static Location<'static>,
+        ptr: ptr::NonNull<ReasonAndSource>,
+        is_silent: bool,
+        severity: Severity,
+    ) -> Self {
+        Self::create_with_fields(loc, ptr, is_silent, severity, Vec::new())
+    }
+
+    pub(crate) fn create_with_fields(
+        loc: &'static Location<'static>,
+        ptr: ptr::NonNull<ReasonAndSource>,
+        is_silent: bool,
+        severity: Severity,
+        fields: Vec<(String, String)>,
+    ) -> Self {
+        Self::create_at(
+            loc.file(),
+            loc.line(),
+            loc.column(),
+            ptr,
+            is_silent,
+            severity,
+            fields,
+        )
+    }
+
+    fn create_at(
+        file: &'static str,
+        line: u32,
+        column: u32,
+        ptr: ptr::NonNull<ReasonAndSource>,
+        is_silent: bool,
+        severity: Severity,
+        fields: Vec<(String, String)>,
+    ) -> Self {
+        #[cfg(feature = "metrics")]
+        metrics::count_err_created();
+
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        notify::count_thread_err();
+
+        // A silent error is not notified, so the creation time is not needed for it.
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        let created_at = if is_silent { None } else { Some(Utc::now()) };
+
+        #[cfg(feature = "std")]
+        let correlation_id = correlation::current_id();
+
+        let meta = ErrMeta {
+            fields,
+            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+            created_at,
+            #[cfg(feature = "std")]
+            correlation_id,
+        };
+
+        let err = Self {
+            file,
+            line,
+            column,
+            is_silent,
+            severity,
+            meta: if meta.is_empty() {
+                None
+            } else {
+                Some(Box::new(meta))
+            },
+            reason_and_source: SendSyncNonNull::new(ptr),
+        };
+
+        // The handlers which may run after this error is returned receive a copy of it, which
+        // shares the reason with this error but not the source.
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        if !is_silent {
+            if let Err(e) = notify::notify_err(&err) {
+                eprintln!("ERROR(errs): {e:?}");
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        if !is_silent {
+            trace::trace_err(&err);
+        }
+
+        #[cfg(feature = "log")]
+        if !is_silent {
+            logging::log_err(&err);
+        }
+
+        err
+    }
+
+    // Creates a copy of this error for the error handlers which may run after this error is
+    // returned. The copy shares the reason with this error, but holds the rendered source chain,
+    // because the source can be taken out of this error while the handlers are running.
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    pub(crate) fn to_notified(&self) -> Self {
+        let ptr = self.reason_and_source.non_null_ptr;
+        self.with_reason_and_source(leak_shared(ptr, render_sources(ptr)))
+    }
+
+    // Creates a copy of this error in the source chain of the copy created by `to_notified`. Its
+    // reason is rendered as well, so that it does not share any data with this error.
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    fn to_rendered(&self) -> Self {
+        let ptr = self.reason_and_source.non_null_ptr;
+        self.with_reason_and_source(render_reason_and_source(ptr))
+    }
+
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    fn with_reason_and_source(&self, ptr: ptr::NonNull<ReasonAndSource>) -> Self {
+        Self {
+            file: self.file,
+            line: self.line,
+            column: self.column,
+            is_silent: self.is_silent,
+            severity: self.severity,
+            meta: self.meta.clone(),
+            reason_and_source: SendSyncNonNull::new(ptr),
+        }
+    }
+
+    /// Gets the name of the source file where the error occurred.
+    #[inline]
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// Gets the line number in the source file where the error occurred.
+    #[inline]
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Gets the column number in the source file where the error occurred.
+    ///
+    /// The column number is included in the output of the alternate `Debug` format (`{:#?}`).
+    #[inline]
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    /// Gets the name of the source file where the error occurred, with forward slashes as
+    /// the path separators on all platforms.
+    ///
+    /// [`Err::file`] returns the path with the platform separators, for example `src\\err.rs`
+    /// on Windows. This method is useful to correlate logs output on different platforms.
+    /// The path is borrowed without allocation if it contains no backslash.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::FailToDoSomething);
+    /// assert!(!err.file_normalized().contains('\\'));
+    /// ```
+    pub fn file_normalized(&self) -> Cow<'static, str> {
+        normalize_path(self.file)
+    }
+
+    /// Returns `true` if this error was created without notifying error handlers.
+    ///
+    /// An error created with [`Err::new_silent`] or [`Err::with_source_silent`] is silent.
+    #[inline]
+    pub fn is_silent(&self) -> bool {
+        self.is_silent
+    }
+
+    /// Returns the time elapsed since this error was created.
+    ///
+    /// This is useful for error handlers which decide to alert only on errors that have been
+    /// left for some time, or for reporting how long an error waited before being handled.
+    ///
+    /// # Returns
+    /// The elapsed time, or `None` if the creation time is not available because this error is
+    /// silent.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::FailToDoSomething);
+    /// assert!(err.age().is_some());
+    ///
+    /// let err = Err::new_silent(Reasons::FailToDoSomething);
+    /// assert!(err.age().is_none());
+    /// ```
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "notify", feature = "notify-tokio"))))]
+    pub fn age(&self) -> Option<Duration> {
+        let created_at = self.meta.as_ref()?.created_at?;
+        Some(Utc::now() - created_at)
+    }
+
+    /// Gets the correlation ID attached to this error.
+    ///
+    /// The correlation ID is the one set with [`set_correlation_id`](crate::set_correlation_id)
+    /// on the thread where this error was created.
+    ///
+    /// # Returns
+    /// The correlation ID, or `None` if no correlation ID was set.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// errs::set_correlation_id(Some("req-123".to_string()));
+    /// let err = Err::new(Reasons::FailToDoSomething);
+    /// assert_eq!(err.correlation_id(), Some("req-123"));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.meta.as_ref()?.correlation_id.as_deref()
+    }
+
+    /// Sets the severity of this error.
+    ///
+    /// Since the severity is set after the error is created, it is not included in the error
+    /// notified to error handlers. To notify the severity, use [`Err::new_with_severity`] or
+    /// [`Err::builder`].
+    ///
+    /// # Parameters
+    /// - `severity`: The severity of the error.
+    ///
+    /// # Returns
+    /// This `Err` instance with the severity set.
+    ///
+    /// ```rust
+    /// use errs::{Err, Severity};
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     CacheMiss,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::CacheMiss).with_severity(Severity::Warning);
+    /// assert_eq!(err.severity(), Severity::Warning);
+    /// ```
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Gets the severity of this error, which is [`Severity::Error`] by default.
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Attaches a key-value field to this error.
+    ///
+    /// The fields are kept in the order they are attached, and are included in the `Debug` and
+    /// `Display` outputs of this error. The value is stored as a string formatted with its
+    /// `Display` implementation.
+    ///
+    /// Since fields are attached after the error is created, they are not included in the
+    /// error notified to error handlers. To notify fields, set them with [`ErrBuilder::field`]
+    /// to the builder created by [`Err::builder`].
+    ///
+    /// # Parameters
+    /// - `key`: The key of the field.
+    /// - `value`: The value of the field.
+    ///
+    /// # Returns
+    /// This `Err` instance with the field attached.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::FailToDoSomething)
+    ///     .with_field("user_id", 42)
+    ///     .with_field("request_id", "abc");
+    /// assert_eq!(err.to_string(), "FailToDoSomething [user_id=42, request_id=abc]");
+    /// ```
+    pub fn with_field<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: fmt::Display,
+    {
+        let meta = self.meta.get_or_insert_with(Default::default);
+        meta.fields.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Gets the key-value fields attached to this error with [`Err::with_field`].
+    #[inline]
+    pub fn fields(&self) -> &[(String, String)] {
+        match &self.meta {
+            Some(meta) => &meta.fields,
+            None => &[],
+        }
+    }
+
+    /// Makes the reason value of this error compared by `Eq` and hashed by `Hash` of `Err`.
+    ///
+    /// By default, two `Err` instances are equal if their reasons are of the same type and they
+    /// are created at the same location. After this method is called with the type of the
+    /// reason, their reason values are also compared. If the reason is not of the type `R`,
+    /// this method does nothing.
+    ///
+    /// # Parameters
+    /// - `R`: The type of the reason, which implements `Eq` and `Hash`.
+    ///
+    /// # Returns
+    /// This `Err` instance whose reason value is compared and hashed.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::collections::HashSet;
+    ///
+    /// #[derive(Debug, PartialEq, Eq, Hash)]
+    /// enum Reasons {
+    ///     FailToOpenFile { path: String },
+    /// }
+    ///
+    /// fn open(path: &str) -> Err {
+    ///     Err::new(Reasons::FailToOpenFile { path: path.to_string() })
+    ///         .with_comparable_reason::<Reasons>()
+    /// }
+    ///
+    /// let mut set = HashSet::new();
+    /// set.insert(open("/aaa"));
+    /// set.insert(open("/aaa"));
+    /// set.insert(open("/bbb"));
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    pub fn with_comparable_reason<R>(self) -> Self
+    where
+        R: Eq + hash::Hash + fmt::Debug + Send + Sync + 'static,
+    {
+        if self.reason_is::<R>() {
+            let ptr = self.reason_and_source.non_null_ptr.as_ptr();
+            unsafe {
+                (*ptr).eq_fn = Some(eq_reason::<R>);
+                (*ptr).hash_fn = Some(hash_reason::<R>);
+            }
+        }
+        self
+    }
+
+    /// Gets the source of the error, if any.
+    ///
+    /// This method is equivalent to the `source` method of the `std::error::Error` trait.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        let source_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).source_fn };
+        source_fn(self.reason_and_source.non_null_ptr)
+    }
+
+    /// Checks whether this error has a source error.
+    ///
+    /// This method is the same as `self.source().is_some()`, and is useful to distinguish a
+    /// leaf error created with [`Err::new`] from a wrapped error created with
+    /// [`Err::with_source`].
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// assert!(!Err::new(Reasons::FailToDoSomething).has_source());
+    /// assert!(Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!")).has_source());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn has_source(&self) -> bool {
+        self.source().is_some()
+    }
+
+    /// Detaches the source of the error and returns its ownership, leaving the reason intact.
+    ///
+    /// After this method is called, [`Err::source`] returns `None`.
+    /// If this error was created with [`Err::with_sources`], all the sources are detached
+    /// together and returned as a single error.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let mut err = Err::with_source(Reasons::FailToDoSomething, std::io::Error::other("oh no!"));
+    /// let source = err.take_source().unwrap();
+    /// assert_eq!(source.to_string(), "oh no!");
+    /// assert!(err.source().is_none());
+    /// assert!(err.reason::<Reasons>().is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn take_source(&mut self) -> Option<Box<dyn error::Error + Send + Sync + 'static>> {
+        let ptr = self.reason_and_source.non_null_ptr;
+
+        let take_source_fn = unsafe { (*ptr.as_ptr()).take_source_fn };
+        take_source_fn(ptr)
+    }
+
+    /// Transforms the source of the error with the given closure, keeping the reason and the
+    /// location.
+    ///
+    /// The current source is taken out in the same way as [`Err::take_source`] and passed to the
+    /// closure, and the source returned by the closure becomes the new source. This is useful to
+    /// wrap or replace the source, e.g. to anonymize an `io::Error` into a generic message, or
+    /// to clear it by returning `None`. The closure is always called, even while the error
+    /// handlers notified of this error are running, because they receive a copy of this error.
+    ///
+    /// # Parameters
+    /// - `f`: The closure which takes the current source and returns the new source.
+    ///
+    /// # Returns
+    /// This `Err` instance with the new source.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToReadFile,
+    /// }
+    ///
+    /// let err = Err::with_source(
+    ///     Reasons::FailToReadFile,
+    ///     io::Error::other("/home/alice/secret.txt is not found"),
+    /// );
+    /// let err = err.map_source(|src| src.map(|_| io::Error::other("file is not found").into()));
+    /// assert_eq!(err.source().unwrap().to_string(), "file is not found");
+    /// assert!(err.reason::<Reasons>().is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn map_source<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(
+            Option<Box<dyn error::Error + Send + Sync>>,
+        ) -> Option<Box<dyn error::Error + Send + Sync>>,
+    {
+        let ptr = self.reason_and_source.non_null_ptr;
+
+        let source = f(self.take_source());
+
+        // While the reason is shared with error handlers, it cannot be moved into a new
+        // `ReasonAndSource`, so a new one which shares the reason is created instead.
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        if is_shared(ptr) {
+            let new_ptr = leak_shared(ptr, source);
+            release_reason_and_source(ptr);
+            self.reason_and_source = SendSyncNonNull::new(new_ptr);
+            return self;
+        }
+
+        // The old `ReasonAndSource` is deallocated by `replace_source_fn`.
+        let replace_source_fn = unsafe { (*ptr.as_ptr()).replace_source_fn };
+        let new_ptr = replace_source_fn(ptr, source);
+
+        self.reason_and_source = SendSyncNonNull::new(new_ptr);
+        self
+    }
+
+    /// Splits this error into the `Display` string of its reason and its source, consuming this
+    /// error.
+    ///
+    /// This is useful to convert this error into a foreign error type. The string is the same as
+    /// [`Err::display_string`], and the source is the same as the one returned by
+    /// [`Err::take_source`].
+    ///
+    /// # Returns
+    /// A tuple of the `Display` string of the reason and the source of this error.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!"));
+    /// let (reason, source) = err.split();
+    /// assert_eq!(reason, "FailToDoSomething");
+    /// assert_eq!(source.unwrap().to_string(), "oh no!");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn split(
+        mut self,
+    ) -> (
+        String,
+        Option<Box<dyn error::Error + Send + Sync + 'static>>,
+    ) {
+        let reason = self.display_string();
+        let source = self.take_source();
+        (reason, source)
+    }
+
+    /// Replaces the reason of this error with [`Redacted`], consuming this error.
+    ///
+    /// This is useful to pass an error across a trust boundary, e.g. to external-facing logs,
+    /// when its reason carries sensitive data. The location, severity, and source of this error
+    /// are kept, but the original reason and the fields attached with [`Err::with_field`] are
+    /// dropped and can no longer be retrieved.
+    ///
+    /// # Returns
+    /// An `Err` instance with `Redacted` as its reason.
+    ///
+    /// ```rust
+    /// use errs::{Err, Redacted};
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToOpenFile { path: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::FailToOpenFile { path: "/home/alice/secret.txt".to_string() });
+    /// let err = err.redacted();
+    /// assert!(err.reason::<Reasons>().is_err());
+    /// assert!(err.reason_is::<Redacted>());
+    /// assert_eq!(format!("{err}"), "<redacted>");
+    /// ```
+    pub fn redacted(mut self) -> Self {
+        #[cfg(feature = "std")]
+        let ptr = leak_reason_and_taken_source(Redacted, self.take_source());
+        #[cfg(not(feature = "std"))]
+        let ptr = leak_reason(Redacted);
+
+        // The fields are dropped as well as the reason, because they are also displayed.
+        let mut meta = self.meta.take();
+        if let Some(meta) = meta.as_mut() {
+            drop(mem::take(&mut meta.fields));
+        }
+
+        Self {
+            file: self.file,
+            line: self.line,
+            column: self.column,
+            is_silent: self.is_silent,
+            severity: self.severity,
+            meta,
+            reason_and_source: SendSyncNonNull::new(ptr),
+        }
+    }
+
+    /// Gets the source errors attached with [`Err::with_sources`].
+    ///
+    /// If this error was created with another constructor, this method returns an empty slice
+    /// even if the error has a source. In that case, use [`Err::source`] instead.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn sources(&self) -> &[Box<dyn error::Error + Send + Sync>] {
+        let sources_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).sources_fn };
+        sources_fn(self.reason_and_source.non_null_ptr)
+    }
+
+    /// Attempts to retrieve the error's reason as a specific type.
+    ///
+    /// This method checks whether the stored reason matches the specified type
+    /// and returns a reference to the reason if the type matches.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// - `Ok(&R)`: A reference to the reason if it is of the specified type.
+    /// - `Err(&self)`: A reference to this `Err` itself if the reason is not of the specified type.
+    ///
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// match err.reason::<Reasons>() {
+    ///   Ok(r) => match r {
+    ///     Reasons::IllegalState { state } => println!("state = {state}"),
+    ///     _ => { /* ... */ }
+    ///   }
+    ///   Err(err) => match err.reason::<String>() {
+    ///      Ok(s) => println!("string reason = {s}"),
+    ///      Err(_err) => { /* ... */ }
+    ///   }
+    /// }
+    /// ```
+    pub fn reason<R>(&self) -> Result<&R, &Self>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        // The reason is got through `as_any_fn`, because the offset of the reason in
+        // `ReasonAndSource<R, E>` depends on the alignment of the source type `E`, which is
+        // unknown here.
+        match self.reason_any().downcast_ref::<R>() {
+            Some(r) => Ok(r),
+            None => Err(self),
+        }
+    }
+
+    /// Gets the error's reason as a specific type, or an error which can be propagated with `?`.
+    ///
+    /// Unlike [`Err::reason`], the error of the returned `Result` is a [`ReasonMismatchError`],
+    /// which carries the type names of the expected and actual reasons.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// - `Ok(&R)`: A reference to the reason if it is of the specified type.
+    /// - `Err(ReasonMismatchError)`: An error if the reason is not of the specified type.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// # #[cfg(feature = "std")]
+    /// fn state_of(err: &Err) -> Result<String, Box<dyn std::error::Error>> {
+    ///     let Reasons::IllegalState { state } = err.reason_result::<Reasons>()?;
+    ///     Ok(state.clone())
+    /// }
+    ///
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// assert_eq!(state_of(&err).unwrap(), "bad state");
+    ///
+    /// let err = Err::new("unknown error");
+    /// assert!(state_of(&err).is_err());
+    /// # }
+    /// ```
+    pub fn reason_result<R>(&self) -> Result<&R, ReasonMismatchError>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        self.reason::<R>()
+            .map_err(|err| ReasonMismatchError::new(any::type_name::<R>(), err.reason_type_name()))
+    }
+
+    /// Checks whether the error's reason is of the specified type and equal to the given value.
+    ///
+    /// This method is useful for assertions in tests and for dispatching on a specific reason.
+    ///
+    /// # Parameters
+    /// - `expected`: The value to be compared with the reason.
+    ///
+    /// # Returns
+    /// `true` if the reason is of type `R` and equal to `expected`, otherwise `false`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Reasons {
+    ///     NotFound,
+    ///     PermissionDenied,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::NotFound);
+    /// assert!(err.reason_eq(&Reasons::NotFound));
+    /// assert!(!err.reason_eq(&Reasons::PermissionDenied));
+    /// ```
+    pub fn reason_eq<R>(&self, expected: &R) -> bool
+    where
+        R: PartialEq + fmt::Debug + Send + Sync + 'static,
+    {
+        self.reason::<R>().is_ok_and(|r| r == expected)
+    }
+
+    /// Gets a mutable reference to the error's reason as a specific type.
+    ///
+    /// This method is useful to add contextual information to the reason after this error is
+    /// created, before it is returned to the caller.
+    ///
+    /// While the error handlers which may run after this error is returned, e.g. the async
+    /// handlers, still hold the copy of this error, the reason is shared with them and this
+    /// method returns `None`.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// - `Some(&mut R)`: A mutable reference to the reason if it is of the specified type.
+    /// - `None`: If the reason is not of the specified type, or is shared with error handlers.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToLoadConfig { notes: Vec<String> },
+    /// }
+    ///
+    /// let mut err = Err::new(Reasons::FailToLoadConfig { notes: Vec::new() });
+    /// if let Some(Reasons::FailToLoadConfig { notes }) = err.reason_mut::<Reasons>() {
+    ///     notes.push("while starting".to_string());
+    /// }
+    /// ```
+    pub fn reason_mut<R>(&mut self) -> Option<&mut R>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        let ptr = self.reason_and_source.non_null_ptr;
+
+        let as_any_mut_fn = unsafe { (*ptr.as_ptr()).as_any_mut_fn };
+        as_any_mut_fn(ptr)?.downcast_mut::<R>()
+    }
+
+    /// Gets the error's reason as a specific type, or panics if the reason is not of the type.
+    ///
+    /// This method is intended for tests and prototypes, where the reason type is known and
+    /// a mismatch is a bug. Use [`Err::reason`] in other cases.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// A reference to the reason.
+    ///
+    /// # Panics
+    /// Panics if the reason is not of the specified type, with a message including the
+    /// expected and actual reason type names.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// let Reasons::IllegalState { state } = err.reason_unwrap::<Reasons>();
+    /// assert_eq!(state, "bad state");
+    /// ```
+    #[track_caller]
+    pub fn reason_unwrap<R>(&self) -> &R
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        match self.reason::<R>() {
+            Ok(r) => r,
+            Err(_) => panic!(
+                "called `Err::reason_unwrap()` with a wrong reason type: expected `{}`, actual `{}`",
+                any::type_name::<R>(),
+                self.reason_type_name(),
+            ),
+        }
+    }
+
+    /// Checks whether the error's reason is of the specified type.
+    ///
+    /// To check against multiple candidate types at once, use the
+    /// [`reason_matches!`](crate::reason_matches) macro. [`Err::is`] is an alias of this method.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// - `true` if the reason is of the specified type, otherwise `false`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// assert!(err.reason_is::<Reasons>());
+    /// assert!(!err.reason_is::<String>());
+    /// ```
+    pub fn reason_is<R>(&self) -> bool
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        let is_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).is_fn };
+        is_fn(self.reason_and_source.non_null_ptr, any::TypeId::of::<R>())
+    }
+
+    /// Checks whether the error's reason is of the specified type.
+    ///
+    /// This method is an alias of [`Err::reason_is`], which is the canonical one, and is named
+    /// after `std::error::Error::is` for familiarity.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// - `true` if the reason is of the specified type, otherwise `false`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// assert!(err.is::<Reasons>());
+    /// assert!(!err.is::<String>());
+    /// ```
+    #[inline]
+    pub fn is<R>(&self) -> bool
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        self.reason_is::<R>()
+    }
+
+    /// Attempts to take the error's reason out as a specific type, consuming this error.
+    ///
+    /// This method is the analog of `downcast` of `Box<dyn std::error::Error>` for the reason.
+    /// The source of this error is dropped if the reason is taken out.
+    ///
+    /// While the error handlers which may run after this error is returned, e.g. the async
+    /// handlers, still hold the copy of this error, the reason is shared with them and cannot
+    /// be taken out.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// - `Ok(R)`: The reason if it is of the specified type.
+    /// - `Err(Self)`: This `Err` itself if the reason is not of the specified type, or is shared
+    ///   with error handlers.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// let err = match err.downcast_reason::<String>() {
+    ///     Ok(_) => panic!(),
+    ///     Err(err) => err,
+    /// };
+    /// match err.downcast_reason::<Reasons>() {
+    ///     Ok(Reasons::IllegalState { state }) => assert_eq!(state, "bad state"),
+    ///     Err(_) => panic!(),
+    /// }
+    /// ```
+    pub fn downcast_reason<R>(self) -> Result<R, Self>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        if !self.reason_is::<R>() {
+            return Err(self);
+        }
+
+        let ptr = self.reason_and_source.non_null_ptr;
+
+        let mut reason = mem::MaybeUninit::<R>::uninit();
+        let move_reason_fn = unsafe { (*ptr.as_ptr()).move_reason_fn };
+        if !move_reason_fn(ptr, ptr::NonNull::from(&mut reason).cast::<()>()) {
+            return Err(self);
+        }
+
+        // The `Drop` of this error must not run, because the `ReasonAndSource` is deallocated
+        // by `move_reason_fn`.
+        let mut this = mem::ManuallyDrop::new(self);
+        drop(this.meta.take());
+        Ok(unsafe { reason.assume_init() })
+    }
+
+    /// Attempts to retrieve the content of the error's boxed reason as a specific type.
+    ///
+    /// This method is used for the reason created with [`Err::new_boxed_reason`], and returns
+    /// a reference to the value inside the box. When the reason is a trait object, `R` is the
+    /// trait object type including its auto trait bounds, e.g. `dyn MyTrait + Send + Sync`.
+    ///
+    /// # Returns
+    /// - `Ok(&R)`: A reference to the value inside the boxed reason if the reason is `Box<R>`.
+    /// - `Err(&self)`: A reference to the current `Err` instance if the reason is not `Box<R>`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::fmt;
+    ///
+    /// trait PluginReason: fmt::Debug {
+    ///     fn plugin_name(&self) -> &str;
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// struct Timeout;
+    ///
+    /// impl PluginReason for Timeout {
+    ///     fn plugin_name(&self) -> &str {
+    ///         "timer"
+    ///     }
+    /// }
+    ///
+    /// let reason: Box<dyn PluginReason + Send + Sync> = Box::new(Timeout);
+    /// let err = Err::new_boxed_reason(reason);
+    ///
+    /// match err.boxed_reason::<dyn PluginReason + Send + Sync>() {
+    ///     Ok(r) => assert_eq!(r.plugin_name(), "timer"),
+    ///     Err(err) => panic!("unexpected reason: {err:?}"),
+    /// }
+    /// ```
+    pub fn boxed_reason<R>(&self) -> Result<&R, &Self>
+    where
+        R: ?Sized + Send + Sync + 'static,
+        Box<R>: fmt::Debug,
+    {
+        self.reason::<Box<R>>().map(|boxed| boxed.as_ref())
+    }
+
+    /// Executes a function if the error's reason matches a specific type.
+    ///
+    /// This method allows you to perform actions based on the type of the error's reason.
+    /// If the reason matches the expected type, the provided function is called with
+    /// a reference to the reason.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    /// - `func`: The function to execute if the reason matches the type.
+    ///
+    /// # Returns
+    /// A reference to the current `Err` instance.
+    ///
+    /// Since `func` is a function pointer, a closure which captures variables cannot be passed
+    /// to this method. Use [`Err::match_reason_with`] for such closures.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// err.match_reason::<Reasons>(|r| match r {
+    ///     Reasons::IllegalState { state } => println!("state = {state}"),
+    ///     _ => { /* ... */ }
+    /// })
+    /// .match_reason::<String>(|s| {
+    ///     println!("string reason = {s}");
+    /// });
+    /// ```
+    pub fn match_reason<R>(&self, func: fn(&R)) -> &Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        if let Ok(r) = self.reason::<R>() {
+            func(r);
+        }
+
+        self
+    }
+
+    /// Executes a closure if the error's reason matches a specific type.
+    ///
+    /// This method is the same as [`Err::match_reason`], except that it accepts a closure which
+    /// captures variables.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    /// - `func`: The closure to execute if the reason matches the type.
+    ///
+    /// # Returns
+    /// A reference to the current `Err` instance.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let mut states = Vec::new();
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// err.match_reason_with::<Reasons, _>(|r| match r {
+    ///     Reasons::IllegalState { state } => states.push(state.clone()),
+    /// });
+    ///
+    /// assert_eq!(states, vec!["bad state".to_string()]);
+    /// ```
+    pub fn match_reason_with<R, F>(&self, func: F) -> &Self
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+        F: FnOnce(&R),
+    {
+        if let Ok(r) = self.reason::<R>() {
+            func(r);
+        }
+
+        self
+    }
+
+    /// Returns the value computed from the error's reason if it is of a specific type, or the
+    /// given default value otherwise.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    /// - `default`: The value returned if the reason is not of type `R`.
+    /// - `f`: The function to compute the value from the reason if it is of type `R`.
+    ///
+    /// # Returns
+    /// The value returned by `f` if the reason is of type `R`, otherwise `default`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// struct HttpErr {
+    ///     status: u16,
+    /// }
+    ///
+    /// let err = Err::new(HttpErr { status: 404 });
+    /// let code = err.fold_reason::<HttpErr, _>(500, |e| e.status);
+    /// assert_eq!(code, 404);
+    ///
+    /// let err = Err::new("unknown error");
+    /// let code = err.fold_reason::<HttpErr, _>(500, |e| e.status);
+    /// assert_eq!(code, 500);
+    /// ```
+    pub fn fold_reason<R, T>(&self, default: T, f: impl FnOnce(&R) -> T) -> T
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        match self.reason::<R>() {
+            Ok(r) => f(r),
+            Err(_) => default,
+        }
+    }
+
+    /// Returns a reference to the error's reason if it is of a specific type, or the given
+    /// default reference otherwise.
+    ///
+    /// Unlike [`Err::fold_reason`], this method returns a reference to an existing value
+    /// rather than computing one, so that a caller can always match on a valid reason.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    /// - `default`: The reference returned if the reason is not of type `R`.
+    ///
+    /// # Returns
+    /// A reference to the reason if it is of type `R`, otherwise `default`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Reasons {
+    ///     NotFound,
+    ///     Unknown,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::NotFound);
+    /// assert_eq!(err.reason_or(&Reasons::Unknown), &Reasons::NotFound);
+    ///
+    /// let err = Err::new("unknown error");
+    /// assert_eq!(err.reason_or(&Reasons::Unknown), &Reasons::Unknown);
+    /// ```
+    pub fn reason_or<'a, R>(&'a self, default: &'a R) -> &'a R
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        self.reason::<R>().unwrap_or(default)
+    }
+
+    /// Gets the error's reason as a `&dyn Any`.
+    ///
+    /// This method is useful for implementing custom dynamic dispatch over reasons, since the
+    /// returned reference can be downcast with `downcast_ref` as many times as needed.
+    ///
+    /// # Returns
+    /// A reference to the reason as `&dyn Any`, which lives as long as this `Err`.
+    ///
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState { state: String },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState { state: "bad state".to_string() });
+    /// let any = err.reason_any();
+    /// if let Some(s) = any.downcast_ref::<String>() {
+    ///     println!("string reason = {s}");
+    /// } else if let Some(Reasons::IllegalState { state }) = any.downcast_ref::<Reasons>() {
+    ///     println!("state = {state}");
+    /// }
+    /// ```
+    pub fn reason_any(&self) -> &dyn any::Any {
+        let as_any_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).as_any_fn };
+        as_any_fn(self.reason_and_source.non_null_ptr)
+    }
+
+    fn reason_type_id(&self) -> any::TypeId {
+        any::Any::type_id(self.reason_any())
+    }
+
+    /// Gets the error's reason as a reference of type `T` through an adapter registered with
+    /// [`register_reason_adapter`](crate::register_reason_adapter).
+    ///
+    /// This is useful when multiple reason types implement a shared trait and the reason is
+    /// needed as the trait object regardless of its concrete type.
+    ///
+    /// # Returns
+    /// - `Some(&T)`: The reason converted by the adapter registered for the reason type and `T`.
+    /// - `None`: If no adapter is registered for the reason type and `T`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// trait AppError {
+    ///     fn code(&self) -> u32;
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// enum DbErr {
+    ///     ConnectionLost,
+    /// }
+    ///
+    /// impl AppError for DbErr {
+    ///     fn code(&self) -> u32 {
+    ///         500
+    ///     }
+    /// }
+    ///
+    /// errs::register_reason_adapter::<DbErr, dyn AppError>(|r| r);
+    ///
+    /// let err = Err::new(DbErr::ConnectionLost);
+    /// match err.reason_as::<dyn AppError>() {
+    ///     Some(e) => assert_eq!(e.code(), 500),
+    ///     None => panic!(),
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn reason_as<T>(&self) -> Option<&T>
+    where
+        T: ?Sized + 'static,
+    {
+        adapter::adapt::<T>(&adapter::ADAPTERS, self.reason_any())
+    }
+
+    /// Gets the type name of the error's reason.
+    ///
+    /// The returned name is the same as `std::any::type_name` of the reason type.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState);
+    /// assert!(err.reason_type_name().ends_with("Reasons"));
+    /// ```
+    pub fn reason_type_name(&self) -> &'static str {
+        let type_name_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).type_name_fn };
+        type_name_fn(self.reason_and_source.non_null_ptr)
+    }
+
+    /// Gets the last segment of the type path of the error's reason.
+    ///
+    /// The module path is removed from [`Err::reason_type_name`], e.g. `Reasons` is returned
+    /// for `mycrate::io::Reasons`. For a generic type, the type arguments are kept as they are,
+    /// e.g. `Wrapper<alloc::string::String>` is returned for
+    /// `mycrate::Wrapper<alloc::string::String>`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// mod io {
+    ///     #[derive(Debug)]
+    ///     pub enum Reasons {
+    ///         FailToRead,
+    ///     }
+    /// }
+    ///
+    /// let err = Err::new(io::Reasons::FailToRead);
+    /// assert_eq!(err.reason_type_short_name(), "Reasons");
+    /// ```
+    pub fn reason_type_short_name(&self) -> &'static str {
+        short_type_name(self.reason_type_name())
+    }
+
+    /// Gets a numeric code derived from the type of the error's reason.
+    ///
+    /// This code is computed with the FNV-1a hash of [`Err::reason_type_name`], so errors whose
+    /// reasons are of the same type always have the same code.
+    ///
+    /// The code is stable across runs of the same binary, but it may change if the path of the
+    /// reason type changes or the binary is built with a different compiler version.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     IllegalState,
+    ///     NotFound,
+    /// }
+    ///
+    /// let err0 = Err::new(Reasons::IllegalState);
+    /// let err1 = Err::new(Reasons::NotFound);
+    /// assert_eq!(err0.reason_code(), err1.reason_code());
+    /// ```
+    pub fn reason_code(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        self.reason_type_name()
+            .bytes()
+            .fold(FNV_OFFSET_BASIS, |hash, b| {
+                (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Gets a clone of the error's reason if it is of a specific type, or the default value of
+    /// the type otherwise.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// A clone of the reason if it is of the specified type, otherwise `R::default()`.
+    ///
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug, Clone, Default, PartialEq)]
+    /// enum Reasons {
+    ///     #[default]
+    ///     Unknown,
+    ///     IllegalState,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::IllegalState);
+    /// assert_eq!(err.reason_cloned_or_default::<Reasons>(), Reasons::IllegalState);
+    ///
+    /// let err = Err::new("illegal state".to_string());
+    /// assert_eq!(err.reason_cloned_or_default::<Reasons>(), Reasons::Unknown);
+    /// ```
+    pub fn reason_cloned_or_default<R>(&self) -> R
+    where
+        R: fmt::Debug + Clone + Default + Send + Sync + 'static,
+    {
+        match self.reason::<R>() {
+            Ok(r) => r.clone(),
+            Err(_) => R::default(),
+        }
+    }
+
+    /// Searches the error's source chain for a reason of a specific type.
+    ///
+    /// This method first checks the reason of this `Err` itself, then walks its source chain,
+    /// and for each source which is an `Err`, checks its reason.
+    /// The first reason that matches the specified type is returned.
+    ///
+    /// # Parameters
+    /// - `R`: The expected type of the reason.
+    ///
+    /// # Returns
+    /// - `Some(&R)`: A reference to the first reason found in the chain of the specified type.
+    /// - `None`: If no `Err` in the chain has a reason of the specified type.
+    ///
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum IoReasons {
+    ///     FileNotFound { path: String },
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// enum AppReasons {
+    ///     FailToLoadConfig,
+    /// }
+    ///
+    /// let inner = Err::new(IoReasons::FileNotFound { path: "/aaa/bbb".to_string() });
+    /// let err = Err::with_source(AppReasons::FailToLoadConfig, inner);
+    ///
+    /// match err.reason_in_chain::<IoReasons>() {
+    ///     Some(IoReasons::FileNotFound { path }) => println!("path = {path}"),
+    ///     None => { /* ... */ }
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn reason_in_chain<R>(&self) -> Option<&R>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        self.chain()
+            .filter_map(|e| e.downcast_ref::<Err>())
+            .find_map(|err| err.reason::<R>().ok())
+    }
+
+    /// Returns an iterator over all reasons of the specified type in the chain of errors.
+    ///
+    /// This method walks the chain from this error in the same way as
+    /// [`Err::reason_in_chain`], but yields every reason of type `R` instead of only the first
+    /// one. Non-`errs::Err` errors in the chain are skipped.
+    ///
+    /// # Parameters
+    /// - `R`: The type of the reasons to be yielded.
+    ///
+    /// # Returns
+    /// An iterator over the references to the reasons of type `R`, from this error to its
+    /// deepest source.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// struct HttpError {
+    ///     status: u16,
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// enum AppReasons {
+    ///     FailToSync,
+    /// }
+    ///
+    /// let err = Err::new(HttpError { status: 503 });
+    /// let err = Err::with_source(HttpError { status: 502 }, err);
+    /// let err = Err::with_source(AppReasons::FailToSync, err);
+    ///
+    /// let statuses: Vec<u16> = err.reasons::<HttpError>().map(|e| e.status).collect();
+    /// assert_eq!(statuses, [502, 503]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn reasons<R>(&self) -> impl Iterator<Item = &R>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        self.chain()
+            .filter_map(|e| e.downcast_ref::<Err>())
+            .filter_map(|err| err.reason::<R>().ok())
+    }
+
+    /// Returns an iterator over the reasons of the `Err` instances in the chain of this error.
+    ///
+    /// This method walks the chain of this error as [`Err::chain`] does, and yields a
+    /// [`ReasonInfo`] for each error which is an `Err`. The other errors in the chain are
+    /// skipped.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum IoReasons {
+    ///     FileNotFound { path: String },
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// enum AppReasons {
+    ///     FailToLoadConfig,
+    /// }
+    ///
+    /// let inner = Err::new(IoReasons::FileNotFound { path: "/aaa/bbb".to_string() });
+    /// let err = Err::with_source(AppReasons::FailToLoadConfig, inner);
+    ///
+    /// for info in err.reason_chain() {
+    ///     println!("{}: {}", info.type_name, info.debug);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn reason_chain(&self) -> impl Iterator<Item = ReasonInfo<'_>> {
+        self.chain()
+            .filter_map(|e| e.downcast_ref::<Err>())
+            .map(|err| ReasonInfo {
+                err,
+                type_name: err.reason_type_name(),
+                debug: err.reason_debug_string(),
+            })
+    }
+
+    /// Checks whether the `Debug` representation of the error's reason equals the given string.
+    ///
+    /// Only the reason is compared, and the location of the error is not included. This is
+    /// useful for assertions in tests where the reason type cannot be imported.
+    ///
+    /// # Parameters
+    /// - `expected`: The expected `Debug` representation of the reason.
+    ///
+    /// # Returns
+    /// `true` if the `Debug` representation of the reason equals `expected`, otherwise `false`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     InvalidValue { value: i32 },
+    /// }
+    ///
+    /// let err = Err::new(Reasons::InvalidValue { value: 1 });
+    /// assert!(err.reason_debug_eq("InvalidValue { value: 1 }"));
+    /// assert!(!err.reason_debug_eq("InvalidValue { value: 2 }"));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn reason_debug_eq(&self, expected: &str) -> bool {
+        self.reason_debug_string() == expected
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn reason_debug_string(&self) -> String {
+        struct ReasonDebug<'a>(&'a Err);
+
+        impl fmt::Debug for ReasonDebug<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let ptr = self.0.reason_and_source.non_null_ptr;
+                let debug_reason_fn = unsafe { (*ptr.as_ptr()).debug_reason_fn };
+                debug_reason_fn(ptr, f)
+            }
+        }
+
+        format!("{:?}", ReasonDebug(self))
+    }
+
+    /// Returns an iterator over this error and its chain of source errors.
+    ///
+    /// The first element of the iterator is this `Err` itself, and each subsequent element is
+    /// the source of the previous one.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!"));
+    /// for e in err.chain() {
+    ///     println!("{e}");
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn chain(&self) -> Chain<'_> {
+        Chain::new(self)
+    }
+
+    /// Checks whether the chain of this error is equivalent to the chain of the other error.
+    ///
+    /// This method is a helper for tests. The errors in both chains returned by [`Err::chain`]
+    /// are compared pairwise: an `Err` is compared by the type and the `Debug` string of its
+    /// reason, ignoring its location, and any other error is compared by its `Debug` string.
+    ///
+    /// # Parameters
+    /// - `other`: The error to be compared with.
+    ///
+    /// # Returns
+    /// `true` if both chains have the same length and all the pairs of errors are equivalent,
+    /// otherwise `false`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err0 = Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!"));
+    /// let err1 = Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!"));
+    /// assert!(err0.chain_eq(&err1));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn chain_eq(&self, other: &Err) -> bool {
+        let mut chain0 = self.chain();
+        let mut chain1 = other.chain();
+        loop {
+            match (chain0.next(), chain1.next()) {
+                (None, None) => return true,
+                (Some(e0), Some(e1)) => {
+                    let is_eq = match (e0.downcast_ref::<Err>(), e1.downcast_ref::<Err>()) {
+                        (Some(err0), Some(err1)) => {
+                            err0.reason_type_name() == err1.reason_type_name()
+                                && err0.reason_debug_string() == err1.reason_debug_string()
+                        }
+                        (None, None) => format!("{e0:?}") == format!("{e1:?}"),
+                        _ => false,
+                    };
+                    if !is_eq {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    /// Returns the deepest source error in the chain of this error.
+    ///
+    /// If this error has no source, this method returns this `Err` itself.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToReadFile,
+    ///     FailToLoadConfig,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToReadFile, io::Error::other("oh no!"));
+    /// let err = Err::with_source(Reasons::FailToLoadConfig, err);
+    /// assert!(err.root_cause().is::<io::Error>());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn root_cause(&self) -> &(dyn error::Error + 'static) {
+        self.chain().last().unwrap_or(self)
+    }
+
+    /// Checks whether any error in the source chain of this error is of the specified type.
+    ///
+    /// This error itself is not checked, only its source and the sources of the source, and so
+    /// on.
+    ///
+    /// # Parameters
+    /// - `E`: The error type to look for.
+    ///
+    /// # Returns
+    /// - `true` if an error of the specified type is found in the source chain.
+    /// - `false` otherwise.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToReadFile,
+    ///     FailToLoadConfig,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToReadFile, io::Error::other("oh no!"));
+    /// let err = Err::with_source(Reasons::FailToLoadConfig, err);
+    /// if err.is_caused_by::<io::Error>() {
+    ///     // retry
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn is_caused_by<E>(&self) -> bool
+    where
+        E: error::Error + 'static,
+    {
+        self.chain().skip(1).any(|e| e.is::<E>())
+    }
+
+    /// Gets the number of errors in the chain of this error, including this error itself.
+    ///
+    /// This is the same as the number of elements of [`Err::chain`], and is at least 1.
+    /// It is useful to detect a deeply wrapped error, which might indicate a rethrow loop.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToReadFile,
+    ///     FailToLoadConfig,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToReadFile, io::Error::other("oh no!"));
+    /// let err = Err::with_source(Reasons::FailToLoadConfig, err);
+    /// assert_eq!(err.chain_len(), 3);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn chain_len(&self) -> usize {
+        let mut len = 1;
+        let mut next = self.source();
+        while let Some(e) = next {
+            len += 1;
+            next = e.source();
+        }
+        len
+    }
+
+    /// Returns a wrapper which displays this error and its source chain on a single line.
+    ///
+    /// The `Display` output of each error in the chain is joined with `": "`.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!"));
+    /// assert_eq!(err.display_chain().to_string(), "FailToDoSomething: oh no!");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn display_chain(&self) -> DisplayChain<'_> {
+        DisplayChain::new(self, ": ")
+    }
+
+    /// Returns a wrapper which displays this error and its source chain on a single line,
+    /// joined with the given separator.
+    ///
+    /// # Parameters
+    /// - `sep`: The separator placed between the `Display` outputs of the errors in the chain.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!"));
+    /// assert_eq!(err.display_chain_with(" <- ").to_string(), "FailToDoSomething <- oh no!");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn display_chain_with<'a>(&'a self, sep: &'a str) -> DisplayChain<'a> {
+        DisplayChain::new(self, sep)
+    }
+
+    /// Returns the `Display` output of this error as an owned `String`.
+    ///
+    /// This is equivalent to `format!("{}", err)`, but the length of the output is measured
+    /// beforehand so that the string is allocated exactly once.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::FailToDoSomething);
+    /// assert_eq!(err.display_string(), "FailToDoSomething");
+    /// ```
+    pub fn display_string(&self) -> String {
+        format_once(format_args!("{}", self))
+    }
+
+    /// Returns the `Debug` output of this error as an owned `String`.
+    ///
+    /// This is equivalent to `format!("{:?}", err)`, but the length of the output is measured
+    /// beforehand so that the string is allocated exactly once.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::FailToDoSomething);
+    /// assert!(err.debug_string().starts_with("errs::Err { reason = "));
+    /// ```
+    pub fn debug_string(&self) -> String {
+        format_once(format_args!("{:?}", self))
+    }
+
+    /// Returns a one-line message which consists of the `Display` output of this error and the
+    /// location where it occurred, in the format `reason (file:line)`.
+    ///
+    /// This is useful for operator-facing messages, e.g. in logs or on a console.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::new(Reasons::FailToDoSomething);
+    /// assert_eq!(
+    ///     err.context_string(),
+    ///     format!("FailToDoSomething ({}:{})", err.file(), err.line()),
+    /// );
+    /// ```
+    pub fn context_string(&self) -> String {
+        format_once(format_args!("{} ({}:{})", self, self.file, self.line))
+    }
+}
+
+fn normalize_path(file: &'static str) -> Cow<'static, str> {
+    if file.contains('\\') {
+        Cow::Owned(file.replace('\\', "/"))
+    } else {
+        Cow::Borrowed(file)
+    }
+}
+
+// Removes the module path from the type name. Only the path before the type arguments or other
+// non-path parts is considered, so that the paths in them are not mistaken for the module path.
+fn short_type_name(name: &'static str) -> &'static str {
+    let end = name
+        .find(['<', '(', '[', '&', '*', ' '])
+        .unwrap_or(name.len());
+    match name[..end].rfind("::") {
+        Some(i) => &name[i + 2..],
+        None => name,
+    }
+}
+
+fn format_once(args: fmt::Arguments<'_>) -> String {
+    struct Counter(usize);
+
+    impl fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.len();
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    let _ = fmt::Write::write_fmt(&mut counter, args);
+
+    let mut s = String::with_capacity(counter.0);
+    let _ = fmt::Write::write_fmt(&mut s, args);
+    s
+}
+
+impl ErrMeta {
+    fn is_empty(&self) -> bool {
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        if self.created_at.is_some() {
+            return false;
+        }
+        #[cfg(feature = "std")]
+        if self.correlation_id.is_some() {
+            return false;
+        }
+        self.fields.is_empty()
+    }
+}
+
+impl Drop for Err {
+    fn drop(&mut self) {
+        release_reason_and_source(self.reason_and_source.non_null_ptr);
+    }
+}
+
+impl fmt::Debug for Err {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let debug_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).debug_fn };
+
+        write!(f, "{} {{ ", any::type_name::<Err>())?;
+        debug_fn(self.reason_and_source.non_null_ptr, f)?;
+        write!(f, ", file = {}, line = {}", self.file, self.line)?;
+        if f.alternate() {
+            write!(f, ", column = {}", self.column)?;
+        }
+        if self.severity != Severity::Error {
+            write!(f, ", severity = {:?}", self.severity)?;
+        }
+        if !self.fields().is_empty() {
+            write!(f, ", fields = ")?;
+            write_fields(self.fields(), f)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl fmt::Debug for Located {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.0.file(), self.0.line(), self.0.column())
+    }
+}
+
+impl fmt::Display for Located {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl fmt::Display for Err {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "std")]
+        if let Some(result) = display::fmt_with_formatter(self, f) {
+            return result;
+        }
+
+        let display_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).display_fn };
+        display_fn(self.reason_and_source.non_null_ptr, f)?;
+        if !self.fields().is_empty() {
+            write!(f, " ")?;
+            write_fields(self.fields(), f)?;
+        }
+
+        #[cfg(feature = "std")]
+        if display::includes_source() {
+            let mut source = error::Error::source(self);
+            while let Some(e) = source {
+                write!(f, ": {e}")?;
+                // An `Err` source displays its own sources.
+                if e.is::<Err>() {
+                    break;
+                }
+                source = e.source();
+            }
+        }
+        Ok(())
+    }
+}
+
+// Two `Err` instances are equal if their reasons are of the same type and they are created at
+// the same location. The reason values are compared only if both errors are specified with
+// `Err::with_comparable_reason`, because the reason type is not required to implement `Eq` or
+// `Hash`. An error whose reason value is hashed is never equal to one whose reason value is not
+// hashed, so that equal errors always have the same hash. The sources and fields are not
+// compared.
+impl PartialEq for Err {
+    fn eq(&self, other: &Self) -> bool {
+        if self.reason_type_id() != other.reason_type_id()
+            || self.file != other.file
+            || self.line != other.line
+            || self.column != other.column
+        {
+            return false;
+        }
+        let ptr = self.reason_and_source.non_null_ptr;
+        let other_ptr = other.reason_and_source.non_null_ptr;
+        let eq_fn = unsafe { (*ptr.as_ptr()).eq_fn };
+        let other_eq_fn = unsafe { (*other_ptr.as_ptr()).eq_fn };
+        match (eq_fn, other_eq_fn) {
+            (Some(eq_fn), Some(_)) => eq_fn(ptr, other_ptr),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Err {}
+
+impl hash::Hash for Err {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.reason_type_id().hash(state);
+        self.file.hash(state);
+        self.line.hash(state);
+        self.column.hash(state);
+        let ptr = self.reason_and_source.non_null_ptr;
+        let hash_fn = unsafe { (*ptr.as_ptr()).hash_fn };
+        if let Some(hash_fn) = hash_fn {
+            hash_fn(ptr, state);
+        }
+    }
+}
+
+fn write_fields(fields: &[(String, String)], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, (key, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{key}={value}")?;
+    }
+    write!(f, "]")
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Err {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        let source_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).source_fn };
+        source_fn(self.reason_and_source.non_null_ptr)
+    }
+}
+
+// In `no_std` mode, an `Err` has no source, so the default `source` method is used.
+#[cfg(all(not(feature = "std"), feature = "core-error"))]
+impl error::Error for Err {}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for Sources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Sources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, src) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{src}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Sources {}
+
+#[cfg(feature = "std")]
+impl LazySource {
+    fn get(&self) -> &(dyn error::Error + Send + Sync + 'static) {
+        self.source
+            .get_or_init(|| {
+                // The lock is released before calling the closure, so that a panic in the
+                // closure does not poison the mutex.
+                let source_fn = self
+                    .source_fn
+                    .lock()
+                    .unwrap_or_else(sync::PoisonError::into_inner)
+                    .take();
+                match source_fn {
+                    Some(source_fn) => source_fn(),
+                    None => panic!("the closure to create the source error has panicked"),
+                }
+            })
+            .as_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for LazySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.get(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for LazySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.get(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for LazySource {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.get().source()
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for BoxedSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for BoxedSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for BoxedSource {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+impl fmt::Debug for RenderedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.debug)
+    }
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+impl fmt::Debug for RenderedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.debug)
+    }
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+impl fmt::Display for RenderedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+impl error::Error for RenderedError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.source {
+            Some(src) => Some(src.as_ref()),
+            None => None,
+        }
+    }
+}
+
+impl<R, E> ReasonAndSource<R, E>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    // The header fields are read through the type-erased `ReasonAndSource`, so their offsets
+    // must be the same regardless of `R` and `E`. Since `ReasonAndSource` is `#[repr(C)]` and
+    // the generic field is the last one, this always holds, but it is checked at compile time
+    // for each instantiation to guard against future changes of the field order.
+    const HEADER_LAYOUT_IS_TYPE_INDEPENDENT: () = {
+        assert!(mem::offset_of!(Self, is_fn) == mem::offset_of!(ReasonAndSource, is_fn));
+        assert!(mem::offset_of!(Self, drop_fn) == mem::offset_of!(ReasonAndSource, drop_fn));
+        assert!(mem::offset_of!(Self, debug_fn) == mem::offset_of!(ReasonAndSource, debug_fn));
+        assert!(
+            mem::offset_of!(Self, debug_reason_fn)
+                == mem::offset_of!(ReasonAndSource, debug_reason_fn)
+        );
+        assert!(mem::offset_of!(Self, display_fn) == mem::offset_of!(ReasonAndSource, display_fn));
+        #[cfg(feature = "std")]
+        assert!(mem::offset_of!(Self, source_fn) == mem::offset_of!(ReasonAndSource, source_fn));
+        #[cfg(feature = "std")]
+        assert!(mem::offset_of!(Self, sources_fn) == mem::offset_of!(ReasonAndSource, sources_fn));
+        #[cfg(feature = "std")]
+        assert!(
+            mem::offset_of!(Self, take_source_fn)
+                == mem::offset_of!(ReasonAndSource, take_source_fn)
+        );
+        #[cfg(feature = "std")]
+        assert!(
+            mem::offset_of!(Self, replace_source_fn)
+                == mem::offset_of!(ReasonAndSource, replace_source_fn)
+        );
+        assert!(mem::offset_of!(Self, as_any_fn) == mem::offset_of!(ReasonAndSource, as_any_fn));
+        assert!(
+            mem::offset_of!(Self, as_any_mut_fn) == mem::offset_of!(ReasonAndSource, as_any_mut_fn)
+        );
+        assert!(
+            mem::offset_of!(Self, move_reason_fn)
+                == mem::offset_of!(ReasonAndSource, move_reason_fn)
+        );
+        assert!(
+            mem::offset_of!(Self, type_name_fn) == mem::offset_of!(ReasonAndSource, type_name_fn)
+        );
+        assert!(mem::offset_of!(Self, eq_fn) == mem::offset_of!(ReasonAndSource, eq_fn));
+        assert!(mem::offset_of!(Self, hash_fn) == mem::offset_of!(ReasonAndSource, hash_fn));
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        assert!(mem::offset_of!(Self, ref_count) == mem::offset_of!(ReasonAndSource, ref_count));
+    };
+
+    fn new(reason: R) -> Self {
+        let () = Self::HEADER_LAYOUT_IS_TYPE_INDEPENDENT;
+        Self {
+            is_fn: is_reason::<R>,
+            drop_fn: drop_reason_and_source::<R, E>,
+            debug_fn: debug_reason_and_source::<R, E>,
+            debug_reason_fn: debug_reason::<R, E>,
+            display_fn: display_reason_by_debug,
+            #[cfg(feature = "std")]
+            source_fn: get_source::<R, E>,
+            #[cfg(feature = "std")]
+            sources_fn: get_no_sources,
+            #[cfg(feature = "std")]
+            take_source_fn: take_source::<R, E>,
+            #[cfg(feature = "std")]
+            replace_source_fn: replace_source::<R, E>,
+            as_any_fn: get_reason_as_any::<R, E>,
+            as_any_mut_fn: get_reason_as_any_mut::<R, E>,
+            move_reason_fn: move_reason::<R, E>,
+            type_name_fn: get_type_name::<R>,
+            eq_fn: None,
+            hash_fn: None,
+            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+            ref_count: sync::atomic::AtomicUsize::new(1),
+            reason_and_source: (reason, None),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn with_source(reason: R, source: E) -> Self {
+        let () = Self::HEADER_LAYOUT_IS_TYPE_INDEPENDENT;
+        Self {
+            is_fn: is_reason::<R>,
+            drop_fn: drop_reason_and_source::<R, E>,
+            debug_fn: debug_reason_and_source::<R, E>,
+            debug_reason_fn: debug_reason::<R, E>,
+            display_fn: display_reason_by_debug,
+            #[cfg(feature = "std")]
+            source_fn: get_source::<R, E>,
+            #[cfg(feature = "std")]
+            sources_fn: get_no_sources,
+            #[cfg(feature = "std")]
+            take_source_fn: take_source::<R, E>,
+            #[cfg(feature = "std")]
+            replace_source_fn: replace_source::<R, E>,
+            as_any_fn: get_reason_as_any::<R, E>,
+            as_any_mut_fn: get_reason_as_any_mut::<R, E>,
+            move_reason_fn: move_reason::<R, E>,
+            type_name_fn: get_type_name::<R>,
+            eq_fn: None,
+            hash_fn: None,
+            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+            ref_count: sync::atomic::AtomicUsize::new(1),
+            reason_and_source: (reason, Some(*Box::new(source))),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> ReasonAndSource<R, Sources>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    fn with_sources(reason: R, sources: Sources) -> Self {
+        let () = Self::HEADER_LAYOUT_IS_TYPE_INDEPENDENT;
+        Self {
+            is_fn: is_reason::<R>,
+            drop_fn: drop_reason_and_source::<R, Sources>,
+            debug_fn: debug_reason_and_source::<R, Sources>,
+            debug_reason_fn: debug_reason::<R, Sources>,
+            display_fn: display_reason_by_debug,
+            source_fn: get_first_source::<R>,
+            sources_fn: get_sources::<R>,
+            take_source_fn: take_source::<R, Sources>,
+            replace_source_fn: replace_source::<R, Sources>,
+            as_any_fn: get_reason_as_any::<R, Sources>,
+            as_any_mut_fn: get_reason_as_any_mut::<R, Sources>,
+            move_reason_fn: move_reason::<R, Sources>,
+            type_name_fn: get_type_name::<R>,
+            eq_fn: None,
+            hash_fn: None,
+            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+            ref_count: sync::atomic::AtomicUsize::new(1),
+            reason_and_source: (reason, Some(sources)),
+        }
+    }
+}
+
+// The source type of `ReasonAndSource` for an `Err` created with `Err::with_arc_source`.
+#[cfg(feature = "std")]
+type ArcSource = Arc<dyn error::Error + Send + Sync>;
+
+#[cfg(feature = "std")]
+impl<R> ReasonAndSource<R, ArcSource>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    fn with_arc_source(reason: R, source: ArcSource) -> Self {
+        let () = Self::HEADER_LAYOUT_IS_TYPE_INDEPENDENT;
+        Self {
+            is_fn: is_reason::<R>,
+            drop_fn: drop_reason_and_source::<R, ArcSource>,
+            debug_fn: debug_reason_and_source::<R, ArcSource>,
+            debug_reason_fn: debug_reason::<R, ArcSource>,
+            display_fn: display_reason_by_debug,
+            source_fn: get_arc_source::<R>,
+            sources_fn: get_no_sources,
+            take_source_fn: take_source::<R, ArcSource>,
+            replace_source_fn: replace_source::<R, ArcSource>,
+            as_any_fn: get_reason_as_any::<R, ArcSource>,
+            as_any_mut_fn: get_reason_as_any_mut::<R, ArcSource>,
+            move_reason_fn: move_reason::<R, ArcSource>,
+            type_name_fn: get_type_name::<R>,
+            eq_fn: None,
+            hash_fn: None,
+            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+            ref_count: sync::atomic::AtomicUsize::new(1),
+            reason_and_source: (reason, Some(source)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> ReasonAndSource<R, LazySource>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    fn with_lazy_source(reason: R, source: LazySource) -> Self {
+        let () = Self::HEADER_LAYOUT_IS_TYPE_INDEPENDENT;
+        Self {
+            is_fn: is_reason::<R>,
+            drop_fn: drop_reason_and_source::<R, LazySource>,
+            debug_fn: debug_reason_and_source::<R, LazySource>,
+            debug_reason_fn: debug_reason::<R, LazySource>,
+            display_fn: display_reason_by_debug,
+            source_fn: get_lazy_source::<R>,
+            sources_fn: get_no_sources,
+            take_source_fn: take_source::<R, LazySource>,
+            replace_source_fn: replace_source::<R, LazySource>,
+            as_any_fn: get_reason_as_any::<R, LazySource>,
+            as_any_mut_fn: get_reason_as_any_mut::<R, LazySource>,
+            move_reason_fn: move_reason::<R, LazySource>,
+            type_name_fn: get_type_name::<R>,
+            eq_fn: None,
+            hash_fn: None,
+            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+            ref_count: sync::atomic::AtomicUsize::new(1),
+            reason_and_source: (reason, Some(source)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> ReasonAndSource<R, BoxedSource>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    fn with_boxed_source(reason: R, source: BoxedSource) -> Self {
+        let () = Self::HEADER_LAYOUT_IS_TYPE_INDEPENDENT;
+        Self {
+            is_fn: is_reason::<R>,
+            drop_fn: drop_reason_and_source::<R, BoxedSource>,
+            debug_fn: debug_reason_and_source::<R, BoxedSource>,
+            debug_reason_fn: debug_reason::<R, BoxedSource>,
+            display_fn: display_reason_by_debug,
+            source_fn: get_boxed_source::<R>,
+            sources_fn: get_no_sources,
+            take_source_fn: take_boxed_source::<R>,
+            replace_source_fn: replace_source::<R, BoxedSource>,
+            as_any_fn: get_reason_as_any::<R, BoxedSource>,
+            as_any_mut_fn: get_reason_as_any_mut::<R, BoxedSource>,
+            move_reason_fn: move_reason::<R, BoxedSource>,
+            type_name_fn: get_type_name::<R>,
+            eq_fn: None,
+            hash_fn: None,
+            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+            ref_count: sync::atomic::AtomicUsize::new(1),
+            reason_and_source: (reason, Some(source)),
+        }
+    }
+}
+
+pub(crate) fn leak_reason<R>(reason: R) -> ptr::NonNull<ReasonAndSource>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    let boxed = Box::new(ReasonAndSource::<R>::new(reason));
+    ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>()
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn leak_reason_and_source<R, E>(reason: R, source: E) -> ptr::NonNull<ReasonAndSource>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let boxed = Box::new(ReasonAndSource::<R, E>::with_source(reason, source));
+    ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>()
+}
+
+fn get_type_name<R>(_ptr: ptr::NonNull<ReasonAndSource>) -> &'static str
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    any::type_name::<R>()
+}
+
+fn eq_reason<R>(ptr: ptr::NonNull<ReasonAndSource>, other: ptr::NonNull<ReasonAndSource>) -> bool
+where
+    R: Eq + fmt::Debug + Send + Sync + 'static,
+{
+    let as_any_fn = unsafe { (*ptr.as_ptr()).as_any_fn };
+    let other_as_any_fn = unsafe { (*other.as_ptr()).as_any_fn };
+    match (
+        as_any_fn(ptr).downcast_ref::<R>(),
+        other_as_any_fn(other).downcast_ref::<R>(),
+    ) {
+        (Some(reason), Some(other_reason)) => reason == other_reason,
+        _ => false,
+    }
+}
+
+fn hash_reason<R>(ptr: ptr::NonNull<ReasonAndSource>, mut state: &mut dyn hash::Hasher)
+where
+    R: hash::Hash + fmt::Debug + Send + Sync + 'static,
+{
+    let as_any_fn = unsafe { (*ptr.as_ptr()).as_any_fn };
+    if let Some(reason) = as_any_fn(ptr).downcast_ref::<R>() {
+        reason.hash(&mut state);
+    }
+}
+
+fn is_reason<R>(_ptr: ptr::NonNull<ReasonAndSource>, type_id: any::TypeId) -> bool
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    any::TypeId::of::<R>() == type_id
+}
+
+fn drop_reason_and_source<R, E>(ptr: ptr::NonNull<ReasonAndSource>)
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    unsafe { drop(Box::from_raw(typed_ptr)) };
+}
+
+fn debug_reason_and_source<R, E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    let reason_and_source = unsafe { &(*typed_ptr).reason_and_source };
+    if debug::shows_reason_type() {
+        // The type name is got through `type_name_fn`, because a rendered reason has the type
+        // name of its original reason.
+        let type_name_fn = unsafe { (*typed_ptr).type_name_fn };
+        write!(
+            f,
+            "reason = {} {:?}",
+            type_name_fn(ptr),
+            reason_and_source.0
+        )?;
+    } else {
+        write!(f, "reason = {:?}", reason_and_source.0)?;
+    }
+
+    match &reason_and_source.1 {
+        Some(src) => write!(f, ", source = {:?}", src),
+        None => Ok(()),
+    }
+}
+
+fn debug_reason<R, E>(ptr: ptr::NonNull<ReasonAndSource>, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    write!(f, "{:?}", unsafe { &(*typed_ptr).reason_and_source.0 })
+}
+
+// The display functions do not cast the pointer with a source type, so that they can be kept
+// in a `ReasonAndSource` rebuilt with another source type.
+fn display_reason_by_debug(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let debug_reason_fn = unsafe { (*ptr.as_ptr()).debug_reason_fn };
+    debug_reason_fn(ptr, f)
+}
+
+fn display_reason_with_display<R>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    R: fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    let as_any_fn = unsafe { (*ptr.as_ptr()).as_any_fn };
+    match as_any_fn(ptr).downcast_ref::<R>() {
+        Some(reason) => write!(f, "{}", reason),
+        None => Ok(()),
+    }
+}
+
+#[cfg(feature = "std")]
+fn get_source<R, E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<&'static (dyn error::Error + 'static)>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    match unsafe { &(*typed_ptr).reason_and_source.1 } {
+        Some(src) => Some(src),
+        None => None,
+    }
+}
+
+// Returns the error inside the `Arc`, instead of the `Arc` itself, so that it can be downcast
+// to the type of the shared source error.
+#[cfg(feature = "std")]
+fn get_arc_source<R>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<&'static (dyn error::Error + 'static)>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, ArcSource>>().as_ptr();
+    match unsafe { &(*typed_ptr).reason_and_source.1 } {
+        Some(src) => Some(&**src),
+        None => None,
+    }
+}
+
+// Returns the created source error, instead of `LazySource` itself, so that it can be downcast
+// to the type of the source error.
+#[cfg(feature = "std")]
+fn get_lazy_source<R>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<&'static (dyn error::Error + 'static)>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, LazySource>>().as_ptr();
+    match unsafe { &(*typed_ptr).reason_and_source.1 } {
+        Some(src) => Some(src.get()),
+        None => None,
+    }
+}
+
+// Returns the boxed error, instead of `BoxedSource` itself, so that it can be downcast to the
+// type of the source error.
+#[cfg(feature = "std")]
+fn get_boxed_source<R>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<&'static (dyn error::Error + 'static)>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, BoxedSource>>().as_ptr();
+    match unsafe { &(*typed_ptr).reason_and_source.1 } {
+        Some(src) => Some(src.0.as_ref()),
+        None => None,
+    }
+}
+
+#[cfg(feature = "std")]
+fn get_first_source<R>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<&'static (dyn error::Error + 'static)>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    match get_sources::<R>(ptr).first() {
+        Some(src) => Some(src.as_ref()),
+        None => None,
+    }
+}
+
+#[cfg(feature = "std")]
+fn get_sources<R>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> &'static [Box<dyn error::Error + Send + Sync>]
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, Sources>>().as_ptr();
+    match unsafe { &(*typed_ptr).reason_and_source.1 } {
+        Some(sources) => &sources.0,
+        None => &[],
+    }
+}
+
+#[cfg(feature = "std")]
+fn get_no_sources(
+    _ptr: ptr::NonNull<ReasonAndSource>,
+) -> &'static [Box<dyn error::Error + Send + Sync>] {
+    &[]
+}
+
+#[cfg(feature = "std")]
+fn take_source<R, E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<Box<dyn error::Error + Send + Sync>>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    match unsafe { (*typed_ptr).reason_and_source.1.take() } {
+        Some(src) => Some(Box::new(src)),
+        None => None,
+    }
+}
+
+#[cfg(feature = "std")]
+fn take_boxed_source<R>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<Box<dyn error::Error + Send + Sync>>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, BoxedSource>>().as_ptr();
+    match unsafe { (*typed_ptr).reason_and_source.1.take() } {
+        Some(src) => Some(src.0),
+        None => None,
+    }
+}
+
+#[cfg(feature = "std")]
+fn replace_source<R, E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+) -> ptr::NonNull<ReasonAndSource>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    let boxed = unsafe { Box::from_raw(typed_ptr) };
+    let (display_fn, eq_fn, hash_fn) = (boxed.display_fn, boxed.eq_fn, boxed.hash_fn);
+    let (reason, old_source) = boxed.reason_and_source;
+    drop(old_source);
+    let new_ptr = leak_reason_and_taken_source(reason, source);
+    unsafe {
+        // The display function does not depend on the source type, so it is kept to display
+        // the reason in the same way, e.g. with `Display` for an `Err` created by `new_display`.
+        (*new_ptr.as_ptr()).display_fn = display_fn;
+        (*new_ptr.as_ptr()).eq_fn = eq_fn;
+        (*new_ptr.as_ptr()).hash_fn = hash_fn;
+    }
+    new_ptr
+}
+
+// Creates a `ReasonAndSource` with a source taken out of another one. The sources of an `Err`
+// created with `Err::with_sources` are taken out together as `Sources`, so they are restored
+// as multiple sources, and the other source is kept as a single source.
+#[cfg(feature = "std")]
+fn leak_reason_and_taken_source<R>(
+    reason: R,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+) -> ptr::NonNull<ReasonAndSource>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    let Some(src) = source else {
+        return leak_reason(reason);
+    };
+    match src.downcast::<Sources>() {
+        Ok(sources) => {
+            let boxed = Box::new(ReasonAndSource::<R, Sources>::with_sources(
+                reason, *sources,
+            ));
+            ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>()
+        }
+        Err(src) => {
+            let boxed = Box::new(ReasonAndSource::<R, BoxedSource>::with_boxed_source(
+                reason,
+                BoxedSource(src),
+            ));
+            ptr::NonNull::from(Box::leak(boxed)).cast::<ReasonAndSource>()
+        }
+    }
+}
+
+fn get_reason_as_any<R, E>(ptr: ptr::NonNull<ReasonAndSource>) -> &'static dyn any::Any
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    unsafe { &(*typed_ptr).reason_and_source.0 }
+}
+
+fn get_reason_as_any_mut<R, E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<&'static mut dyn any::Any>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    if is_shared(ptr) {
+        return None;
+    }
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    Some(unsafe { &mut (*typed_ptr).reason_and_source.0 })
+}
+
+// Checks whether the `ReasonAndSource` is shared with the copies of an `Err` passed to the error
+// handlers, in which case its reason must not be borrowed mutably nor moved out.
+fn is_shared(ptr: ptr::NonNull<ReasonAndSource>) -> bool {
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    {
+        let ref_count = unsafe { &(*ptr.as_ptr()).ref_count };
+        ref_count.load(sync::atomic::Ordering::Acquire) > 1
+    }
+    #[cfg(not(any(feature = "notify", feature = "notify-tokio")))]
+    {
+        let _ = ptr;
+        false
+    }
+}
+
+// Releases a reference to the `ReasonAndSource`, and drops it when the reference is the last one.
+fn release_reason_and_source(ptr: ptr::NonNull<ReasonAndSource>) {
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    {
+        let ref_count = unsafe { &(*ptr.as_ptr()).ref_count };
+        if ref_count.fetch_sub(1, sync::atomic::Ordering::Release) != 1 {
+            return;
+        }
+        sync::atomic::fence(sync::atomic::Ordering::Acquire);
+    }
+    let drop_fn = unsafe { (*ptr.as_ptr()).drop_fn };
+    drop_fn(ptr);
+}
+
+/// Checks whether the reason of an `Err` is of any of the specified types.
+///
+/// This macro expands to a short-circuiting disjunction of [`Err::reason_is`] checks and
+/// evaluates to a `bool`. The candidate types are separated by `|`, and a trailing comma is
+/// accepted.
+///
+/// ```rust
+/// use errs::{reason_matches, Err};
+///
+/// #[derive(Debug)]
+/// enum DbErr {
+///     ConnectionLost,
+/// }
+///
+/// #[derive(Debug)]
+/// enum NetErr {
+///     Timeout,
+/// }
+///
+/// let err = Err::new(NetErr::Timeout);
+/// assert!(reason_matches!(err, DbErr | NetErr));
+/// assert!(!reason_matches!(err, DbErr | String));
+/// ```
+#[macro_export]
+macro_rules! reason_matches {
+    ($err:expr, $($ty:ty)|+ $(,)?) => {{
+        let err: &$crate::Err = &$err;
+        false $(|| err.reason_is::<$ty>())+
+    }};
+}
+
+// Creates a `ReasonAndSource` which holds the rendered strings of the reason and the source
+// chain of the given one, for an `Err` in the source chain of the copy passed to the handlers.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn render_reason_and_source(ptr: ptr::NonNull<ReasonAndSource>) -> ptr::NonNull<ReasonAndSource> {
+    struct FmtReason(
+        ptr::NonNull<ReasonAndSource>,
+        fn(ptr::NonNull<ReasonAndSource>, &mut fmt::Formatter<'_>) -> fmt::Result,
+    );
+
+    impl fmt::Display for FmtReason {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.1)(self.0, f)
+        }
+    }
+
+    let header = unsafe { &*ptr.as_ptr() };
+    let reason = RenderedReason {
+        type_name: (header.type_name_fn)(ptr),
+        debug: FmtReason(ptr, header.debug_reason_fn).to_string(),
+        display: FmtReason(ptr, header.display_fn).to_string(),
+    };
+
+    match render_sources(ptr) {
+        Some(src) => match src.downcast::<Sources>() {
+            Ok(sources) => leak_rendered(ReasonAndSource::with_sources(reason, *sources)),
+            Err(src) => leak_rendered(ReasonAndSource::with_boxed_source(reason, BoxedSource(src))),
+        },
+        None => leak_rendered(ReasonAndSource::<RenderedReason>::new(reason)),
+    }
+}
+
+// Renders the source chain of the given `ReasonAndSource`. The sources of an `Err` created with
+// `Err::with_sources` are rendered together as `Sources`, like `Err::take_source`.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn render_sources(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<Box<dyn error::Error + Send + Sync>> {
+    let header = unsafe { &*ptr.as_ptr() };
+    let sources = (header.sources_fn)(ptr);
+    if !sources.is_empty() {
+        return Some(Box::new(Sources(
+            sources
+                .iter()
+                .map(|src| render_source(src.as_ref()))
+                .collect(),
+        )));
+    }
+    (header.source_fn)(ptr).map(render_source)
+}
+
+// An `Err` in the source chain is rendered as an `Err`, so that it is still displayed with its
+// own sources.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn render_source(src: &(dyn error::Error + 'static)) -> Box<dyn error::Error + Send + Sync> {
+    match src.downcast_ref::<Err>() {
+        Some(err) => Box::new(err.to_rendered()),
+        None => Box::new(RenderedError {
+            debug: format!("{src:?}"),
+            display: src.to_string(),
+            source: src.source().map(render_source),
+        }),
+    }
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn leak_rendered<E>(mut ras: ReasonAndSource<RenderedReason, E>) -> ptr::NonNull<ReasonAndSource>
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    ras.display_fn = display_rendered_reason::<E>;
+    ras.type_name_fn = get_rendered_type_name::<E>;
+    ptr::NonNull::from(Box::leak(Box::new(ras))).cast::<ReasonAndSource>()
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn display_rendered_reason<E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<RenderedReason, E>>().as_ptr();
+    f.write_str(unsafe { &(*typed_ptr).reason_and_source.0.display })
+}
+
+// A rendered reason has the type name of its original reason.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn get_rendered_type_name<E>(ptr: ptr::NonNull<ReasonAndSource>) -> &'static str
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<RenderedReason, E>>().as_ptr();
+    unsafe { (*typed_ptr).reason_and_source.0.type_name }
+}
+
+// Creates a `ReasonAndSource` which shares the reason of the given one, with the given source.
+// The reason is shared by holding a reference to the given `ReasonAndSource`.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn leak_shared(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+) -> ptr::NonNull<ReasonAndSource> {
+    let ref_count = unsafe { &(*ptr.as_ptr()).ref_count };
+    ref_count.fetch_add(1, sync::atomic::Ordering::Relaxed);
+    leak_shared_reason(SharedReason(SendSyncNonNull::new(ptr)), source)
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn leak_shared_reason(
+    reason: SharedReason,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+) -> ptr::NonNull<ReasonAndSource> {
+    let Some(src) = source else {
+        return leak_shared_with(ReasonAndSource::<SharedReason>::new(reason));
+    };
+    match src.downcast::<Sources>() {
+        Ok(sources) => leak_shared_with(ReasonAndSource::with_sources(reason, *sources)),
+        Err(src) => leak_shared_with(ReasonAndSource::with_boxed_source(reason, BoxedSource(src))),
+    }
+}
+
+// The functions about the reason are replaced with the ones which forward to the shared
+// `ReasonAndSource`, so that the shared reason is treated as the reason of this one.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn leak_shared_with<E>(mut ras: ReasonAndSource<SharedReason, E>) -> ptr::NonNull<ReasonAndSource>
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let shared_ptr = ras.reason_and_source.0 .0.non_null_ptr;
+    ras.is_fn = is_shared_reason::<E>;
+    ras.display_fn = display_shared_reason::<E>;
+    ras.replace_source_fn = replace_shared_source::<E>;
+    ras.as_any_fn = get_shared_reason_as_any::<E>;
+    ras.as_any_mut_fn = get_shared_reason_as_any_mut::<E>;
+    ras.move_reason_fn = move_shared_reason::<E>;
+    ras.type_name_fn = get_shared_type_name::<E>;
+    unsafe {
+        ras.eq_fn = (*shared_ptr.as_ptr()).eq_fn;
+        ras.hash_fn = (*shared_ptr.as_ptr()).hash_fn;
+    }
+    ptr::NonNull::from(Box::leak(Box::new(ras))).cast::<ReasonAndSource>()
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn get_shared_ptr<E>(ptr: ptr::NonNull<ReasonAndSource>) -> ptr::NonNull<ReasonAndSource>
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<SharedReason, E>>().as_ptr();
+    unsafe { (*typed_ptr).reason_and_source.0 .0.non_null_ptr }
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn is_shared_reason<E>(ptr: ptr::NonNull<ReasonAndSource>, type_id: any::TypeId) -> bool
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let shared_ptr = get_shared_ptr::<E>(ptr);
+    let is_fn = unsafe { (*shared_ptr.as_ptr()).is_fn };
+    is_fn(shared_ptr, type_id)
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn display_shared_reason<E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let shared_ptr = get_shared_ptr::<E>(ptr);
+    let display_fn = unsafe { (*shared_ptr.as_ptr()).display_fn };
+    display_fn(shared_ptr, f)
+}
+
+// Replaces the source, keeping the shared reason without changing the number of references to it.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn replace_shared_source<E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+) -> ptr::NonNull<ReasonAndSource>
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let typed_ptr = ptr.cast::<ReasonAndSource<SharedReason, E>>().as_ptr();
+    let boxed = unsafe { Box::from_raw(typed_ptr) };
+    let (eq_fn, hash_fn) = (boxed.eq_fn, boxed.hash_fn);
+    let (reason, old_source) = boxed.reason_and_source;
+    drop(old_source);
+    let new_ptr = leak_shared_reason(reason, source);
+    unsafe {
+        (*new_ptr.as_ptr()).eq_fn = eq_fn;
+        (*new_ptr.as_ptr()).hash_fn = hash_fn;
+    }
+    new_ptr
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn get_shared_reason_as_any<E>(ptr: ptr::NonNull<ReasonAndSource>) -> &'static dyn any::Any
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let shared_ptr = get_shared_ptr::<E>(ptr);
+    let as_any_fn = unsafe { (*shared_ptr.as_ptr()).as_any_fn };
+    as_any_fn(shared_ptr)
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn get_shared_reason_as_any_mut<E>(
+    ptr: ptr::NonNull<ReasonAndSource>,
+) -> Option<&'static mut dyn any::Any>
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    if is_shared(ptr) {
+        return None;
+    }
+    let shared_ptr = get_shared_ptr::<E>(ptr);
+    let as_any_mut_fn = unsafe { (*shared_ptr.as_ptr()).as_any_mut_fn };
+    as_any_mut_fn(shared_ptr)
+}
+
+// Moves the shared reason, which succeeds only when no other one shares it.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn move_shared_reason<E>(ptr: ptr::NonNull<ReasonAndSource>, out: ptr::NonNull<()>) -> bool
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    if is_shared(ptr) {
+        return false;
+    }
+    let shared_ptr = get_shared_ptr::<E>(ptr);
+    let move_reason_fn = unsafe { (*shared_ptr.as_ptr()).move_reason_fn };
+    if !move_reason_fn(shared_ptr, out) {
+        return false;
+    }
+
+    // The shared `ReasonAndSource` is already deallocated by `move_reason_fn`, so the reference
+    // to it must not be released.
+    let typed_ptr = ptr.cast::<ReasonAndSource<SharedReason, E>>().as_ptr();
+    let boxed = unsafe { Box::from_raw(typed_ptr) };
+    let (reason, source) = boxed.reason_and_source;
+    mem::forget(reason);
+    drop(source);
+    true
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+fn get_shared_type_name<E>(ptr: ptr::NonNull<ReasonAndSource>) -> &'static str
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    let shared_ptr = get_shared_ptr::<E>(ptr);
+    let type_name_fn = unsafe { (*shared_ptr.as_ptr()).type_name_fn };
+    type_name_fn(shared_ptr)
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+impl fmt::Debug for SharedReason {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let debug_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).debug_fn };
+        let shared_ptr = self.0.non_null_ptr;
+        let debug_reason_fn = unsafe { (*shared_ptr.as_ptr()).debug_reason_fn };
+        debug_reason_fn(shared_ptr, f)
+    }
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+impl Drop for SharedReason {
+    fn drop(&mut self) {
+        release_reason_and_source(self.0.non_null_ptr);
+    }
+}
+
+// Moves the reason to the memory pointed by `out`, and deallocates the `ReasonAndSource` after
+// dropping the source. If the `ReasonAndSource` is shared, this does nothing and returns `false`.
+fn move_reason<R, E>(ptr: ptr::NonNull<ReasonAndSource>, out: ptr::NonNull<()>) -> bool
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    E: error::Error + Send + Sync + 'static,
+{
+    if is_shared(ptr) {
+        return false;
+    }
+    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
+    let boxed = unsafe { Box::from_raw(typed_ptr) };
+    let (reason, source) = boxed.reason_and_source;
+    drop(source);
+    unsafe { out.cast::<R>().as_ptr().write(reason) };
+    true
+}
+
+/// Creates an `Err` instance with a reason and a source error in one call.
+///
+/// This macro expands to [`Err::with_source`], and is useful when the reason is a unit variant
+/// which represents the kind of the source error. The location where the `Err` is created is
+/// the location where this macro is called.
+///
+/// ```rust
+/// use errs::{reason_from_source, Err};
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     Io,
+/// }
+///
+/// fn read_config() -> errs::Result<String> {
+///     std::fs::read_to_string("/no/such/file").map_err(|e| reason_from_source!(Reasons::Io, e))
+/// }
+///
+/// let err = read_config().unwrap_err();
+/// assert!(matches!(err.reason::<Reasons>(), Ok(Reasons::Io)));
+/// assert!(err.source().is_some());
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[macro_export]
+macro_rules! reason_from_source {
+    ($reason:expr, $source:expr $(,)?) => {
+        $crate::Err::with_source($reason, $source)
+    };
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests_of_err {
+    use super::*;
+    use std::sync::{LazyLock, Mutex};
+
+    struct Logger {
+        log_vec: Vec<String>,
+    }
+
+    impl Logger {
+        fn new() -> Self {
+            Self {
+                log_vec: Vec::<String>::new(),
+            }
+        }
+        fn log(&mut self, s: &str) {
+            self.log_vec.push(s.to_string());
+        }
+        // Compares the logs one by one by index so that a failure shows the first unmatched entry.
+        #[allow(clippy::needless_range_loop)]
+        fn assert_logs(&self, logs: &[&str]) {
+            if self.log_vec.len() != logs.len() {
+                assert_eq!(self.log_vec, logs);
+                return;
+            }
+            for i in 0..self.log_vec.len() {
+                assert_eq!(self.log_vec[i], logs[i]);
+            }
+        }
+    }
+
+    const BASE_LINE: u32 = line!();
+
+    mod test_of_drop {
+        use super::*;
+
+        static LOGGER: LazyLock<Mutex<Logger>> = LazyLock::new(|| Mutex::new(Logger::new()));
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Enum0 {
+            InvalidValue { name: String, value: String },
+        }
+        impl Drop for Enum0 {
+            fn drop(&mut self) {
+                LOGGER.lock().unwrap().log("drop Enum0");
+            }
+        }
+
+        fn create_err() -> Result<(), Err> {
+            let err = Err::new(Enum0::InvalidValue {
+                name: "foo".to_string(),
+                value: "abc".to_string(),
+            });
+            LOGGER.lock().unwrap().log("created Enum0");
+            Err(err)
+        }
+
+        fn consume_err() {
+            let err = create_err().unwrap_err();
+            assert_eq!(
+                format!("{err}"),
+                "InvalidValue { name: \"foo\", value: \"abc\" }",
+            );
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_drop::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 19),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_drop::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 19),
+            );
+
+            LOGGER.lock().unwrap().log("consumed Enum0");
+        }
+
+        #[test]
+        fn test() {
+            consume_err();
+
+            std::thread::sleep(std::time::Duration::from_secs(1));
+
+            LOGGER.lock().unwrap().log("end");
+
+            LOGGER.lock().unwrap().assert_logs(&[
+                "created Enum0",
+                "consumed Enum0",
+                "drop Enum0",
+                "end",
+            ]);
+        }
+    }
+
+    mod test_of_new {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Enum0 {
+            InvalidValue { name: String, value: String },
+        }
+
+        #[test]
+        fn new_err() {
+            let err = Err::new(Enum0::InvalidValue {
+                name: "foo".to_string(),
+                value: "abc".to_string(),
+            });
+
+            #[cfg(unix)]
+            assert_eq!(err.file(), "src/err.rs");
+            #[cfg(windows)]
+            assert_eq!(err.file(), "src\\err.rs");
+            assert_eq!(err.line(), BASE_LINE + 74);
+            assert_eq!(
+                format!("{err}"),
+                "InvalidValue { name: \"foo\", value: \"abc\" }",
+            );
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_new::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 74),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_new::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 74),
+            );
+
+            match err.reason::<Enum0>().unwrap() {
+                Enum0::InvalidValue { name, value } => {
+                    assert_eq!(name, "foo");
+                    assert_eq!(value, "abc");
+                }
+            }
+            assert!(err.source().is_none());
+        }
+    }
+
+    mod test_of_with_source {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Enum0 {
+            InvalidValue { name: String, value: String },
+        }
+
+        #[test]
+        fn source_is_a_standard_error() {
+            let source = std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!");
+            let err = Err::with_source(
+                Enum0::InvalidValue {
+                    name: "foo".to_string(),
+                    value: "abc".to_string(),
+                },
+                source,
+            );
+
+            #[cfg(unix)]
+            assert_eq!(err.file, "src/err.rs");
+            #[cfg(windows)]
+            assert_eq!(err.file, "src\\err.rs");
+
+            assert_eq!(err.line, BASE_LINE + 120);
+            assert_eq!(
+                format!("{err}"),
+                "InvalidValue { name: \"foo\", value: \"abc\" }",
+            );
+
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = Custom {{ kind: NotFound, error: \"oh no!\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 120),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = Custom {{ kind: NotFound, error: \"oh no!\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 120),
+            );
+
+            match err.reason::<Enum0>().unwrap() {
+                Enum0::InvalidValue { name, value } => {
+                    assert_eq!(name, "foo");
+                    assert_eq!(value, "abc");
+                }
+            }
+
+            assert!(err.source().is_some());
+            match err.source() {
+                Some(e) => match e.downcast_ref::<std::io::Error>() {
+                    Some(io_err) => {
+                        assert_eq!((*io_err).kind(), std::io::ErrorKind::NotFound);
+                    }
+                    _ => unreachable!(),
+                },
+                None => unreachable!(),
+            }
+        }
+
+        #[derive(Debug)]
+        struct MyError {
+            message: String,
+        }
+        impl MyError {
+            fn new(msg: &str) -> Self {
+                Self {
+                    message: msg.to_string(),
+                }
+            }
+        }
+        impl fmt::Display for MyError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                write!(f, "MyError{{message:\"{}\"}}", self.message)
+            }
+        }
+        impl error::Error for MyError {}
+
+        #[test]
+        fn source_is_a_user_defined_error() {
+            let source = MyError::new("hello");
+            let err = Err::with_source(
+                Enum0::InvalidValue {
+                    name: "foo".to_string(),
+                    value: "abc".to_string(),
+                },
+                source,
+            );
+
+            #[cfg(unix)]
+            assert_eq!(err.file, "src/err.rs");
+            #[cfg(windows)]
+            assert_eq!(err.file, "src\\err.rs");
+            assert_eq!(err.line, BASE_LINE + 190);
+            assert_eq!(
+                format!("{err}"),
+                "InvalidValue { name: \"foo\", value: \"abc\" }",
+            );
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = MyError {{ message: \"hello\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 190),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = MyError {{ message: \"hello\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 190),
+            );
+
+            assert!(err.source().is_some());
+            match err.source() {
+                Some(e) => match e.downcast_ref::<MyError>() {
+                    // Dereferences explicitly to show that `my_err` is a reference to `MyError`.
+                    #[allow(clippy::explicit_auto_deref)]
+                    Some(my_err) => {
+                        assert_eq!((*my_err).message, "hello".to_string());
+                    }
+                    _ => unreachable!(),
+                },
+                None => unreachable!(),
+            }
+        }
+    }
+
+    mod test_of_reason {
+        use super::*;
+
+        #[test]
+        fn reason_is_a_boolean() {
+            let err = Err::new(true);
+            assert_eq!(format!("{err}"), "true");
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = bool true, file = src/err.rs, line = {} }}",
+                    BASE_LINE + 238,
+                ),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = bool true, file = src\\err.rs, line = {} }}",
+                    BASE_LINE + 238,
+                ),
+            );
+
+            match err.reason() {
+                Ok(true) => {}
+                Ok(false) => unreachable!(),
+                Err(_) => unreachable!(),
+            }
+
+            assert!(err.source().is_none());
+        }
+
+        #[test]
+        fn reason_is_a_number() {
+            let err = Err::new(123i64);
+            assert_eq!(format!("{err}"), "123");
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = i64 123, file = src/err.rs, line = {} }}",
+                    BASE_LINE + 268,
+                ),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = i64 123, file = src\\err.rs, line = {} }}",
+                    BASE_LINE + 268,
+                ),
+            );
+
+            match err.reason::<i64>() {
+                Ok(n) => assert_eq!(*n, 123i64),
+                Err(_) => unreachable!(),
+            }
+
+            assert!(err.source().is_none());
+        }
+
+        #[test]
+        fn reason_is_a_string() {
+            let err = Err::new("abc".to_string());
+            assert_eq!(format!("{err}"), "\"abc\"");
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = alloc::string::String \"abc\", file = src/err.rs, line = {} }}",
+                    BASE_LINE + 297,
+                ),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = alloc::string::String \"abc\", file = src\\err.rs, line = {} }}",
+                    BASE_LINE + 297,
+                ),
+            );
+
+            match err.reason::<String>() {
+                Ok(s) => assert_eq!(s, "abc"),
+                Err(_) => unreachable!(),
+            }
+
+            assert!(err.source().is_none());
+        }
+
+        #[derive(Debug)]
+        struct StructA {
+            name: String,
+            value: i64,
+        }
+
+        #[test]
+        fn reason_is_a_struct() {
+            let err = Err::new(StructA {
+                name: "abc".to_string(),
+                value: 123,
+            });
+            assert_eq!(format!("{err}"), "StructA { name: \"abc\", value: 123 }");
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_reason::StructA StructA {{ name: \"abc\", value: 123 }}, file = src/err.rs, line = {} }}", BASE_LINE + 332),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_reason::StructA StructA {{ name: \"abc\", value: 123 }}, file = src\\err.rs, line = {} }}", BASE_LINE + 332),
+            );
+
+            match err.reason::<StructA>() {
+                Ok(st) => {
+                    assert_eq!(st.name, "abc".to_string());
+                    assert_eq!(st.value, 123);
+                }
+                Err(_) => unreachable!(),
+            }
+
+            assert!(err.source().is_none());
+        }
+
+        #[test]
+        fn reaason_is_an_unit() {
+            let err = Err::new(());
+            assert_eq!(format!("{err}"), "()");
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = () (), file = src/err.rs, line = {} }}",
+                    BASE_LINE + 361,
+                ),
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = () (), file = src\\err.rs, line = {} }}",
+                    BASE_LINE + 361,
+                ),
+            );
+
+            match err.reason::<()>() {
+                Ok(()) => {}
+                Err(_) => unreachable!(),
+            }
+
+            assert!(err.source().is_none());
+        }
+    }
+
+    mod test_of_match_reason {
+        use super::*;
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Enum0 {
+            InvalidValue { name: String, value: String },
+            FailToGetValue { name: String },
+        }
+
+        #[test]
+        fn reason_is_enum() {
+            let err = Err::new(Enum0::InvalidValue {
+                name: "foo".to_string(),
+                value: "abc".to_string(),
+            });
+
+            match err.reason::<String>() {
+                Ok(_) => panic!(),
+                Err(err) => match err.reason::<Enum0>() {
+                    Ok(r) => match r {
+                        Enum0::InvalidValue { name, value } => {
+                            assert_eq!(name, "foo");
+                            assert_eq!(value, "abc");
+                        }
+                        _ => panic!(),
+                    },
+                    Err(_) => panic!(),
+                },
+            }
+
+            err.match_reason::<String>(|_s| {
+                panic!();
+            })
+            .match_reason::<Enum0>(|r| match r {
+                Enum0::InvalidValue { name, value } => {
+                    assert_eq!(name, "foo");
+                    assert_eq!(value, "abc");
+                }
+                _ => panic!(),
+            });
+        }
+    }
+
+    mod test_of_match_reason_with {
+        use super::*;
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Enum0 {
+            InvalidValue { name: String, value: String },
+            FailToGetValue { name: String },
+        }
+
+        #[test]
+        fn closure_captures_variables() {
+            let err = Err::new(Enum0::InvalidValue {
+                name: "foo".to_string(),
+                value: "abc".to_string(),
+            });
+
+            let mut names = Vec::<String>::new();
+            let mut count = 0;
+
+            err.match_reason_with::<String, _>(|_s| {
+                count += 1;
+            })
+            .match_reason_with::<Enum0, _>(|r| match r {
+                Enum0::InvalidValue { name, .. } => names.push(name.clone()),
+                _ => panic!(),
+            });
+
+            assert_eq!(count, 0);
+            assert_eq!(names, vec!["foo".to_string()]);
+        }
+    }
+
+    mod test_of_reason_in_chain {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Enum0 {
+            FailToDoSomething,
+        }
+
+        #[derive(Debug)]
+        enum Enum1 {
+            FailToCallSomething,
+        }
+
+        #[derive(Debug)]
+        enum Enum2 {
+            InvalidValue { name: String },
+        }
+
+        #[test]
+        fn reason_is_on_self() {
+            let err = Err::new(Enum0::FailToDoSomething);
+            match err.reason_in_chain::<Enum0>() {
+                Some(Enum0::FailToDoSomething) => {}
+                None => panic!(),
+            }
+            assert!(err.reason_in_chain::<Enum1>().is_none());
+        }
+
+        #[test]
+        fn reason_is_two_levels_deep() {
+            let err2 = Err::new(Enum2::InvalidValue {
+                name: "foo".to_string(),
+            });
+            let err1 = Err::with_source(Enum1::FailToCallSomething, err2);
+            let err0 = Err::with_source(Enum0::FailToDoSomething, err1);
+
+            match err0.reason_in_chain::<Enum2>() {
+                Some(Enum2::InvalidValue { name }) => assert_eq!(name, "foo"),
+                None => panic!(),
+            }
+            match err0.reason_in_chain::<Enum1>() {
+                Some(Enum1::FailToCallSomething) => {}
+                None => panic!(),
+            }
+            assert!(err0.reason_in_chain::<String>().is_none());
+        }
+
+        #[test]
+        fn chain_contains_a_non_errs_error() {
+            let io_err = std::io::Error::other("oh no!");
+            let err1 = Err::with_source(Enum1::FailToCallSomething, io_err);
+            let err0 = Err::with_source(Enum0::FailToDoSomething, err1);
+
+            assert!(err0.reason_in_chain::<Enum1>().is_some());
+            assert!(err0.reason_in_chain::<Enum2>().is_none());
+        }
+    }
+
+    mod test_of_silent {
+        use super::*;
+
+        static LOGGER: LazyLock<Mutex<Logger>> = LazyLock::new(|| Mutex::new(Logger::new()));
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Enum0 {
+            CacheMiss { key: String },
+        }
+        impl Drop for Enum0 {
+            fn drop(&mut self) {
+                LOGGER.lock().unwrap().log("drop Enum0");
+            }
+        }
+
+        #[test]
+        fn test() {
+            let err = Err::new_silent(Enum0::CacheMiss {
+                key: "foo".to_string(),
+            });
+            assert!(err.is_silent());
+            assert_eq!(format!("{err}"), "CacheMiss { key: \"foo\" }");
+            drop(err);
+            LOGGER.lock().unwrap().log("dropped err");
+
+            let err = Err::with_source_silent(
+                Enum0::CacheMiss {
+                    key: "bar".to_string(),
+                },
+                std::io::Error::other("oh no!"),
+            );
+            assert!(err.is_silent());
+            assert!(err.source().is_some());
+            drop(err);
+            LOGGER.lock().unwrap().log("dropped err");
+
+            LOGGER.lock().unwrap().assert_logs(&[
+                "drop Enum0",
+                "dropped err",
+                "drop Enum0",
+                "dropped err",
+            ]);
+        }
+    }
+
+    mod test_of_reason_any {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Enum0 {
+            InvalidValue { name: String, value: String },
+        }
+
+        #[test]
+        fn reason_is_enum() {
+            let err = Err::new(Enum0::InvalidValue {
+                name: "foo".to_string(),
+                value: "abc".to_string(),
+            });
+
+            let any = err.reason_any();
+            assert!(any.downcast_ref::<String>().is_none());
+            match any.downcast_ref::<Enum0>() {
+                Some(Enum0::InvalidValue { name, value }) => {
+                    assert_eq!(name, "foo");
+                    assert_eq!(value, "abc");
+                }
+                None => panic!(),
+            }
+        }
+
+        #[test]
+        fn reason_is_a_number_with_source() {
+            let err = Err::with_source(123i64, std::io::Error::other("oh no!"));
+
+            let any = err.reason_any();
+            assert!(any.is::<i64>());
+            assert_eq!(any.downcast_ref::<i64>(), Some(&123i64));
+        }
+    }
+
+    mod test_of_reason_cloned_or_default {
+        use super::*;
+
+        #[derive(Debug, Clone, Default, PartialEq)]
+        enum Enum0 {
+            #[default]
+            Unknown,
+            InvalidValue {
+                name: String,
+            },
+        }
+
+        #[test]
+        fn reason_matches_the_type() {
+            let err = Err::new(Enum0::InvalidValue {
+                name: "foo".to_string(),
+            });
+            assert_eq!(
+                err.reason_cloned_or_default::<Enum0>(),
+                Enum0::InvalidValue {
+                    name: "foo".to_string()
+                },
+            );
+        }
+
+        #[test]
+        fn reason_does_not_match_the_type() {
+            let err = Err::new(123i64);
+            assert_eq!(err.reason_cloned_or_default::<Enum0>(), Enum0::Unknown);
+            assert_eq!(err.reason_cloned_or_default::<String>(), "");
+        }
+    }
+
+    mod test_of_reason_code {
+        use super::*;
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Enum0 {
+            InvalidValue { name: String },
+            FailToGetValue,
+        }
+
+        #[derive(Debug)]
+        enum Enum1 {
+            InvalidValue,
+        }
+
+        #[test]
+        fn reason_type_name() {
+            let err = Err::new(Enum0::FailToGetValue);
+            assert_eq!(
+                err.reason_type_name(),
+                "errs::err::tests_of_err::test_of_reason_code::Enum0"
+            );
+
+            let err = Err::with_source(123i64, std::io::Error::other("oh no!"));
+            assert_eq!(err.reason_type_name(), "i64");
+        }
+
+        #[test]
+        fn same_reason_type_has_same_code() {
+            let err0 = Err::new(Enum0::InvalidValue {
+                name: "foo".to_string(),
+            });
+            let err1 = Err::with_source(Enum0::FailToGetValue, std::io::Error::other("oh no!"));
+            assert_eq!(err0.reason_code(), err1.reason_code());
+        }
+
+        #[test]
+        fn different_reason_types_have_different_codes() {
+            let err0 = Err::new(Enum0::FailToGetValue);
+            let err1 = Err::new(Enum1::InvalidValue);
+            assert_ne!(err0.reason_code(), err1.reason_code());
+        }
+
+        #[test]
+        fn code_is_fnv1a_of_type_name() {
+            let err = Err::new(());
+            // FNV-1a 64-bit hash of "()".
+            assert_eq!(err.reason_code(), 0x07e11f07b4a6665a);
+        }
+    }
+
+    mod test_of_boxed_reason {
+        use super::*;
+
+        trait PluginReason: fmt::Debug {
+            fn plugin_name(&self) -> &str;
+        }
+
+        #[derive(Debug)]
+        struct Timeout {
+            millis: u64,
+        }
+
+        impl PluginReason for Timeout {
+            fn plugin_name(&self) -> &str {
+                "timer"
+            }
+        }
+
+        #[derive(Debug)]
+        struct Unavailable;
+
+        impl PluginReason for Unavailable {
+            fn plugin_name(&self) -> &str {
+                "network"
+            }
+        }
+
+        fn plugin_err(is_timeout: bool) -> Err {
+            let reason: Box<dyn PluginReason + Send + Sync> = if is_timeout {
+                Box::new(Timeout { millis: 100 })
+            } else {
+                Box::new(Unavailable)
+            };
+            Err::new_boxed_reason(reason)
+        }
+
+        #[test]
+        fn trait_object_reason_round_trip() {
+            let err = plugin_err(true);
+            match err.boxed_reason::<dyn PluginReason + Send + Sync>() {
+                Ok(r) => {
+                    assert_eq!(r.plugin_name(), "timer");
+                    assert_eq!(format!("{r:?}"), "Timeout { millis: 100 }");
+                }
+                Err(_) => panic!(),
+            }
+
+            let err = plugin_err(false);
+            match err.boxed_reason::<dyn PluginReason + Send + Sync>() {
+                Ok(r) => assert_eq!(r.plugin_name(), "network"),
+                Err(_) => panic!(),
+            }
+            assert!(err.reason::<Box<dyn PluginReason + Send + Sync>>().is_ok());
+        }
+
+        #[test]
+        fn boxed_reason_of_different_type() {
+            let err = plugin_err(true);
+            assert!(err.boxed_reason::<Timeout>().is_err());
+
+            let err = Err::new(Timeout { millis: 1 });
+            assert!(err
+                .boxed_reason::<dyn PluginReason + Send + Sync>()
+                .is_err());
+
+            let err = Err::new_boxed_reason(Box::new(Timeout { millis: 1 }));
+            match err.boxed_reason::<Timeout>() {
+                Ok(r) => assert_eq!(r.millis, 1),
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn debug_output_of_boxed_reason() {
+            let err = plugin_err(false);
+            assert!(format!("{err:?}").contains("reason = alloc::boxed::Box<dyn errs::err::tests_of_err::test_of_boxed_reason::PluginReason + core::marker::Send + core::marker::Sync> Unavailable"));
+        }
+    }
+
+    mod test_of_column {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            IllegalState,
+        }
+
+        #[test]
+        fn column() {
+            let line = line!() + 1;
+            let err = Err::new(Reasons::IllegalState);
+            assert_eq!(err.line(), line);
+            assert_eq!(err.column(), 23);
+
+            let line = line!() + 1;
+            let err = Err::with_source_silent(Reasons::IllegalState, std::io::Error::other("x"));
+            assert_eq!(err.line(), line);
+            assert_eq!(err.column(), 23);
+        }
+
+        #[test]
+        fn debug_with_alternate_flag_contains_column() {
+            let err = Err::new(Reasons::IllegalState);
+            let line = line!() - 1;
+
+            #[cfg(unix)]
+            {
+                assert_eq!(
+                    format!("{err:?}"),
+                    format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_column::Reasons IllegalState, file = src/err.rs, line = {line} }}")
+                );
+                assert_eq!(
+                    format!("{err:#?}"),
+                    format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_column::Reasons IllegalState, file = src/err.rs, line = {line}, column = 23 }}")
+                );
+            }
+            #[cfg(windows)]
+            assert!(format!("{err:#?}").ends_with(&format!("line = {line}, column = 23 }}")));
+        }
+    }
+
+    mod test_of_with_sources {
+        use super::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToCommitAndRollback,
+        }
+
+        static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug)]
+        struct CountedError(&'static str);
+
+        impl fmt::Display for CountedError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl error::Error for CountedError {}
+
+        impl Drop for CountedError {
+            fn drop(&mut self) {
+                DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        #[test]
+        fn with_two_sources() {
+            let sources: Vec<Box<dyn error::Error + Send + Sync>> = vec![
+                Box::new(std::io::Error::other("fail to commit")),
+                Box::new(std::io::Error::other("fail to rollback")),
+            ];
+            let line = line!() + 1;
+            let err = Err::with_sources(Reasons::FailToCommitAndRollback, sources);
+
+            assert!(err.reason::<Reasons>().is_ok());
+            assert_eq!(err.line(), line);
+
+            let srcs = err.sources();
+            assert_eq!(srcs.len(), 2);
+            assert_eq!(srcs[0].to_string(), "fail to commit");
+            assert_eq!(srcs[1].to_string(), "fail to rollback");
+
+            assert_eq!(err.source().unwrap().to_string(), "fail to commit");
+            assert_eq!(
+                error::Error::source(&err).unwrap().to_string(),
+                "fail to commit"
+            );
+
+            assert_eq!(format!("{err}"), "FailToCommitAndRollback");
+            assert!(format!("{err:?}").contains("source = [Custom { kind: Other, error: \"fail to commit\" }, Custom { kind: Other, error: \"fail to rollback\" }]"));
+        }
+
+        #[test]
+        fn with_no_sources() {
+            let err = Err::with_sources(Reasons::FailToCommitAndRollback, Vec::new());
+            assert!(err.sources().is_empty());
+            assert!(err.source().is_none());
+        }
+
+        #[test]
+        fn sources_of_err_created_by_other_constructors() {
+            let err = Err::new(Reasons::FailToCommitAndRollback);
+            assert!(err.sources().is_empty());
+
+            let err = Err::with_source(
+                Reasons::FailToCommitAndRollback,
+                std::io::Error::other("oh no!"),
+            );
+            assert!(err.sources().is_empty());
+            assert!(err.source().is_some());
+        }
+
+        #[test]
+        fn drop_frees_all_sources() {
+            {
+                let sources: Vec<Box<dyn error::Error + Send + Sync>> = vec![
+                    Box::new(CountedError("first")),
+                    Box::new(CountedError("second")),
+                ];
+                let err = Err::with_sources(Reasons::FailToCommitAndRollback, sources);
+                assert_eq!(err.sources().len(), 2);
+                assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 0);
+            }
+            assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 2);
+        }
+    }
+
+    mod test_of_new_display {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FileNotFound { path: String },
+        }
+
+        impl fmt::Display for Reasons {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    Reasons::FileNotFound { path } => write!(f, "file not found: {path}"),
+                }
+            }
+        }
+
+        #[test]
+        fn display_differs_from_debug() {
+            let line = line!() + 1;
+            let err = Err::new_display(Reasons::FileNotFound {
+                path: "/aaa/bbb".to_string(),
+            });
+
+            assert_eq!(format!("{err}"), "file not found: /aaa/bbb");
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_new_display::Reasons FileNotFound {{ path: \"/aaa/bbb\" }}, file = src/err.rs, line = {line} }}")
+            );
+            match err.reason::<Reasons>() {
+                Ok(Reasons::FileNotFound { path }) => assert_eq!(path, "/aaa/bbb"),
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn display_of_new_is_debug_of_reason() {
+            let err = Err::new(Reasons::FileNotFound {
+                path: "/aaa/bbb".to_string(),
+            });
+            assert_eq!(format!("{err}"), "FileNotFound { path: \"/aaa/bbb\" }");
+        }
+
+        #[test]
+        fn display_chain_uses_display_of_reason() {
+            let err = Err::new_display(Reasons::FileNotFound {
+                path: "/aaa/bbb".to_string(),
+            });
+            let err = Err::with_source("while loading", err);
+            assert_eq!(
+                err.display_chain().to_string(),
+                "\"while loading\": file not found: /aaa/bbb"
+            );
+        }
+    }
+
+    mod test_of_fold_reason {
+        use super::*;
+
+        #[derive(Debug)]
+        struct HttpErr {
+            status: u16,
+        }
+
+        #[derive(Debug)]
+        enum Reasons {
+            NotFound { path: String },
+        }
+
+        #[test]
+        fn returns_value_of_function_if_reason_matches() {
+            let err = Err::new(HttpErr { status: 404 });
+            let code = err.fold_reason::<HttpErr, _>(500, |e| e.status);
+            assert_eq!(code, 404);
+        }
+
+        #[test]
+        fn returns_default_if_reason_does_not_match() {
+            let err = Err::new(Reasons::NotFound {
+                path: "/aaa".to_string(),
+            });
+            let code = err.fold_reason::<HttpErr, _>(500, |e| e.status);
+            assert_eq!(code, 500);
+        }
+
+        #[test]
+        fn with_non_copy_default() {
+            let err = Err::new(Reasons::NotFound {
+                path: "/aaa".to_string(),
+            });
+
+            let path = err.fold_reason::<Reasons, _>("unknown".to_string(), |r| match r {
+                Reasons::NotFound { path } => path.clone(),
+            });
+            assert_eq!(path, "/aaa");
+
+            let names = err.fold_reason::<HttpErr, _>(vec!["default".to_string()], |e| {
+                vec![e.status.to_string()]
+            });
+            assert_eq!(names, vec!["default".to_string()]);
+        }
+    }
+
+    mod test_of_display_string_and_debug_string {
+        use super::*;
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Reasons {
+            InvalidValue { name: String, value: String },
+        }
+
+        #[test]
+        fn equal_to_format() {
+            let err = Err::with_source(
+                Reasons::InvalidValue {
+                    name: "foo".to_string(),
+                    value: "abc".to_string(),
+                },
+                std::io::Error::other("oh no!"),
+            );
+
+            let s = err.display_string();
+            assert_eq!(s, format!("{}", err));
+            assert_eq!(s.capacity(), s.len());
+
+            let s = err.debug_string();
+            assert_eq!(s, format!("{:?}", err));
+            assert_eq!(s.capacity(), s.len());
+        }
+
+        #[test]
+        fn empty_display() {
+            let err = Err::new("");
+            assert_eq!(err.display_string(), "\"\"");
+
+            #[derive(Debug)]
+            struct Empty;
+            impl fmt::Display for Empty {
+                fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    Ok(())
+                }
+            }
+            let err = Err::new_display(Empty);
+            let s = err.display_string();
+            assert_eq!(s, "");
+            assert_eq!(s.capacity(), 0);
+        }
+    }
+
+    mod test_of_root_cause {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToReadFile,
+            FailToLoadConfig,
+        }
+
+        #[test]
+        fn three_level_chain() {
+            let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+            let err = Err::with_source(Reasons::FailToReadFile, io_err);
+            let err = Err::with_source(Reasons::FailToLoadConfig, err);
+
+            let root = err.root_cause();
+            let io_err = root.downcast_ref::<std::io::Error>().unwrap();
+            assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound);
+            assert_eq!(root.to_string(), "no such file");
+        }
+
+        #[test]
+        fn no_source() {
+            let err = Err::new(Reasons::FailToLoadConfig);
+
+            let root = err.root_cause();
+            let e = root.downcast_ref::<Err>().unwrap();
+            assert!(std::ptr::eq(e, &err));
+            assert!(e.reason::<Reasons>().is_ok());
+        }
+    }
+
+    mod test_of_fields {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[test]
+        fn fields_are_kept_in_order() {
+            let err = Err::new(Reasons::FailToDoSomething)
+                .with_field("user_id", 42)
+                .with_field("request_id", "abc")
+                .with_field(String::from("ratio"), 0.5);
+
+            assert_eq!(
+                err.fields(),
+                &[
+                    ("user_id".to_string(), "42".to_string()),
+                    ("request_id".to_string(), "abc".to_string()),
+                    ("ratio".to_string(), "0.5".to_string()),
+                ]
+            );
+            assert!(err.reason::<Reasons>().is_ok());
+        }
+
+        #[test]
+        fn fields_in_output() {
+            let line = line!() + 1;
+            let err = Err::new(Reasons::FailToDoSomething)
+                .with_field("user_id", 42)
+                .with_field("request_id", "abc");
+
+            assert_eq!(
+                format!("{err}"),
+                "FailToDoSomething [user_id=42, request_id=abc]"
+            );
+            #[cfg(unix)]
+            assert_eq!(
+                format!("{err:?}"),
+                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_fields::Reasons FailToDoSomething, file = src/err.rs, line = {line}, fields = [user_id=42, request_id=abc] }}")
+            );
+        }
+
+        #[test]
+        fn no_fields() {
+            let err = Err::new(Reasons::FailToDoSomething);
+            assert!(err.fields().is_empty());
+            assert_eq!(format!("{err}"), "FailToDoSomething");
+            assert!(!format!("{err:?}").contains(", fields = "));
+        }
+    }
+
+    mod tests_of_take_source {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[test]
+        fn take_source_of_err_with_source() {
+            let mut err = Err::with_source_silent(
+                Reasons::FailToDoSomething,
+                std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!"),
+            );
+            let source = err.take_source().unwrap();
+            let io_err = source.downcast_ref::<std::io::Error>().unwrap();
+            assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound);
+            assert_eq!(io_err.to_string(), "oh no!");
+
+            assert!(err.source().is_none());
+            assert!(err.take_source().is_none());
+            assert!(matches!(
+                err.reason::<Reasons>(),
+                Ok(Reasons::FailToDoSomething)
+            ));
+            assert_eq!(format!("{err}"), "FailToDoSomething");
+        }
+
+        #[test]
+        fn take_source_of_err_without_source() {
+            let mut err = Err::new_silent(Reasons::FailToDoSomething);
+            assert!(err.take_source().is_none());
+            assert!(err.source().is_none());
+            assert!(err.reason::<Reasons>().is_ok());
+        }
+
+        #[test]
+        fn take_source_of_err_with_sources() {
+            let mut err = Err::with_sources(
+                Reasons::FailToDoSomething,
+                [
+                    Box::new(std::io::Error::other("first")) as Box<dyn error::Error + Send + Sync>,
+                    Box::new(std::io::Error::other("second")),
+                ],
+            );
+            let source = err.take_source().unwrap();
+            assert_eq!(source.to_string(), "first, second");
+
+            assert!(err.source().is_none());
+            assert!(err.sources().is_empty());
+            assert!(err.reason::<Reasons>().is_ok());
+        }
+
+        #[test]
+        fn take_source_of_non_silent_err() {
+            let mut err = Err::with_source(Reasons::FailToDoSomething, std::io::Error::other("x"));
+            assert_eq!(err.take_source().unwrap().to_string(), "x");
+            assert!(err.source().is_none());
+        }
+    }
+
+    mod tests_of_reason_matches {
+        use super::*;
+
+        #[derive(Debug)]
+        enum DbErr {
+            ConnectionLost,
+        }
+
+        #[derive(Debug)]
+        enum NetErr {
+            Timeout,
+        }
+
+        #[derive(Debug)]
+        enum FileErr {
+            NotFound,
+        }
+
+        #[test]
+        fn reason_is() {
+            let err = Err::new(DbErr::ConnectionLost);
+            assert!(err.reason_is::<DbErr>());
+            assert!(!err.reason_is::<NetErr>());
+        }
+
+        #[test]
+        fn two_candidate_types() {
+            let err = Err::new(DbErr::ConnectionLost);
+            assert!(reason_matches!(err, DbErr | NetErr));
+            assert!(reason_matches!(err, NetErr | DbErr));
+
+            let err = Err::new(FileErr::NotFound);
+            assert!(!reason_matches!(err, DbErr | NetErr));
+        }
+
+        #[test]
+        fn three_candidate_types() {
+            let err = Err::new(NetErr::Timeout);
+            assert!(reason_matches!(err, DbErr | NetErr | FileErr));
+
+            let err = Err::new(FileErr::NotFound);
+            assert!(reason_matches!(&err, DbErr | NetErr | FileErr));
+
+            let err = Err::new(String::from("other"));
+            assert!(!reason_matches!(err, DbErr | NetErr | FileErr));
+        }
+
+        #[test]
+        fn single_candidate_type_and_trailing_comma() {
+            let err = Err::new(DbErr::ConnectionLost);
+            assert!(reason_matches!(err, DbErr));
+            assert!(reason_matches!(err, NetErr | DbErr,));
+            assert!(!reason_matches!(err, NetErr | FileErr,));
+        }
+    }
+
+    mod tests_of_eq_and_hash {
+        use super::*;
+        use std::collections::{HashMap, HashSet};
+
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        enum Reasons {
+            FailToDoSomething(u32),
+        }
+
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        enum OtherReasons {
+            FailToDoSomething(u32),
+        }
+
+        fn create_err(n: u32) -> Err {
+            Err::new(Reasons::FailToDoSomething(n))
+        }
+
+        fn create_other_err(n: u32) -> Err {
+            Err::new(OtherReasons::FailToDoSomething(n))
+        }
+
+        #[test]
+        fn errs_created_at_same_location_are_equal() {
+            let err0 = create_err(0);
+            let err1 = create_err(0);
+            assert_eq!(err0, err1);
+
+            let mut set = HashSet::new();
+            set.insert(err0);
+            set.insert(err1);
+            assert_eq!(set.len(), 1);
+        }
+
+        #[test]
+        fn errs_with_different_reason_types_or_locations_are_not_equal() {
+            let err0 = create_err(0);
+            let err1 = create_other_err(0);
+            let err2 = Err::new(Reasons::FailToDoSomething(0));
+            assert_ne!(err0, err1);
+            assert_ne!(err0, err2);
+
+            let mut set = HashSet::new();
+            set.insert(err0);
+            set.insert(err1);
+            set.insert(err2);
+            assert_eq!(set.len(), 3);
+        }
+
+        #[test]
+        fn count_errs_in_hash_map() {
+            let mut map = HashMap::<Err, usize>::new();
+            for i in 0..3 {
+                *map.entry(create_err(i)).or_default() += 1;
+            }
+            *map.entry(create_other_err(0)).or_default() += 1;
+
+            assert_eq!(map.len(), 2);
+            assert_eq!(map[&create_err(9)], 3);
+            assert_eq!(map[&create_other_err(9)], 1);
+        }
+
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        enum ComparableReasons {
+            FailToDoSomething(u32),
+        }
+
+        fn create_comparable_err(n: u32) -> Err {
+            Err::new(ComparableReasons::FailToDoSomething(n))
+                .with_comparable_reason::<ComparableReasons>()
+        }
+
+        #[test]
+        fn errs_with_comparable_reasons_are_compared_by_reason_values() {
+            assert_eq!(create_comparable_err(0), create_comparable_err(0));
+            assert_ne!(create_comparable_err(0), create_comparable_err(1));
+
+            let mut set = HashSet::new();
+            set.insert(create_comparable_err(0));
+            set.insert(create_comparable_err(0));
+            set.insert(create_comparable_err(1));
+            assert_eq!(set.len(), 2);
+        }
+
+        #[test]
+        fn err_with_comparable_reason_is_not_equal_to_err_without_it() {
+            let errs: Vec<Err> = (0..2)
+                .map(|i| {
+                    let err = Err::new(ComparableReasons::FailToDoSomething(0));
+                    if i == 0 {
+                        err.with_comparable_reason::<ComparableReasons>()
+                    } else {
+                        err
+                    }
+                })
+                .collect();
+            assert_ne!(errs[0], errs[1]);
+            assert_ne!(errs[1], errs[0]);
+        }
+
+        #[test]
+        fn do_nothing_if_reason_type_is_different() {
+            let err0 = create_err(0).with_comparable_reason::<ComparableReasons>();
+            let err1 = create_err(1);
+            assert_eq!(err0, err1);
+        }
+
+        #[test]
+        fn keep_comparable_reason_after_map_source() {
+            let err0 = create_comparable_err(0)
+                .map_source(|_| Some(std::io::Error::other("oh no!").into()));
+            let err1 = create_comparable_err(0);
+            let err2 = create_comparable_err(1)
+                .map_source(|_| Some(std::io::Error::other("oh no!").into()));
+            assert_eq!(err0, err1);
+            assert_ne!(err0, err2);
+        }
+    }
+
+    mod tests_of_severity {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[test]
+        fn default_severity() {
+            let err = Err::new(Reasons::FailToDoSomething);
+            assert_eq!(err.severity(), Severity::Error);
+            assert!(!format!("{err:?}").contains(", severity = "));
+
+            let err = Err::new_silent(Reasons::FailToDoSomething);
+            assert_eq!(err.severity(), Severity::Error);
+        }
+
+        #[test]
+        fn overridden_severity() {
+            let err = Err::new_with_severity(Reasons::FailToDoSomething, Severity::Fatal);
+            assert_eq!(err.severity(), Severity::Fatal);
+            assert!(format!("{err:?}").contains(", severity = Fatal"));
+
+            let err = err.with_severity(Severity::Warning);
+            assert_eq!(err.severity(), Severity::Warning);
+            assert!(format!("{err:?}").contains(", severity = Warning"));
+            assert_eq!(format!("{err}"), "FailToDoSomething");
+        }
+
+        #[test]
+        fn order_of_severities() {
+            assert!(Severity::Warning < Severity::Error);
+            assert!(Severity::Error < Severity::Fatal);
+            assert_eq!(Severity::default(), Severity::Error);
+        }
+    }
+
+    mod tests_of_downcast_reason {
+        use super::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        #[derive(Debug)]
+        enum Reasons {
+            IllegalState { state: String },
+        }
+
+        #[derive(Debug)]
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl fmt::Display for DropCounter {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "drop counter")
+            }
+        }
+
+        impl error::Error for DropCounter {}
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        #[test]
+        fn downcast_successfully() {
+            let err = Err::new_silent(Reasons::IllegalState {
+                state: "bad state".to_string(),
+            })
+            .with_field("user_id", 42);
+
+            match err.downcast_reason::<Reasons>() {
+                Ok(Reasons::IllegalState { state }) => assert_eq!(state, "bad state"),
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn fail_to_downcast_and_recover_err() {
+            let err = Err::new_silent(Reasons::IllegalState {
+                state: "bad state".to_string(),
+            });
+
+            let err = match err.downcast_reason::<String>() {
+                Ok(_) => panic!(),
+                Err(err) => err,
+            };
+            assert!(matches!(
+                err.reason::<Reasons>(),
+                Ok(Reasons::IllegalState { state }) if state == "bad state"
+            ));
+            assert_eq!(format!("{err}"), "IllegalState { state: \"bad state\" }");
+        }
+
+        #[test]
+        fn source_is_dropped_when_downcast() {
+            let count = Arc::new(AtomicUsize::new(0));
+            let err = Err::with_source_silent(
+                DropCounter(Arc::clone(&count)),
+                DropCounter(Arc::clone(&count)),
+            );
+
+            let reason = err.downcast_reason::<DropCounter>().unwrap();
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+            drop(reason);
+            assert_eq!(count.load(Ordering::SeqCst), 2);
+        }
+
+        #[test]
+        fn downcast_err_with_sources() {
+            let err = Err::with_sources(
+                Reasons::IllegalState {
+                    state: "bad state".to_string(),
+                },
+                [Box::new(std::io::Error::other("first")) as Box<dyn error::Error + Send + Sync>],
+            );
+            match err.downcast_reason::<Reasons>() {
+                Ok(Reasons::IllegalState { state }) => assert_eq!(state, "bad state"),
+                Err(_) => panic!(),
+            }
+        }
+    }
+
+    mod tests_of_over_aligned_types {
+        use super::*;
+
+        #[derive(Debug, PartialEq)]
+        #[repr(align(64))]
+        struct AlignedReason(u64);
+
+        #[derive(Debug)]
+        #[repr(align(64))]
+        struct AlignedError(u8);
+
+        impl fmt::Display for AlignedError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "aligned error {}", self.0)
+            }
+        }
+
+        impl error::Error for AlignedError {}
+
+        #[test]
+        fn over_aligned_reason() {
+            let err = Err::new(AlignedReason(7));
+            assert!(err.reason_is::<AlignedReason>());
+            assert_eq!(err.reason::<AlignedReason>().unwrap(), &AlignedReason(7));
+            assert!(err.reason_type_name().ends_with("AlignedReason"));
+            assert_eq!(format!("{err}"), "AlignedReason(7)");
+            assert!(err.source().is_none());
+
+            let err = Err::with_source(AlignedReason(8), std::io::Error::other("oh no!"));
+            assert_eq!(err.reason::<AlignedReason>().unwrap(), &AlignedReason(8));
+            assert_eq!(err.source().unwrap().to_string(), "oh no!");
+            assert!(format!("{err:?}")
+                .contains("AlignedReason(8), source = Custom { kind: Other, error: \"oh no!\" }"));
+
+            let err = Err::new_silent(AlignedReason(9));
+            assert_eq!(
+                err.downcast_reason::<AlignedReason>().unwrap(),
+                AlignedReason(9)
+            );
+        }
+
+        #[test]
+        fn over_aligned_source() {
+            let err = Err::with_source(42u32, AlignedError(1));
+            assert_eq!(err.reason::<u32>().unwrap(), &42);
+            err.match_reason::<u32>(|n| assert_eq!(*n, 42));
+            assert_eq!(err.fold_reason::<u32, _>(0, |n| *n), 42);
+            assert_eq!(format!("{err}"), "42");
+            assert_eq!(err.source().unwrap().to_string(), "aligned error 1");
+
+            let mut err = Err::with_source_silent(AlignedReason(3), AlignedError(2));
+            assert_eq!(err.reason::<AlignedReason>().unwrap(), &AlignedReason(3));
+            assert_eq!(err.take_source().unwrap().to_string(), "aligned error 2");
+            assert!(err.source().is_none());
+            assert_eq!(
+                err.downcast_reason::<AlignedReason>().unwrap(),
+                AlignedReason(3)
+            );
+        }
+    }
+
+    mod tests_of_reason_unwrap {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            IllegalState { state: String },
+        }
+
+        #[test]
+        fn return_reason_on_match() {
+            let err = Err::new(Reasons::IllegalState {
+                state: "bad state".to_string(),
+            });
+            let Reasons::IllegalState { state } = err.reason_unwrap::<Reasons>();
+            assert_eq!(state, "bad state");
+
+            let err = Err::new("string reason".to_string());
+            assert_eq!(err.reason_unwrap::<String>(), "string reason");
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "called `Err::reason_unwrap()` with a wrong reason type: expected `alloc::string::String`, actual `errs::err::tests_of_err::tests_of_reason_unwrap::Reasons`"
+        )]
+        fn panic_on_mismatch() {
+            let err = Err::new(Reasons::IllegalState {
+                state: "bad state".to_string(),
+            });
+            err.reason_unwrap::<String>();
+        }
+    }
+
+    mod tests_of_reason_from_source {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            Io,
+            Parse { text: String },
+        }
+
+        #[test]
+        fn create_err_from_unit_variant() {
+            let line = line!() + 1;
+            let err = reason_from_source!(Reasons::Io, std::io::Error::other("oh no!"));
+            assert!(matches!(err.reason::<Reasons>(), Ok(Reasons::Io)));
+            assert_eq!(err.source().unwrap().to_string(), "oh no!");
+            assert_eq!(err.line(), line);
+            #[cfg(unix)]
+            assert_eq!(err.file(), "src/err.rs");
+        }
+
+        #[test]
+        fn create_err_from_struct_variant() {
+            let text = "abc".to_string();
+            let source = text.parse::<u32>().unwrap_err();
+            let err = reason_from_source!(Reasons::Parse { text }, source,);
+            assert!(matches!(
+                err.reason::<Reasons>(),
+                Ok(Reasons::Parse { text }) if text == "abc"
+            ));
+            assert!(err
+                .source()
+                .unwrap()
+                .downcast_ref::<std::num::ParseIntError>()
+                .is_some());
+        }
+
+        #[test]
+        fn use_in_map_err() {
+            fn read() -> crate::Result<String> {
+                std::fs::read_to_string("/no/such/file")
+                    .map_err(|e| reason_from_source!(Reasons::Io, e))
+            }
+            let err = read().unwrap_err();
+            let io_err = err.source().unwrap().downcast_ref::<std::io::Error>();
+            assert_eq!(io_err.unwrap().kind(), std::io::ErrorKind::NotFound);
+        }
+    }
+
+    mod tests_of_with_arc_source {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToRead,
+            FailToWrite,
+        }
+
+        #[test]
+        fn two_errs_share_one_source() {
+            let io_error = Arc::new(std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!"));
+
+            let err0 = Err::with_arc_source(Reasons::FailToRead, io_error.clone());
+            let err1 = Err::with_arc_source(Reasons::FailToWrite, io_error.clone());
+            assert_eq!(Arc::strong_count(&io_error), 3);
+
+            let src0 = err0.source().unwrap();
+            let src1 = err1.source().unwrap();
+            assert!(std::ptr::addr_eq(src0, src1));
+            assert!(std::ptr::addr_eq(src0, Arc::as_ptr(&io_error)));
+
+            let io_err = src0.downcast_ref::<std::io::Error>().unwrap();
+            assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound);
+            assert_eq!(io_err.to_string(), "oh no!");
+            assert!(err1.sources().is_empty());
+
+            assert!(format!("{err0:?}")
+                .contains("FailToRead, source = Custom { kind: NotFound, error: \"oh no!\" }"));
+            assert_eq!(format!("{err1}"), "FailToWrite");
+
+            drop(err0);
+            drop(err1);
+            assert_eq!(Arc::strong_count(&io_error), 1);
+        }
+    }
+
+    mod tests_of_reason_chain {
+        use super::*;
+
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        enum IoReasons {
+            FileNotFound { path: String },
+        }
+
+        #[derive(Debug)]
+        enum AppReasons {
+            FailToLoadConfig,
+        }
+
+        #[derive(Debug)]
+        struct Wrapper(Err);
+
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "wrapper")
+            }
+        }
+
+        impl error::Error for Wrapper {
+            fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        #[test]
+        fn two_level_chain() {
+            let inner = Err::new(IoReasons::FileNotFound {
+                path: "/aaa/bbb".to_string(),
+            });
+            let err = Err::with_source(AppReasons::FailToLoadConfig, inner);
+
+            let infos: Vec<ReasonInfo<'_>> = err.reason_chain().collect();
+            assert_eq!(infos.len(), 2);
+
+            assert_eq!(
+                infos[0].type_name,
+                "errs::err::tests_of_err::tests_of_reason_chain::AppReasons"
+            );
+            assert_eq!(infos[0].debug, "FailToLoadConfig");
+            assert!(std::ptr::eq(infos[0].err, &err));
+
+            assert_eq!(
+                infos[1].type_name,
+                "errs::err::tests_of_err::tests_of_reason_chain::IoReasons"
+            );
+            assert_eq!(infos[1].debug, "FileNotFound { path: \"/aaa/bbb\" }");
+            assert!(infos[1].err.reason::<IoReasons>().is_ok());
+        }
+
+        #[test]
+        fn skip_non_err_sources() {
+            let io_error = std::io::Error::other("oh no!");
+            let inner = Err::with_source(
+                IoReasons::FileNotFound {
+                    path: "/aaa/bbb".to_string(),
+                },
+                io_error,
+            );
+            let middle = Wrapper(inner);
+            let err = Err::with_source(AppReasons::FailToLoadConfig, middle);
+
+            let debugs: Vec<String> = err.reason_chain().map(|info| info.debug).collect();
+            assert_eq!(
+                debugs,
+                vec!["FailToLoadConfig", "FileNotFound { path: \"/aaa/bbb\" }"]
+            );
+        }
+
+        #[test]
+        fn debug_of_display_reason() {
+            let err = Err::new_display("display reason".to_string());
+            let info = err.reason_chain().next().unwrap();
+            assert_eq!(info.type_name, "alloc::string::String");
+            assert_eq!(info.debug, "\"display reason\"");
+        }
+    }
+
+    mod tests_of_is_caused_by {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToReadFile,
+            FailToLoadConfig,
+        }
+
+        #[test]
+        fn chain_contains_target_type() {
+            let err = Err::with_source(Reasons::FailToReadFile, std::io::Error::other("oh no!"));
+            assert!(err.is_caused_by::<std::io::Error>());
+
+            let err = Err::with_source(Reasons::FailToLoadConfig, err);
+            assert!(err.is_caused_by::<std::io::Error>());
+            assert!(err.is_caused_by::<Err>());
+        }
+
+        #[test]
+        fn chain_does_not_contain_target_type() {
+            let err = Err::new(Reasons::FailToReadFile);
+            assert!(!err.is_caused_by::<std::io::Error>());
+            assert!(!err.is_caused_by::<Err>());
+
+            let err = Err::with_source(Reasons::FailToLoadConfig, err);
+            assert!(!err.is_caused_by::<std::io::Error>());
+            assert!(!err.is_caused_by::<std::fmt::Error>());
+        }
+    }
+
+    mod tests_of_chain_len {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToReadFile,
+            FailToLoadConfig,
+        }
+
+        #[test]
+        fn no_source() {
+            let err = Err::new(Reasons::FailToReadFile);
+            assert_eq!(err.chain_len(), 1);
+            assert_eq!(err.chain_len(), err.chain().count());
+        }
+
+        #[test]
+        fn one_source() {
+            let err = Err::with_source(Reasons::FailToReadFile, std::io::Error::other("oh no!"));
+            assert_eq!(err.chain_len(), 2);
+            assert_eq!(err.chain_len(), err.chain().count());
+        }
+
+        #[test]
+        fn nested_err() {
+            let err = Err::with_source(Reasons::FailToReadFile, std::io::Error::other("oh no!"));
+            let err = Err::with_source(Reasons::FailToLoadConfig, err);
+            assert_eq!(err.chain_len(), 3);
+            assert_eq!(err.chain_len(), err.chain().count());
+        }
+    }
+
+    mod tests_of_with_source_fn {
+        use super::*;
+        use std::io;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[test]
+        fn source_is_not_created_if_not_accessed() {
+            let count = Arc::new(AtomicUsize::new(0));
+            let count_in_fn = Arc::clone(&count);
+
+            let err = Err::with_source_fn(Reasons::FailToDoSomething, move || {
+                count_in_fn.fetch_add(1, Ordering::SeqCst);
+                io::Error::other("oh no!")
+            });
+            assert!(err.reason_is::<Reasons>());
+            assert_eq!(err.to_string(), "FailToDoSomething");
+            drop(err);
+
+            assert_eq!(count.load(Ordering::SeqCst), 0);
+        }
+
+        #[test]
+        fn source_is_created_once_when_accessed() {
+            let count = Arc::new(AtomicUsize::new(0));
+            let count_in_fn = Arc::clone(&count);
+
+            let err = Err::with_source_fn(Reasons::FailToDoSomething, move || {
+                count_in_fn.fetch_add(1, Ordering::SeqCst);
+                io::Error::other("oh no!")
+            });
+
+            let src = err.source().unwrap();
+            assert_eq!(src.to_string(), "oh no!");
+            assert!(src.downcast_ref::<io::Error>().is_some());
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+
+            assert_eq!(err.chain_len(), 2);
+            assert!(err.is_caused_by::<io::Error>());
+            assert!(
+                format!("{err:?}").contains(", source = Custom { kind: Other, error: \"oh no!\" }")
+            );
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+        }
+
+        #[test]
+        fn source_is_created_once_when_accessed_from_multiple_threads() {
+            let count = Arc::new(AtomicUsize::new(0));
+            let count_in_fn = Arc::clone(&count);
+
+            let err = Err::with_source_fn(Reasons::FailToDoSomething, move || {
+                count_in_fn.fetch_add(1, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                io::Error::other("oh no!")
+            });
+
+            std::thread::scope(|s| {
+                for _ in 0..4 {
+                    s.spawn(|| {
+                        assert_eq!(err.source().unwrap().to_string(), "oh no!");
+                    });
+                }
+            });
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+        }
+    }
+
+    mod tests_of_reason_mut {
+        use super::*;
 
-        write!(f, "{} {{ ", any::type_name::<Err>())?;
-        debug_fn(self.reason_and_source.non_null_ptr, f)?;
-        write!(f, ", file = {}, line = {}", self.file, self.line)?;
-        write!(f, " }}")
+        #[derive(Debug)]
+        enum Reasons {
+            FailToLoadConfig { notes: Vec<String> },
+        }
+
+        #[test]
+        fn mutate_reason() {
+            let mut err = Err::new_silent(Reasons::FailToLoadConfig { notes: Vec::new() });
+            match err.reason_mut::<Reasons>() {
+                Some(Reasons::FailToLoadConfig { notes }) => notes.push("note 1".to_string()),
+                None => panic!(),
+            }
+            match err.reason_mut::<Reasons>() {
+                Some(Reasons::FailToLoadConfig { notes }) => notes.push("note 2".to_string()),
+                None => panic!(),
+            }
+            assert!(err.reason_mut::<String>().is_none());
+
+            match err.reason::<Reasons>() {
+                Ok(Reasons::FailToLoadConfig { notes }) => assert_eq!(notes, &["note 1", "note 2"]),
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn mutate_reason_of_err_with_source() {
+            let mut err = Err::with_source(
+                Reasons::FailToLoadConfig { notes: Vec::new() },
+                std::io::Error::other("oh no!"),
+            );
+            if let Some(Reasons::FailToLoadConfig { notes }) = err.reason_mut::<Reasons>() {
+                notes.push("note".to_string());
+            }
+            assert_eq!(format!("{err}"), "FailToLoadConfig { notes: [\"note\"] }");
+        }
     }
-}
 
-impl fmt::Display for Err {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let display_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).display_fn };
-        display_fn(self.reason_and_source.non_null_ptr, f)
+    mod tests_of_here {
+        use super::*;
+
+        #[test]
+        fn display_and_debug_show_location() {
+            let line = line!() + 1;
+            let err = Err::here();
+
+            assert_eq!(err.file(), file!());
+            assert_eq!(err.line(), line);
+            assert_eq!(err.to_string(), format!("{}:{}:23", file!(), line));
+            assert_eq!(
+                format!("{err:?}"),
+                format!(
+                    "errs::Err {{ reason = errs::Located {}:{}:23, file = {}, line = {} }}",
+                    file!(),
+                    line,
+                    file!(),
+                    line
+                )
+            );
+            assert!(err.reason::<()>().is_err());
+        }
     }
-}
 
-impl error::Error for Err {
-    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-        let source_fn = unsafe { (*self.reason_and_source.non_null_ptr.as_ptr()).source_fn };
-        source_fn(self.reason_and_source.non_null_ptr)
+    mod tests_of_has_source {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[test]
+        fn err_created_with_new_has_no_source() {
+            let err = Err::new(Reasons::FailToDoSomething);
+            assert!(!err.has_source());
+        }
+
+        #[test]
+        fn err_created_with_source_has_source() {
+            let err = Err::with_source(Reasons::FailToDoSomething, std::io::Error::other("oh no!"));
+            assert!(err.has_source());
+        }
     }
-}
 
-impl<R, E> ReasonAndSource<R, E>
-where
-    R: fmt::Debug + Send + Sync + 'static,
-    E: error::Error + Send + Sync + 'static,
-{
-    fn new(reason: R) -> Self {
-        Self {
-            is_fn: is_reason::<R>,
-            drop_fn: drop_reason_and_source::<R, E>,
-            debug_fn: debug_reason_and_source::<R, E>,
-            display_fn: display_reason_and_source::<R, E>,
-            source_fn: get_source::<R, E>,
-            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
-            is_referenced_by_another: atomic::AtomicBool::new(true),
-            reason_and_source: (reason, None),
+    mod tests_of_reason_eq {
+        use super::*;
+
+        #[derive(Debug, PartialEq)]
+        enum Reasons {
+            NotFound { name: String },
+            PermissionDenied,
+        }
+
+        #[test]
+        fn matching_value() {
+            let err = Err::new(Reasons::NotFound {
+                name: "foo".to_string(),
+            });
+            assert!(err.reason_eq(&Reasons::NotFound {
+                name: "foo".to_string()
+            }));
+        }
+
+        #[test]
+        fn mismatching_value() {
+            let err = Err::new(Reasons::NotFound {
+                name: "foo".to_string(),
+            });
+            assert!(!err.reason_eq(&Reasons::NotFound {
+                name: "bar".to_string()
+            }));
+            assert!(!err.reason_eq(&Reasons::PermissionDenied));
+        }
+
+        #[test]
+        fn mismatching_type() {
+            let err = Err::new("not found".to_string());
+            assert!(!err.reason_eq(&Reasons::PermissionDenied));
+            assert!(err.reason_eq(&"not found".to_string()));
         }
     }
 
-    fn with_source(reason: R, source: E) -> Self {
-        Self {
-            is_fn: is_reason::<R>,
-            drop_fn: drop_reason_and_source::<R, E>,
-            debug_fn: debug_reason_and_source::<R, E>,
-            display_fn: display_reason_and_source::<R, E>,
-            source_fn: get_source::<R, E>,
-            #[cfg(any(feature = "notify", feature = "notify-tokio"))]
-            is_referenced_by_another: atomic::AtomicBool::new(true),
-            reason_and_source: (reason, Some(*Box::new(source))),
+    mod tests_of_split {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[test]
+        fn split_err_with_source() {
+            let err = Err::with_source_silent(
+                Reasons::FailToDoSomething,
+                std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!"),
+            );
+            let (reason, source) = err.split();
+            assert_eq!(reason, "FailToDoSomething");
+
+            let source = source.unwrap();
+            let io_err = source.downcast_ref::<std::io::Error>().unwrap();
+            assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound);
+        }
+
+        #[test]
+        fn split_err_without_source() {
+            let err = Err::new_silent(Reasons::FailToDoSomething).with_field("id", 1);
+            let (reason, source) = err.split();
+            assert_eq!(reason, "FailToDoSomething [id=1]");
+            assert!(source.is_none());
         }
     }
-}
 
-fn is_reason<R>(type_id: any::TypeId) -> bool
-where
-    R: fmt::Debug + Send + Sync + 'static,
-{
-    any::TypeId::of::<R>() == type_id
-}
+    mod tests_of_reason_or {
+        use super::*;
 
-fn drop_reason_and_source<R, E>(ptr: ptr::NonNull<ReasonAndSource>)
-where
-    R: fmt::Debug + Send + Sync + 'static,
-    E: error::Error + Send + Sync + 'static,
-{
-    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
-    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
-    {
-        let is_ref = unsafe { &(*typed_ptr).is_referenced_by_another };
-        if !is_ref.fetch_and(false, atomic::Ordering::AcqRel) {
-            unsafe { drop(Box::from_raw(typed_ptr)) };
+        #[derive(Debug, PartialEq)]
+        enum Reasons {
+            NotFound { name: String },
+            Unknown,
+        }
+
+        #[test]
+        fn matching_type() {
+            let err = Err::new(Reasons::NotFound {
+                name: "foo".to_string(),
+            });
+            let r = err.reason_or::<Reasons>(&Reasons::Unknown);
+            assert_eq!(
+                r,
+                &Reasons::NotFound {
+                    name: "foo".to_string()
+                }
+            );
+        }
+
+        #[test]
+        fn mismatching_type() {
+            let err = Err::new("not found".to_string());
+            let default = Reasons::Unknown;
+            let r = err.reason_or::<Reasons>(&default);
+            assert!(std::ptr::eq(r, &default));
         }
     }
-    #[cfg(not(any(feature = "notify", feature = "notify-tokio")))]
-    {
-        unsafe { drop(Box::from_raw(typed_ptr)) };
+
+    mod tests_of_file_normalized {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[test]
+        fn normalize_unix_path() {
+            let file = normalize_path("src/err.rs");
+            assert!(matches!(file, Cow::Borrowed("src/err.rs")));
+        }
+
+        #[test]
+        fn normalize_windows_path() {
+            let file = normalize_path("src\\notify\\mod.rs");
+            assert!(matches!(file, Cow::Owned(_)));
+            assert_eq!(file, "src/notify/mod.rs");
+        }
+
+        #[test]
+        fn file_of_err() {
+            let err = Err::new(Reasons::FailToDoSomething);
+            assert_eq!(err.file_normalized(), "src/err.rs");
+        }
     }
-}
 
-fn debug_reason_and_source<R, E>(
-    ptr: ptr::NonNull<ReasonAndSource>,
-    f: &mut fmt::Formatter<'_>,
-) -> fmt::Result
-where
-    R: fmt::Debug + Send + Sync + 'static,
-    E: error::Error + Send + Sync + 'static,
-{
-    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
-    let reason_and_source = unsafe { &(*typed_ptr).reason_and_source };
-    write!(
-        f,
-        "reason = {} {:?}",
-        any::type_name::<R>(),
-        reason_and_source.0
-    )?;
+    mod tests_of_redacted {
+        use super::*;
 
-    match &reason_and_source.1 {
-        Some(src) => write!(f, ", source = {:?}", src),
-        None => Ok(()),
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Reasons {
+            FailToOpenFile { path: String },
+        }
+
+        #[test]
+        fn redact_err_without_source() {
+            let err = Err::new_silent(Reasons::FailToOpenFile {
+                path: "/home/alice/secret.txt".to_string(),
+            })
+            .with_field("id", 1);
+            let (file, line, column) = (err.file(), err.line(), err.column());
+
+            let err = err.redacted();
+            assert!(err.reason::<Reasons>().is_err());
+            assert!(err.reason_eq(&Redacted));
+            assert!(err.is_silent());
+            assert_eq!(err.file(), file);
+            assert_eq!(err.line(), line);
+            assert_eq!(err.column(), column);
+            assert!(err.fields().is_empty());
+            assert!(err.source().is_none());
+            assert_eq!(format!("{err}"), "<redacted>");
+            assert!(!format!("{err:?}").contains("secret"));
+        }
+
+        #[test]
+        fn redact_err_with_source() {
+            let err = Err::with_source_silent(
+                Reasons::FailToOpenFile {
+                    path: "/home/alice/secret.txt".to_string(),
+                },
+                std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!"),
+            )
+            .with_severity(Severity::Fatal);
+            let line = err.line();
+
+            let err = err.redacted();
+            assert!(err.reason::<Reasons>().is_err());
+            assert!(err.reason_is::<Redacted>());
+            assert_eq!(err.line(), line);
+            assert_eq!(err.severity(), Severity::Fatal);
+
+            let source = err.source().unwrap();
+            let io_err = source.downcast_ref::<std::io::Error>().unwrap();
+            assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound);
+            assert!(err.sources().is_empty());
+            assert_eq!(format!("{err}"), "<redacted>");
+        }
+
+        #[test]
+        fn redact_err_with_sources() {
+            let err = Err::with_sources(
+                Reasons::FailToOpenFile {
+                    path: "/home/alice/secret.txt".to_string(),
+                },
+                [
+                    Box::new(std::io::Error::other("first")) as Box<dyn error::Error + Send + Sync>,
+                    Box::new(std::io::Error::other("second")),
+                ],
+            );
+
+            let err = err.redacted();
+            assert!(err.reason_is::<Redacted>());
+            assert_eq!(err.sources().len(), 2);
+            assert_eq!(err.sources()[1].to_string(), "second");
+            assert_eq!(err.source().unwrap().to_string(), "first");
+        }
     }
-}
 
-fn display_reason_and_source<R, E>(
-    ptr: ptr::NonNull<ReasonAndSource>,
-    f: &mut fmt::Formatter<'_>,
-) -> fmt::Result
-where
-    R: fmt::Debug + Send + Sync + 'static,
-    E: error::Error + Send + Sync + 'static,
-{
-    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
-    write!(f, "{:?}", unsafe { &(*typed_ptr).reason_and_source.0 })
-}
+    mod tests_of_reasons {
+        use super::*;
 
-fn get_source<R, E>(
-    ptr: ptr::NonNull<ReasonAndSource>,
-) -> Option<&'static (dyn error::Error + 'static)>
-where
-    R: fmt::Debug + Send + Sync + 'static,
-    E: error::Error + Send + Sync + 'static,
-{
-    let typed_ptr = ptr.cast::<ReasonAndSource<R, E>>().as_ptr();
-    match unsafe { &(*typed_ptr).reason_and_source.1 } {
-        Some(src) => Some(src),
-        None => None,
+        #[derive(Debug)]
+        struct HttpError {
+            status: u16,
+        }
+
+        #[derive(Debug)]
+        enum AppReasons {
+            FailToSync,
+        }
+
+        #[test]
+        fn chain_has_two_reasons_of_same_type() {
+            let io_err = std::io::Error::other("oh no!");
+            let err2 = Err::with_source(HttpError { status: 503 }, io_err);
+            let err1 = Err::with_source(AppReasons::FailToSync, err2);
+            let err0 = Err::with_source(HttpError { status: 502 }, err1);
+
+            let statuses: Vec<u16> = err0.reasons::<HttpError>().map(|e| e.status).collect();
+            assert_eq!(statuses, [502, 503]);
+
+            let mut it = err0.reasons::<AppReasons>();
+            assert!(matches!(it.next(), Some(AppReasons::FailToSync)));
+            assert!(it.next().is_none());
+        }
+
+        #[test]
+        fn chain_has_no_reason_of_type() {
+            let err1 = Err::new(AppReasons::FailToSync);
+            let err0 = Err::with_source(AppReasons::FailToSync, err1);
+            assert_eq!(err0.reasons::<HttpError>().count(), 0);
+            assert_eq!(err0.reasons::<AppReasons>().count(), 2);
+        }
     }
-}
 
-#[cfg(test)]
-mod tests_of_err {
-    use super::*;
-    use std::sync::{LazyLock, Mutex};
+    mod tests_of_context_string {
+        use super::*;
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+            InvalidValue { value: i32 },
+        }
+
+        #[track_caller]
+        fn create_err() -> Err {
+            Err::new(Reasons::InvalidValue { value: 3 })
+        }
+
+        #[test]
+        fn context_string() {
+            let line = line!() + 1;
+            let err = Err::new(Reasons::FailToDoSomething);
+            #[cfg(unix)]
+            assert_eq!(
+                err.context_string(),
+                format!("FailToDoSomething (src/err.rs:{line})")
+            );
+            #[cfg(windows)]
+            assert_eq!(
+                err.context_string(),
+                format!("FailToDoSomething (src\\err.rs:{line})")
+            );
+        }
+
+        #[test]
+        fn context_string_with_fields_of_err_created_in_function() {
+            let line = line!() + 1;
+            let err = create_err().with_field("id", 1);
+            #[cfg(unix)]
+            assert_eq!(
+                err.context_string(),
+                format!("InvalidValue {{ value: 3 }} [id=1] (src/err.rs:{line})")
+            );
+            assert_eq!(err.line(), line);
+        }
+    }
+
+    mod tests_of_new_at {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToDoSomething,
+        }
+
+        #[track_caller]
+        fn create_err() -> Err {
+            let loc = Location::caller();
+            Err::new_at(Reasons::FailToDoSomething, loc.file(), loc.line())
+        }
+
+        #[test]
+        fn explicit_location() {
+            let err = Err::new_at(Reasons::FailToDoSomething, "src/foo.rs", 123);
+            assert!(err.reason::<Reasons>().is_ok());
+            assert_eq!(err.file(), "src/foo.rs");
+            assert_eq!(err.line(), 123);
+            assert_eq!(err.column(), 0);
+            assert!(!err.is_silent());
+        }
+
+        #[test]
+        fn location_forwarded_by_wrapper() {
+            let line = line!() + 1;
+            let err = create_err();
+            #[cfg(unix)]
+            assert_eq!(err.file(), "src/err.rs");
+            assert_eq!(err.line(), line);
+        }
+    }
+
+    mod tests_of_from_panic_payload {
+        use super::*;
+        use std::panic;
+
+        fn message_of(err: &Err) -> &str {
+            &err.reason::<PanicReason>().unwrap().message
+        }
+
+        #[test]
+        fn str_payload() {
+            let payload = panic::catch_unwind(|| panic!("oh no!")).unwrap_err();
+            let err = Err::from_panic_payload(payload);
+            assert_eq!(message_of(&err), "oh no!");
+        }
+
+        #[test]
+        fn string_payload() {
+            let n = 3;
+            let payload = panic::catch_unwind(|| panic!("oh no! {n}")).unwrap_err();
+            let err = Err::from_panic_payload(payload);
+            assert_eq!(message_of(&err), "oh no! 3");
+        }
 
-    struct Logger {
-        log_vec: Vec<String>,
+        #[test]
+        fn unknown_payload() {
+            let payload = panic::catch_unwind(|| panic::panic_any(42u32)).unwrap_err();
+            let err = Err::from_panic_payload(payload);
+            assert_eq!(message_of(&err), "unknown panic");
+        }
+
+        #[test]
+        fn location_is_call_site() {
+            let line = line!() + 1;
+            let err = Err::from_panic_payload(Box::new("oh no!"));
+            assert_eq!(err.line(), line);
+        }
     }
 
-    impl Logger {
-        fn new() -> Self {
-            Self {
-                log_vec: Vec::<String>::new(),
+    mod tests_of_new_with_fields {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Reasons {
+            FailToReadFile { path: String, size: u64 },
+            Unknown,
+        }
+
+        impl ReasonFields for Reasons {
+            fn fields(&self) -> Vec<(String, String)> {
+                match self {
+                    Reasons::FailToReadFile { path, size } => vec![
+                        ("path".to_string(), path.clone()),
+                        ("size".to_string(), size.to_string()),
+                    ],
+                    Reasons::Unknown => Vec::new(),
+                }
             }
         }
-        fn log(&mut self, s: &str) {
-            self.log_vec.push(s.to_string());
+
+        #[test]
+        fn reason_contributes_fields() {
+            let line = line!() + 1;
+            let err = Err::new_with_fields(Reasons::FailToReadFile {
+                path: "/aaa/bbb".to_string(),
+                size: 12,
+            })
+            .with_field("user", "alice");
+            assert_eq!(err.line(), line);
+            assert_eq!(
+                err.fields(),
+                &[
+                    ("path".to_string(), "/aaa/bbb".to_string()),
+                    ("size".to_string(), "12".to_string()),
+                    ("user".to_string(), "alice".to_string()),
+                ]
+            );
+            assert!(matches!(
+                err.reason::<Reasons>(),
+                Ok(Reasons::FailToReadFile { .. })
+            ));
         }
-        fn assert_logs(&self, logs: &[&str]) {
-            if self.log_vec.len() != logs.len() {
-                assert_eq!(self.log_vec, logs);
-                return;
-            }
-            for i in 0..self.log_vec.len() {
-                assert_eq!(self.log_vec[i], logs[i]);
-            }
+
+        #[test]
+        fn reason_contributes_no_fields() {
+            let err = Err::new_with_fields(Reasons::Unknown);
+            assert!(err.fields().is_empty());
+            assert_eq!(format!("{err}"), "Unknown");
         }
     }
 
-    const BASE_LINE: u32 = line!();
-
-    mod test_of_drop {
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    mod tests_of_age {
         use super::*;
 
-        static LOGGER: LazyLock<Mutex<Logger>> = LazyLock::new(|| Mutex::new(Logger::new()));
-
-        #[allow(dead_code)]
         #[derive(Debug)]
         enum Enum0 {
-            InvalidValue { name: String, value: String },
-        }
-        impl Drop for Enum0 {
-            fn drop(&mut self) {
-                LOGGER.lock().unwrap().log("drop Enum0");
-            }
+            Reason0,
         }
 
-        fn create_err() -> Result<(), Err> {
-            let err = Err::new(Enum0::InvalidValue {
-                name: "foo".to_string(),
-                value: "abc".to_string(),
-            });
-            LOGGER.lock().unwrap().log("created Enum0");
-            Err(err)
+        #[test]
+        fn age_is_at_least_elapsed_time() {
+            let err = Err::new_silent(Enum0::Reason0);
+            assert!(err.age().is_none());
+
+            let err = Err::new(Enum0::Reason0);
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            let age = err.age().unwrap();
+            assert!(age >= chrono::Duration::milliseconds(50));
         }
+    }
 
-        fn consume_err() {
-            let err = create_err().unwrap_err();
-            assert_eq!(
-                format!("{err}"),
-                "InvalidValue { name: \"foo\", value: \"abc\" }",
-            );
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_drop::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 19),
-            );
-            #[cfg(windows)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_drop::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 19),
-            );
+    mod tests_of_with_shared_source {
+        use super::*;
 
-            LOGGER.lock().unwrap().log("consumed Enum0");
+        #[derive(Debug)]
+        enum Enum0 {
+            ConnectionRefused,
+            FailToLoadUser,
+            FailToLoadItems,
         }
 
         #[test]
-        fn test() {
-            consume_err();
+        fn share_source_between_two_errors() {
+            let source = Arc::new(Err::new(Enum0::ConnectionRefused));
 
-            std::thread::sleep(std::time::Duration::from_secs(1));
+            let line = line!() + 1;
+            let err0 = Err::with_shared_source(Enum0::FailToLoadUser, Arc::clone(&source));
+            let err1 = Err::with_shared_source(Enum0::FailToLoadItems, Arc::clone(&source));
+            assert_eq!(err0.line(), line);
+            assert_eq!(Arc::strong_count(&source), 3);
 
-            LOGGER.lock().unwrap().log("end");
+            assert!(matches!(err0.reason::<Enum0>(), Ok(Enum0::FailToLoadUser)));
+            assert!(matches!(err1.reason::<Enum0>(), Ok(Enum0::FailToLoadItems)));
 
-            LOGGER.lock().unwrap().assert_logs(&[
-                "created Enum0",
-                "consumed Enum0",
-                "drop Enum0",
-                "end",
-            ]);
+            let src0 = err0.source().unwrap().downcast_ref::<Err>().unwrap();
+            let src1 = err1.source().unwrap().downcast_ref::<Err>().unwrap();
+            assert!(ptr::eq(src0, &*source));
+            assert!(ptr::eq(src1, &*source));
+            assert!(matches!(
+                src0.reason::<Enum0>(),
+                Ok(Enum0::ConnectionRefused)
+            ));
+
+            drop(err0);
+            drop(err1);
+            assert_eq!(Arc::strong_count(&source), 1);
+            assert!(matches!(
+                source.reason::<Enum0>(),
+                Ok(Enum0::ConnectionRefused)
+            ));
         }
     }
 
-    mod test_of_new {
+    mod tests_of_reason_debug_eq {
         use super::*;
 
+        #[allow(dead_code)]
         #[derive(Debug)]
         enum Enum0 {
-            InvalidValue { name: String, value: String },
+            InvalidValue { name: String, value: i32 },
         }
 
         #[test]
-        fn new_err() {
+        fn compare_with_debug_string_of_reason() {
             let err = Err::new(Enum0::InvalidValue {
                 name: "foo".to_string(),
-                value: "abc".to_string(),
+                value: 1,
             });
+            assert!(err.reason_debug_eq(r#"InvalidValue { name: "foo", value: 1 }"#));
+            assert!(!err.reason_debug_eq(r#"InvalidValue { name: "foo", value: 2 }"#));
+            assert!(!err.reason_debug_eq(&format!("{err:?}")));
+        }
 
-            #[cfg(unix)]
-            assert_eq!(err.file(), "src/err.rs");
-            #[cfg(windows)]
-            assert_eq!(err.file(), "src\\err.rs");
-            assert_eq!(err.line(), BASE_LINE + 74);
+        #[test]
+        fn ignore_location() {
+            let err0 = Err::new("oops".to_string());
+            let err1 = Err::new("oops".to_string());
+            assert_ne!(err0.line(), err1.line());
+            assert!(err0.reason_debug_eq(r#""oops""#));
+            assert!(err1.reason_debug_eq(r#""oops""#));
+        }
+    }
+
+    mod tests_of_reason_type_short_name {
+        use super::*;
+
+        #[derive(Debug)]
+        enum Enum0 {
+            Reason0,
+        }
+
+        mod nested {
+            pub mod inner {
+                #[derive(Debug)]
+                pub enum Enum1 {
+                    Reason1,
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        struct Wrapper<T>(T);
+
+        #[test]
+        fn plain_enum() {
+            let err = Err::new(Enum0::Reason0);
+            assert_eq!(err.reason_type_short_name(), "Enum0");
+        }
+
+        #[test]
+        fn nested_module_enum() {
+            let err = Err::new(nested::inner::Enum1::Reason1);
+            assert!(err.reason_type_name().ends_with("::nested::inner::Enum1"));
+            assert_eq!(err.reason_type_short_name(), "Enum1");
+        }
+
+        #[test]
+        fn generic_type() {
+            let err = Err::new(Wrapper(Enum0::Reason0));
             assert_eq!(
-                format!("{err}"),
-                "InvalidValue { name: \"foo\", value: \"abc\" }",
+                err.reason_type_short_name(),
+                "Wrapper<errs::err::tests_of_err::tests_of_reason_type_short_name::Enum0>"
             );
-            #[cfg(unix)]
+
+            let err = Err::new(Some("abc".to_string()));
             assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_new::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 74),
+                err.reason_type_short_name(),
+                "Option<alloc::string::String>"
             );
-            #[cfg(windows)]
+        }
+
+        #[test]
+        fn non_path_type() {
+            assert_eq!(short_type_name("&str"), "&str");
+            assert_eq!(short_type_name("u32"), "u32");
             assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_new::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 74),
+                short_type_name("(u32, alloc::string::String)"),
+                "(u32, alloc::string::String)"
+            );
+            assert_eq!(
+                short_type_name("[alloc::string::String; 2]"),
+                "[alloc::string::String; 2]"
             );
+        }
+    }
 
-            match err.reason::<Enum0>().unwrap() {
-                Enum0::InvalidValue { name, value } => {
-                    assert_eq!(name, "foo");
-                    assert_eq!(value, "abc");
-                }
-            }
-            assert!(err.source().is_none());
+    mod tests_of_correlation_id {
+        use super::*;
+        use std::io;
+
+        #[derive(Debug)]
+        enum Enum0 {
+            Reason0,
+        }
+
+        #[test]
+        fn attach_correlation_id() {
+            crate::set_correlation_id(Some("req-123".to_string()));
+
+            let err = Err::new(Enum0::Reason0);
+            assert_eq!(err.correlation_id(), Some("req-123"));
+
+            let err = Err::with_source(Enum0::Reason0, io::Error::other("oh no!"));
+            assert_eq!(err.correlation_id(), Some("req-123"));
+
+            let err = Err::new_silent(Enum0::Reason0).redacted();
+            assert_eq!(err.correlation_id(), Some("req-123"));
+
+            crate::set_correlation_id(None);
+        }
+
+        #[test]
+        fn release_correlation_id_on_downcast_reason() {
+            crate::set_correlation_id(Some("req-456".to_string()));
+            let err = Err::new(Enum0::Reason0);
+            crate::set_correlation_id(None);
+
+            let id = Arc::clone(err.meta.as_ref().unwrap().correlation_id.as_ref().unwrap());
+            assert_eq!(Arc::strong_count(&id), 2);
+
+            assert!(err.downcast_reason::<Enum0>().is_ok());
+            assert_eq!(Arc::strong_count(&id), 1);
+        }
+
+        #[test]
+        fn no_correlation_id() {
+            crate::set_correlation_id(None);
+
+            let err = Err::new(Enum0::Reason0);
+            assert_eq!(err.correlation_id(), None);
         }
     }
 
-    mod test_of_with_source {
+    mod tests_of_map_source {
         use super::*;
+        use std::io;
 
+        #[allow(dead_code)]
         #[derive(Debug)]
         enum Enum0 {
-            InvalidValue { name: String, value: String },
+            FailToReadFile { path: String },
         }
 
         #[test]
-        fn source_is_a_standard_error() {
-            let source = std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!");
-            let err = Err::with_source(
-                Enum0::InvalidValue {
-                    name: "foo".to_string(),
-                    value: "abc".to_string(),
+        fn replace_source() {
+            let line = line!() + 1;
+            let err = Err::with_source_silent(
+                Enum0::FailToReadFile {
+                    path: "/aaa/bbb".to_string(),
                 },
-                source,
+                io::Error::other("/aaa/bbb is not found"),
             );
+            let err = err.map_source(|src| {
+                assert_eq!(src.unwrap().to_string(), "/aaa/bbb is not found");
+                Some(io::Error::other("file is not found").into())
+            });
 
-            #[cfg(unix)]
-            assert_eq!(err.file, "src/err.rs");
-            #[cfg(windows)]
-            assert_eq!(err.file, "src\\err.rs");
+            assert_eq!(err.source().unwrap().to_string(), "file is not found");
+            assert!(err.sources().is_empty());
+            match err.reason::<Enum0>() {
+                Ok(Enum0::FailToReadFile { path }) => assert_eq!(path, "/aaa/bbb"),
+                Err(_) => panic!(),
+            }
+            assert_eq!(err.line(), line);
+            assert!(err.is_silent());
+        }
 
-            assert_eq!(err.line, BASE_LINE + 120);
-            assert_eq!(
-                format!("{err}"),
-                "InvalidValue { name: \"foo\", value: \"abc\" }",
-            );
+        #[test]
+        fn add_source() {
+            let err = Err::new_silent(Enum0::FailToReadFile {
+                path: "/aaa/bbb".to_string(),
+            });
+            let err = err.map_source(|src| {
+                assert!(src.is_none());
+                Some(io::Error::other("oh no!").into())
+            });
+            assert_eq!(err.source().unwrap().to_string(), "oh no!");
+            assert!(err.reason::<Enum0>().is_ok());
+        }
 
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = Custom {{ kind: NotFound, error: \"oh no!\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 120),
-            );
-            #[cfg(windows)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = Custom {{ kind: NotFound, error: \"oh no!\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 120),
+        #[test]
+        fn clear_source() {
+            let err = Err::with_source_silent(
+                Enum0::FailToReadFile {
+                    path: "/aaa/bbb".to_string(),
+                },
+                io::Error::other("oh no!"),
             );
+            let err = err.map_source(|_| None);
+            assert!(err.source().is_none());
+            assert!(err.reason::<Enum0>().is_ok());
 
-            match err.reason::<Enum0>().unwrap() {
-                Enum0::InvalidValue { name, value } => {
-                    assert_eq!(name, "foo");
-                    assert_eq!(value, "abc");
-                }
+            match err.downcast_reason::<Enum0>() {
+                Ok(Enum0::FailToReadFile { path }) => assert_eq!(path, "/aaa/bbb"),
+                Err(_) => panic!(),
             }
+        }
 
-            assert!(err.source().is_some());
-            match err.source() {
-                Some(e) => match e.downcast_ref::<std::io::Error>() {
-                    Some(io_err) => {
-                        assert_eq!((*io_err).kind(), std::io::ErrorKind::NotFound);
-                    }
-                    _ => unreachable!(),
+        #[test]
+        fn keep_fields_and_severity() {
+            let err = Err::with_source_silent(
+                Enum0::FailToReadFile {
+                    path: "/aaa/bbb".to_string(),
                 },
-                None => unreachable!(),
-            }
+                io::Error::other("oh no!"),
+            )
+            .with_severity(Severity::Warning)
+            .with_field("user", "alice");
+            let err = err.map_source(|_| None);
+            assert_eq!(err.severity(), Severity::Warning);
+            assert_eq!(err.fields(), &[("user".to_string(), "alice".to_string())]);
         }
 
-        #[derive(Debug)]
-        struct MyError {
-            message: String,
-        }
-        impl MyError {
-            fn new(msg: &str) -> Self {
-                Self {
-                    message: msg.to_string(),
+        #[test]
+        fn keep_display_of_reason() {
+            #[derive(Debug)]
+            struct FileNotFound {
+                path: String,
+            }
+            impl fmt::Display for FileNotFound {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "file not found: {}", self.path)
                 }
             }
+
+            let err = Err::new_display(FileNotFound {
+                path: "/aaa/bbb".to_string(),
+            });
+            assert_eq!(err.to_string(), "file not found: /aaa/bbb");
+
+            let err = err.map_source(|_| Some(io::Error::other("oh no!").into()));
+            assert_eq!(err.to_string(), "file not found: /aaa/bbb");
+            assert_eq!(err.source().unwrap().to_string(), "oh no!");
+
+            let err = err.map_source(|_| None);
+            assert_eq!(err.to_string(), "file not found: /aaa/bbb");
+
+            let err = Err::new_silent(Enum0::FailToReadFile {
+                path: "/aaa/bbb".to_string(),
+            });
+            let err = err.map_source(|_| Some(io::Error::other("oh no!").into()));
+            assert_eq!(err.to_string(), "FailToReadFile { path: \"/aaa/bbb\" }");
         }
-        impl fmt::Display for MyError {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-                write!(f, "MyError{{message:\"{}\"}}", self.message)
-            }
+    }
+
+    mod tests_of_chain_eq {
+        use super::*;
+        use std::io;
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Enum0 {
+            FailToLoad { name: String },
+        }
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Enum1 {
+            FailToRead { path: String },
+        }
+
+        fn create_err(path: &str) -> Err {
+            let inner = Err::new(Enum1::FailToRead {
+                path: path.to_string(),
+            });
+            Err::with_source(
+                Enum0::FailToLoad {
+                    name: "config".to_string(),
+                },
+                inner,
+            )
         }
-        impl error::Error for MyError {}
 
         #[test]
-        fn source_is_a_user_defined_error() {
-            let source = MyError::new("hello");
-            let err = Err::with_source(
-                Enum0::InvalidValue {
-                    name: "foo".to_string(),
-                    value: "abc".to_string(),
+        fn same_two_level_chains() {
+            let err0 = create_err("/aaa/bbb");
+            let err1 = create_err("/aaa/bbb");
+            assert!(err0.chain_eq(&err1));
+            assert!(err1.chain_eq(&err0));
+
+            let inner = Err::new(Enum1::FailToRead {
+                path: "/aaa/bbb".to_string(),
+            });
+            let err2 = Err::with_source(
+                Enum0::FailToLoad {
+                    name: "config".to_string(),
                 },
-                source,
+                inner,
             );
+            assert_ne!(err0.line(), err2.line());
+            assert!(err0.chain_eq(&err2));
+        }
 
-            #[cfg(unix)]
-            assert_eq!(err.file, "src/err.rs");
-            #[cfg(windows)]
-            assert_eq!(err.file, "src\\err.rs");
-            assert_eq!(err.line, BASE_LINE + 190);
-            assert_eq!(
-                format!("{err}"),
-                "InvalidValue { name: \"foo\", value: \"abc\" }",
-            );
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = MyError {{ message: \"hello\" }}, file = src/err.rs, line = {} }}", BASE_LINE + 190),
+        #[test]
+        fn chains_of_different_lengths() {
+            let err0 = create_err("/aaa/bbb");
+            let err1 = Err::new(Enum0::FailToLoad {
+                name: "config".to_string(),
+            });
+            assert!(!err0.chain_eq(&err1));
+            assert!(!err1.chain_eq(&err0));
+        }
+
+        #[test]
+        fn chains_with_different_innermost_reasons() {
+            let err0 = create_err("/aaa/bbb");
+            let err1 = create_err("/aaa/ccc");
+            assert!(!err0.chain_eq(&err1));
+        }
+
+        #[test]
+        fn chains_including_non_err_sources() {
+            let err0 = Err::with_source(
+                Enum1::FailToRead {
+                    path: "/aaa".to_string(),
+                },
+                io::Error::other("oh no!"),
             );
-            #[cfg(windows)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_with_source::Enum0 InvalidValue {{ name: \"foo\", value: \"abc\" }}, source = MyError {{ message: \"hello\" }}, file = src\\err.rs, line = {} }}", BASE_LINE + 190),
+            let err1 = Err::with_source(
+                Enum1::FailToRead {
+                    path: "/aaa".to_string(),
+                },
+                io::Error::other("oh no!"),
             );
-
-            assert!(err.source().is_some());
-            match err.source() {
-                Some(e) => match e.downcast_ref::<MyError>() {
-                    Some(my_err) => {
-                        assert_eq!((*my_err).message, "hello".to_string());
-                    }
-                    _ => unreachable!(),
+            let err2 = Err::with_source(
+                Enum1::FailToRead {
+                    path: "/aaa".to_string(),
                 },
-                None => unreachable!(),
-            }
+                io::Error::other("oops"),
+            );
+            let err3 = create_err("/aaa");
+            assert!(err0.chain_eq(&err1));
+            assert!(!err0.chain_eq(&err2));
+            assert!(!err0.chain_eq(&err3));
         }
     }
 
-    mod test_of_reason {
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    mod tests_of_to_notified {
         use super::*;
 
+        #[derive(Debug)]
+        enum Reasons {
+            FailToReadFile { path: String },
+            FailToLoadConfig,
+        }
+
         #[test]
-        fn reason_is_a_boolean() {
-            let err = Err::new(true);
-            assert_eq!(format!("{err}"), "true");
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = bool true, file = src/err.rs, line = {} }}",
-                    BASE_LINE + 236,
-                ),
-            );
-            #[cfg(windows)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = bool true, file = src\\err.rs, line = {} }}",
-                    BASE_LINE + 236,
-                ),
-            );
+        fn copy_shares_reason_and_has_rendered_source() {
+            let err = Err::with_source_silent(
+                Reasons::FailToReadFile {
+                    path: "/etc/app.conf".to_string(),
+                },
+                std::io::Error::other("oh no!"),
+            )
+            .with_field("id", 1);
+            let copy = err.to_notified();
 
-            match err.reason() {
-                Ok(true) => {}
-                Ok(false) => unreachable!(),
-                Err(_) => unreachable!(),
+            assert_eq!(copy.to_string(), err.to_string());
+            assert_eq!(format!("{copy:?}"), format!("{err:?}"));
+            assert_eq!(copy.reason_type_name(), err.reason_type_name());
+            assert_eq!(copy.file(), err.file());
+            assert_eq!(copy.line(), err.line());
+            assert!(copy.reason_is::<Reasons>());
+            match copy.reason::<Reasons>() {
+                Ok(Reasons::FailToReadFile { path }) => assert_eq!(path, "/etc/app.conf"),
+                _ => panic!(),
             }
+            assert!(ptr::eq(
+                copy.reason::<Reasons>().unwrap(),
+                err.reason::<Reasons>().unwrap()
+            ));
 
-            assert!(err.source().is_none());
+            let source = copy.source().unwrap();
+            assert_eq!(source.to_string(), "oh no!");
+            assert_eq!(
+                format!("{source:?}"),
+                format!("{:?}", err.source().unwrap())
+            );
+            assert!(copy.sources().is_empty());
         }
 
         #[test]
-        fn reason_is_a_number() {
-            let err = Err::new(123i64);
-            assert_eq!(format!("{err}"), "123");
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = i64 123, file = src/err.rs, line = {} }}",
-                    BASE_LINE + 266,
+        fn copy_renders_err_in_source_chain_as_err() {
+            let err = Err::with_source_silent(
+                Reasons::FailToLoadConfig,
+                Err::with_source_silent(
+                    Reasons::FailToReadFile {
+                        path: "/etc/app.conf".to_string(),
+                    },
+                    std::io::Error::other("oh no!"),
                 ),
             );
-            #[cfg(windows)]
+            let copy = err.to_notified();
+
+            let source = copy.source().unwrap();
+            let source_err = source.downcast_ref::<Err>().unwrap();
             assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = i64 123, file = src\\err.rs, line = {} }}",
-                    BASE_LINE + 266,
-                ),
+                source_err.to_string(),
+                "FailToReadFile { path: \"/etc/app.conf\" }"
             );
+            assert_eq!(source_err.source().unwrap().to_string(), "oh no!");
+            assert_eq!(format!("{copy:?}"), format!("{err:?}"));
+        }
 
-            match err.reason::<i64>() {
-                Ok(n) => assert_eq!(*n, 123i64),
-                Err(_) => unreachable!(),
-            }
+        #[test]
+        fn copy_keeps_multiple_sources() {
+            let err = Err::with_sources(
+                Reasons::FailToLoadConfig,
+                vec![
+                    Box::new(std::io::Error::other("first")) as Box<dyn error::Error + Send + Sync>,
+                    Box::new(std::io::Error::other("second")),
+                ],
+            );
+            let copy = err.to_notified();
 
-            assert!(err.source().is_none());
+            assert_eq!(copy.sources().len(), 2);
+            assert_eq!(copy.sources()[1].to_string(), "second");
+            assert_eq!(format!("{copy:?}"), format!("{err:?}"));
         }
 
         #[test]
-        fn reason_is_a_string() {
-            let err = Err::new("abc".to_string());
-            assert_eq!(format!("{err}"), "\"abc\"");
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = alloc::string::String \"abc\", file = src/err.rs, line = {} }}",
-                    BASE_LINE + 295,
-                ),
-            );
-            #[cfg(windows)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = alloc::string::String \"abc\", file = src\\err.rs, line = {} }}",
-                    BASE_LINE + 295,
-                ),
+        fn source_of_original_is_not_shared_with_copy() {
+            let mut err = Err::with_source(
+                Reasons::FailToReadFile {
+                    path: "/etc/app.conf".to_string(),
+                },
+                std::io::Error::other("oh no!"),
             );
+            let copy = err.to_notified();
 
-            match err.reason::<String>() {
-                Ok(s) => assert_eq!(s, "abc"),
-                Err(_) => unreachable!(),
-            }
-
+            assert_eq!(err.take_source().unwrap().to_string(), "oh no!");
             assert!(err.source().is_none());
-        }
-
-        #[derive(Debug)]
-        struct StructA {
-            name: String,
-            value: i64,
+            assert_eq!(copy.source().unwrap().to_string(), "oh no!");
         }
 
         #[test]
-        fn reason_is_a_struct() {
-            let err = Err::new(StructA {
-                name: "abc".to_string(),
-                value: 123,
+        fn reason_is_not_mutable_nor_movable_while_shared() {
+            let mut err = Err::new_silent(Reasons::FailToReadFile {
+                path: "/etc/app.conf".to_string(),
             });
-            assert_eq!(format!("{err}"), "StructA { name: \"abc\", value: 123 }");
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_reason::StructA StructA {{ name: \"abc\", value: 123 }}, file = src/err.rs, line = {} }}", BASE_LINE + 330),
-            );
-            #[cfg(windows)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!("errs::Err {{ reason = errs::err::tests_of_err::test_of_reason::StructA StructA {{ name: \"abc\", value: 123 }}, file = src\\err.rs, line = {} }}", BASE_LINE + 330),
-            );
+            let copy = err.to_notified();
 
-            match err.reason::<StructA>() {
-                Ok(st) => {
-                    assert_eq!(st.name, "abc".to_string());
-                    assert_eq!(st.value, 123);
-                }
-                Err(_) => unreachable!(),
+            assert!(err.reason_mut::<Reasons>().is_none());
+            let mut err = match err.downcast_reason::<Reasons>() {
+                Ok(_) => panic!(),
+                Err(err) => err,
+            };
+            assert!(err.reason::<Reasons>().is_ok());
+
+            drop(copy);
+
+            if let Some(Reasons::FailToReadFile { path }) = err.reason_mut::<Reasons>() {
+                path.push_str(".bak");
             }
+            match err.downcast_reason::<Reasons>() {
+                Ok(Reasons::FailToReadFile { path }) => assert_eq!(path, "/etc/app.conf.bak"),
+                _ => panic!(),
+            }
+        }
 
-            assert!(err.source().is_none());
+        #[test]
+        fn reason_of_copy_is_movable_after_original_is_dropped() {
+            let err = Err::new_silent(Reasons::FailToLoadConfig);
+            let copy = err.to_notified();
+            let copy = match copy.downcast_reason::<Reasons>() {
+                Ok(_) => panic!(),
+                Err(copy) => copy,
+            };
+
+            drop(err);
+
+            match copy.downcast_reason::<Reasons>() {
+                Ok(Reasons::FailToLoadConfig) => {}
+                _ => panic!(),
+            }
         }
 
         #[test]
-        fn reaason_is_an_unit() {
-            let err = Err::new(());
-            assert_eq!(format!("{err}"), "()");
-            #[cfg(unix)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = () (), file = src/err.rs, line = {} }}",
-                    BASE_LINE + 359,
-                ),
-            );
-            #[cfg(windows)]
-            assert_eq!(
-                format!("{err:?}"),
-                format!(
-                    "errs::Err {{ reason = () (), file = src\\err.rs, line = {} }}",
-                    BASE_LINE + 359,
-                ),
+        fn map_source_while_shared() {
+            let err = Err::with_source_silent(
+                Reasons::FailToReadFile {
+                    path: "/etc/app.conf".to_string(),
+                },
+                std::io::Error::other("oh no!"),
             );
+            let copy = err.to_notified();
 
-            match err.reason::<()>() {
-                Ok(()) => {}
-                Err(_) => unreachable!(),
-            }
+            let err = err.map_source(|src| {
+                assert_eq!(src.unwrap().to_string(), "oh no!");
+                Some(std::io::Error::other("file is not found").into())
+            });
+            assert_eq!(err.source().unwrap().to_string(), "file is not found");
+            assert_eq!(copy.source().unwrap().to_string(), "oh no!");
+            assert_eq!(err.reason_type_name(), copy.reason_type_name());
+            assert!(err.reason::<Reasons>().is_ok());
 
+            let err = err.map_source(|_| None);
             assert!(err.source().is_none());
-        }
-    }
+            let err = match err.downcast_reason::<Reasons>() {
+                Ok(_) => panic!(),
+                Err(err) => err,
+            };
 
-    mod test_of_match_reason {
-        use super::*;
+            drop(copy);
 
-        #[allow(dead_code)]
-        #[derive(Debug)]
-        enum Enum0 {
-            InvalidValue { name: String, value: String },
-            FailToGetValue { name: String },
+            match err.downcast_reason::<Reasons>() {
+                Ok(Reasons::FailToReadFile { path }) => assert_eq!(path, "/etc/app.conf"),
+                _ => panic!(),
+            }
         }
 
         #[test]
-        fn reason_is_enum() {
-            let err = Err::new(Enum0::InvalidValue {
-                name: "foo".to_string(),
-                value: "abc".to_string(),
-            });
-
-            match err.reason::<String>() {
-                Ok(_) => panic!(),
-                Err(err) => match err.reason::<Enum0>() {
-                    Ok(r) => match r {
-                        Enum0::InvalidValue { name, value } => {
-                            assert_eq!(name, "foo");
-                            assert_eq!(value, "abc");
-                        }
-                        _ => panic!(),
-                    },
-                    Err(_) => panic!(),
-                },
+        fn copy_keeps_display_and_comparability_of_reason() {
+            #[derive(Debug, PartialEq, Eq, Hash)]
+            struct FileNotFound;
+            impl fmt::Display for FileNotFound {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "file not found")
+                }
             }
 
-            err.match_reason::<String>(|_s| {
-                panic!();
-            })
-            .match_reason::<Enum0>(|r| match r {
-                Enum0::InvalidValue { name, value } => {
-                    assert_eq!(name, "foo");
-                    assert_eq!(value, "abc");
-                }
-                _ => panic!(),
-            });
+            let err = Err::new_display(FileNotFound).with_comparable_reason::<FileNotFound>();
+            let copy = err.to_notified();
+            assert_eq!(copy.to_string(), "file not found");
+            assert!(copy == err);
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod tests_of_no_std {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Reasons {
+        IllegalState { code: u32 },
+    }
+
+    #[test]
+    fn new_and_reason() {
+        let line = line!() + 1;
+        let err = Err::new(Reasons::IllegalState { code: 12 });
+
+        match err.reason::<Reasons>() {
+            Ok(r) => assert_eq!(*r, Reasons::IllegalState { code: 12 }),
+            Err(_) => panic!(),
+        }
+        assert!(err.reason::<u32>().is_err());
+        assert_eq!(err.line(), line);
+        assert!(err.file().ends_with("err.rs"));
+    }
+
+    #[test]
+    fn new_boxed_reason_and_boxed_reason() {
+        let err = Err::new_boxed_reason(Box::new(Reasons::IllegalState { code: 3 }));
+
+        match err.boxed_reason::<Reasons>() {
+            Ok(r) => assert_eq!(*r, Reasons::IllegalState { code: 3 }),
+            Err(_) => panic!(),
         }
     }
+
+    #[cfg(feature = "core-error")]
+    #[test]
+    fn source_through_core_error() {
+        let err = Err::new(Reasons::IllegalState { code: 4 });
+        let e: &dyn core::error::Error = &err;
+        assert!(e.source().is_none());
+        assert_eq!(alloc::format!("{e}"), "IllegalState { code: 4 }");
+    }
+}
+
+#[cfg(test)]
+mod tests_of_size {
+    use super::*;
+
+    #[test]
+    fn err_is_small_enough_to_be_returned_in_result() {
+        // file (2 words), line, column, is_silent and severity (1 word), meta, and the pointer
+        // to the reason and source. Clippy's `result_large_err` lints errors of 128 bytes or more.
+        assert!(mem::size_of::<Err>() <= 6 * mem::size_of::<usize>());
+        assert_eq!(mem::size_of::<Result<(), Err>>(), mem::size_of::<Err>());
+    }
 }
@@ -0,0 +1,114 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{AggregateReason, Err, Errors};
+
+use std::error;
+
+impl Errors {
+    /// Creates an empty `Errors` instance.
+    pub const fn new() -> Self {
+        Self { errs: Vec::new() }
+    }
+
+    /// Adds an error to this collection.
+    ///
+    /// # Parameters
+    /// - `err`: The error to be added.
+    pub fn push(&mut self, err: Err) {
+        self.errs.push(err);
+    }
+
+    /// Returns `true` if no error has been added.
+    pub fn is_empty(&self) -> bool {
+        self.errs.is_empty()
+    }
+
+    /// Returns the number of the added errors.
+    pub fn len(&self) -> usize {
+        self.errs.len()
+    }
+
+    /// Converts this collection into a `Result`, consuming this instance.
+    ///
+    /// If no error has been added, this method returns `Ok` with the given value. Otherwise,
+    /// this method returns an `Err` whose reason is [`AggregateReason`] and whose sources are
+    /// the added errors in the order they were added.
+    ///
+    /// # Parameters
+    /// - `ok`: The value to be returned when no error has been added.
+    ///
+    /// # Returns
+    /// `Ok` with the given value, or an `Err` combining the added errors.
+    #[track_caller]
+    pub fn into_result<T>(self, ok: T) -> Result<T, Err> {
+        if self.errs.is_empty() {
+            return Ok(ok);
+        }
+
+        let count = self.errs.len();
+        let sources = self
+            .errs
+            .into_iter()
+            .map(|err| Box::new(err) as Box<dyn error::Error + Send + Sync>);
+        Err(Err::with_sources(AggregateReason { count }, sources))
+    }
+}
+
+#[cfg(test)]
+mod tests_of_errors {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Reasons {
+        EmptyName,
+        InvalidAge { age: i32 },
+        InvalidEmail { email: String },
+    }
+
+    #[test]
+    fn no_error_is_added() {
+        let errors = Errors::new();
+        assert!(errors.is_empty());
+        assert_eq!(errors.len(), 0);
+        assert_eq!(errors.into_result(123).unwrap(), 123);
+    }
+
+    #[test]
+    fn collect_three_errors() {
+        let mut errors = Errors::default();
+        errors.push(Err::new(Reasons::EmptyName));
+        errors.push(Err::new(Reasons::InvalidAge { age: -1 }));
+        errors.push(Err::new(Reasons::InvalidEmail {
+            email: "abc".to_string(),
+        }));
+        assert!(!errors.is_empty());
+        assert_eq!(errors.len(), 3);
+
+        let err = errors.into_result(()).unwrap_err();
+        match err.reason::<AggregateReason>() {
+            Ok(AggregateReason { count }) => assert_eq!(*count, 3),
+            Err(_) => panic!(),
+        }
+
+        let sources: Vec<&Err> = err
+            .sources()
+            .iter()
+            .map(|e| e.downcast_ref::<Err>().unwrap())
+            .collect();
+        assert_eq!(sources.len(), 3);
+        assert!(matches!(
+            sources[0].reason::<Reasons>(),
+            Ok(Reasons::EmptyName)
+        ));
+        assert!(matches!(
+            sources[1].reason::<Reasons>(),
+            Ok(Reasons::InvalidAge { age: -1 })
+        ));
+        match sources[2].reason::<Reasons>() {
+            Ok(Reasons::InvalidEmail { email }) => assert_eq!(email, "abc"),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{Err, FirstErr};
+
+use std::sync;
+
+impl FirstErr {
+    /// Creates an empty `FirstErr` instance.
+    pub const fn new() -> Self {
+        Self {
+            cell: sync::OnceLock::new(),
+        }
+    }
+
+    /// Sets the given error if no error has been set yet, otherwise drops it.
+    ///
+    /// # Parameters
+    /// - `err`: The error to be set.
+    pub fn set_if_empty(&self, err: Err) {
+        let _ = self.cell.set(err);
+    }
+
+    /// Takes the error set first, consuming this instance.
+    ///
+    /// # Returns
+    /// The error set first, or `None` if no error has been set.
+    pub fn take(self) -> Option<Err> {
+        self.cell.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests_of_first_err {
+    use super::*;
+    use std::thread;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoTask { id: u32 },
+    }
+
+    #[test]
+    fn no_error_is_set() {
+        let first_err = FirstErr::new();
+        assert!(first_err.take().is_none());
+    }
+
+    #[test]
+    fn keep_first_error() {
+        let first_err = FirstErr::default();
+        first_err.set_if_empty(Err::new_silent(Reasons::FailToDoTask { id: 1 }));
+        first_err.set_if_empty(Err::new_silent(Reasons::FailToDoTask { id: 2 }));
+
+        let err = first_err.take().unwrap();
+        match err.reason::<Reasons>() {
+            Ok(Reasons::FailToDoTask { id }) => assert_eq!(*id, 1),
+            Err(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn race_to_set_error() {
+        let first_err = sync::Arc::new(FirstErr::new());
+        let barrier = sync::Arc::new(sync::Barrier::new(8));
+
+        let handles: Vec<_> = (0..8)
+            .map(|id| {
+                let first_err = sync::Arc::clone(&first_err);
+                let barrier = sync::Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    first_err.set_if_empty(Err::new_silent(Reasons::FailToDoTask { id }));
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let first_err = sync::Arc::into_inner(first_err).unwrap();
+        let err = first_err.take().unwrap();
+        match err.reason::<Reasons>() {
+            Ok(Reasons::FailToDoTask { id }) => assert!(*id < 8),
+            Err(_) => panic!(),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock, Mutex};
+
+pub(crate) static POOL: LazyLock<Mutex<HashSet<Arc<str>>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+pub(crate) fn intern(pool: &Mutex<HashSet<Arc<str>>>, s: &str) -> Arc<str> {
+    let mut set = match pool.lock() {
+        Ok(set) => set,
+        Err(e) => e.into_inner(),
+    };
+    if let Some(interned) = set.get(s) {
+        return Arc::clone(interned);
+    }
+    let interned: Arc<str> = Arc::from(s);
+    set.insert(Arc::clone(&interned));
+    interned
+}
+
+#[cfg(test)]
+mod tests_of_intern {
+    use super::*;
+    use crate::Err;
+
+    #[test]
+    fn same_strings_share_allocation() {
+        let pool = Mutex::new(HashSet::new());
+
+        let s0 = intern(&pool, "connection refused");
+        let owned = String::from("connection refused");
+        let s1 = intern(&pool, &owned);
+        let s2 = intern(&pool, "timed out");
+
+        assert!(Arc::ptr_eq(&s0, &s1));
+        assert!(!Arc::ptr_eq(&s0, &s2));
+        assert_eq!(pool.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn interned_reasons_share_allocation() {
+        let err0 = Err::new_interned("fail to connect to the database");
+        let err1 = Err::new_interned("fail to connect to the database");
+        let err2 = Err::new_interned("fail to read a file");
+
+        let r0 = err0.reason::<Arc<str>>().unwrap();
+        let r1 = err1.reason::<Arc<str>>().unwrap();
+        let r2 = err2.reason::<Arc<str>>().unwrap();
+
+        assert_eq!(&**r0, "fail to connect to the database");
+        assert!(Arc::ptr_eq(r0, r1));
+        assert!(!Arc::ptr_eq(r0, r2));
+        assert!(err0.reason::<String>().is_err());
+    }
+}
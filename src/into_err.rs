@@ -0,0 +1,57 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{Err, IntoErr};
+
+use core::fmt;
+
+impl<R> IntoErr for R
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn into_err(self) -> Err {
+        Err::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_into_err {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        InvalidValue { value: i32 },
+    }
+
+    fn check(value: i32) -> crate::Result<()> {
+        if value < 0 {
+            return Err(Reasons::InvalidValue { value }.into_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reason() {
+        assert!(check(1).is_ok());
+
+        let err = check(-1).unwrap_err();
+        match err.reason::<Reasons>() {
+            Ok(Reasons::InvalidValue { value }) => assert_eq!(*value, -1),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn location_is_call_site() {
+        let line = line!() + 1;
+        let err = Reasons::InvalidValue { value: -1 }.into_err();
+        #[cfg(unix)]
+        assert_eq!(err.file(), "src/into_err.rs");
+        #[cfg(windows)]
+        assert_eq!(err.file(), "src\\into_err.rs");
+        assert_eq!(err.line(), line);
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Err;
+
+use std::fmt::Write;
+
+impl Err {
+    /// Formats this error as a single-line JSON object.
+    ///
+    /// The JSON object has the following properties:
+    /// - `reason`: The reason formatted with its `Debug` implementation.
+    /// - `file`: The file name where the error occurred.
+    /// - `line`: The line number where the error occurred.
+    /// - `fields`: The object of the fields of this error. This property is output only if this
+    ///   error has fields.
+    /// - `causes`: The array of the `Display` strings of the errors in the source chain, from
+    ///   the direct source to the root cause.
+    ///
+    /// This is useful to output errors as structured logs in the JSON Lines format.
+    ///
+    /// ```rust
+    /// use errs::Err;
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// let err = Err::with_source(Reasons::FailToDoSomething, io::Error::other("oh no!"));
+    /// let json = err.to_json_line();
+    /// assert!(json.starts_with("{\"reason\":\"FailToDoSomething\",\"file\":\""));
+    /// assert!(json.ends_with(",\"causes\":[\"oh no!\"]}"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn to_json_line(&self) -> String {
+        let mut s = String::new();
+        s.push_str("{\"reason\":");
+        write_json_string(&mut s, &self.reason_debug_string());
+        s.push_str(",\"file\":");
+        write_json_string(&mut s, self.file());
+        let _ = write!(s, ",\"line\":{}", self.line());
+        if !self.fields().is_empty() {
+            s.push_str(",\"fields\":{");
+            for (i, (key, value)) in self.fields().iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                write_json_string(&mut s, key);
+                s.push(':');
+                write_json_string(&mut s, value);
+            }
+            s.push('}');
+        }
+        s.push_str(",\"causes\":[");
+        for (i, cause) in self.chain().skip(1).enumerate() {
+            if i > 0 {
+                s.push(',');
+            }
+            write_json_string(&mut s, &cause.to_string());
+        }
+        s.push_str("]}");
+        s
+    }
+}
+
+fn write_json_string(s: &mut String, value: &str) {
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(s, "\\u{:04x}", c as u32);
+            }
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+#[cfg(test)]
+mod tests_of_json {
+    use super::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Reasons {
+        FailToDoSomething,
+        InvalidValue { value: String },
+    }
+
+    #[test]
+    fn err_without_source() {
+        let line = line!() + 1;
+        let err = Err::new(Reasons::FailToDoSomething);
+        assert_eq!(
+            err.to_json_line(),
+            format!(
+                "{{\"reason\":\"FailToDoSomething\",\"file\":\"src/json.rs\",\"line\":{line},\"causes\":[]}}"
+            )
+        );
+    }
+
+    #[test]
+    fn err_with_source_chain() {
+        let err = Err::with_source(Reasons::FailToDoSomething, std::io::Error::other("oh no!"));
+        let line = line!() + 1;
+        let err = Err::with_source("outer", err);
+        assert_eq!(
+            err.to_json_line(),
+            format!(
+                "{{\"reason\":\"\\\"outer\\\"\",\"file\":\"src/json.rs\",\"line\":{line},\"causes\":[\"FailToDoSomething\",\"oh no!\"]}}"
+            )
+        );
+    }
+
+    #[test]
+    fn escape_quotes_and_newlines() {
+        let err = Err::new(Reasons::InvalidValue {
+            value: "a\"b\nc\\d\te\u{1}".to_string(),
+        });
+        let json = err.to_json_line();
+        assert!(json.starts_with(
+            "{\"reason\":\"InvalidValue { value: \\\"a\\\\\\\"b\\\\nc\\\\\\\\d\\\\te\\\\u{1}\\\" }\","
+        ));
+        assert!(!json.contains('\n'));
+    }
+
+    #[test]
+    fn escape_control_characters() {
+        let mut s = String::new();
+        write_json_string(&mut s, "a\"b\nc\\d\re\tf\u{1}g");
+        assert_eq!(s, "\"a\\\"b\\nc\\\\d\\re\\tf\\u0001g\"");
+    }
+
+    #[test]
+    fn err_with_fields() {
+        #[derive(Debug)]
+        struct HttpError {
+            status: u16,
+        }
+
+        impl crate::ReasonFields for HttpError {
+            fn fields(&self) -> Vec<(String, String)> {
+                vec![("status".to_string(), self.status.to_string())]
+            }
+        }
+
+        let line = line!() + 1;
+        let err = Err::new_with_fields(HttpError { status: 404 }).with_field("path", "/a\"b");
+        assert_eq!(
+            err.to_json_line(),
+            format!(
+                "{{\"reason\":\"HttpError {{ status: 404 }}\",\"file\":\"src/json.rs\",\"line\":{line},\"fields\":{{\"status\":\"404\",\"path\":\"/a\\\"b\"}},\"causes\":[]}}"
+            )
+        );
+    }
+}
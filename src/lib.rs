@@ -45,6 +45,72 @@
 //! errs = { version = "0.8.2", features = ["notify-tokio"] }
 //! ```
 //!
+//! If you want to emit a `tracing` event each time an `Err` is created, specify `tracing`.
+//! This feature does not require registering any error handler.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", features = ["tracing"] }
+//! ```
+//!
+//! If you want to output a log record with the `log` crate facade each time an `Err` is
+//! created, specify `log`. This feature does not require registering any error handler.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", features = ["log"] }
+//! ```
+//!
+//! If you want to share the allocations of identical reason strings with `Err::new_interned`,
+//! specify `intern`.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", features = ["intern"] }
+//! ```
+//!
+//! If you want to count the created errors, the dispatched notifications, and the caught
+//! handler panics, specify `metrics`. The counters can be read with `metrics_snapshot`.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", features = ["metrics"] }
+//! ```
+//!
+//! If you want to output errors as single-line JSON objects with `Err::to_json_line`, specify
+//! `json`. This feature does not depend on any serialization crate.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", features = ["json"] }
+//! ```
+//!
+//! If you want to enumerate the reason types registered with `register_reason_type!` for
+//! documentation, specify `registry`.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", features = ["registry"] }
+//! ```
+//!
+//! This crate can be used in `no_std` environments with `alloc`, by disabling the default `std`
+//! feature.
+//! In `no_std` mode, error sources, error chains, the `Context` trait, and the implementation of
+//! `std::error::Error` for `Err` are unavailable, and error notification cannot be used.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", default-features = false }
+//! ```
+//!
+//! If you want `Err` to implement `core::error::Error` in `no_std` mode, specify `core-error`.
+//! This feature requires Rust 1.81 or later.
+//!
+//! ```toml
+//! [dependencies]
+//! errs = { version = "0.8.2", default-features = false, features = ["core-error"] }
+//! ```
+//!
 //! ## Usage
 //!
 //! ### Err instantiation and identification of a reason
@@ -54,6 +120,7 @@
 //!
 //! Then, the reason can be identified with `reason<R>(&self)` method and a `match` statement,
 //! or `match_reason<R>(&self, func fn(&R))` method.
+//! For a closure which captures variables, use `match_reason_with<R, F>(&self, func: F)` method instead.
 //!
 //! The following code is an example which uses `new<R>(reason: R)` function for instantiation,
 //! and `reason<R>(&self)` method and a `match` statement for identifying a reason:
@@ -206,8 +273,44 @@
 //! ```
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+mod adapter;
+mod builder;
+#[cfg(feature = "std")]
+mod chain;
+#[cfg(feature = "std")]
+mod context;
+#[cfg(feature = "std")]
+mod correlation;
+mod debug;
+#[cfg(feature = "std")]
+mod display;
 mod err;
+#[cfg(feature = "std")]
+mod errors;
+#[cfg(feature = "std")]
+mod first_err;
+#[cfg(feature = "intern")]
+mod intern;
+mod into_err;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "log")]
+mod logging;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod option_ext;
+mod reason_mismatch;
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "std")]
+mod result_ext;
 
 #[cfg(any(feature = "notify", feature = "notify-tokio"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "notify", feature = "notify-tokio"))))]
@@ -216,22 +319,76 @@ mod notify;
 #[cfg(feature = "notify")]
 #[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
 pub use notify::{
-    add_async_err_handler, add_sync_err_handler, AsyncHandlerRegistration, SyncHandlerRegistration,
+    add_arc_sync_err_handler, add_async_err_handler, add_sequential_async_err_handler,
+    add_sync_batch_err_handler, add_sync_err_handler, add_sync_err_handler_in_group,
+    add_sync_err_handler_reporting_handled, add_sync_err_handler_timeout,
+    add_sync_err_handler_with_priority, add_typed_sync_err_handler, add_unhandled_err_handler,
+    drain_ring_buffer, enable_ring_buffer, notify_batch, set_group_enabled,
+    AsyncHandlerRegistration, ErrRecord, SyncHandlerRegistration,
 };
 
 #[cfg(feature = "notify-tokio")]
 #[cfg_attr(docsrs, doc(cfg(feature = "notify-tokio")))]
-pub use notify::{add_tokio_async_err_handler, TokioAsyncHandlerRegistration};
+pub use notify::{
+    add_tokio_async_err_handler, flush_tokio_handlers, set_tokio_handler_error_logging,
+    TokioAsyncHandlerRegistration, TokioAsyncRawFn,
+};
 
 #[cfg(any(feature = "notify", feature = "notify-tokio"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "notify", feature = "notify-tokio"))))]
-pub use notify::{fix_err_handlers, ErrHandlingError, ErrHandlingErrorKind};
+pub use notify::{
+    assert_no_errors, capture_errors_of, dedup_handler, fix_err_handlers, notify_is_degraded,
+    reset_thread_error_count, set_notify_sampling, set_notify_sink, thread_error_count,
+    CapturedErr, ErrHandlingError, ErrHandlingErrorKind, ErrNotifyBuilder,
+};
 
-use std::{any, cell, error, fmt, marker, ptr, result};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use adapter::register_reason_adapter;
 
-#[cfg(any(feature = "notify", feature = "notify-tokio"))]
-#[cfg_attr(docsrs, doc(cfg(any(feature = "notify", feature = "notify-tokio"))))]
-use std::sync::atomic;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use correlation::set_correlation_id;
+
+pub use debug::set_debug_show_reason_type;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use display::{set_display_formatter, set_display_includes_source};
+
+#[cfg(feature = "log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+pub use logging::{set_log_level, set_log_target};
+
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub use metrics::metrics_snapshot;
+
+#[cfg(feature = "registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+pub use registry::{known_reason_types, ReasonTypeRegistration};
+
+#[cfg(feature = "tracing")]
+mod trace;
+
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub use trace::set_tracing_level;
+
+use core::{any, cell, fmt, hash, marker, ptr, result};
+
+#[cfg(feature = "std")]
+use std::{error, sync};
+
+#[cfg(all(not(feature = "std"), feature = "core-error"))]
+use core::error;
+
+// Since `std::error::Error` is not available in `no_std` mode, this trait is used instead as the
+// bound of the source type parameter of `ReasonAndSource`, which is always `DummyError` there.
+#[cfg(all(not(feature = "std"), not(feature = "core-error")))]
+mod error {
+    pub trait Error: core::fmt::Debug + core::fmt::Display {}
+}
 
 /// Struct that represents an error with a reason.
 ///
@@ -247,14 +404,107 @@ use std::sync::atomic;
 /// This struct also contains an optional cause error, which is the error caused the
 /// current error. This is useful for chaining errors.
 ///
-/// This struct is implements the `std::errors::Error` trait, so it can be used as an
-/// common error type in Rust programs.
+/// This struct is implements the `std::errors::Error` trait when the `std` feature is enabled,
+/// so it can be used as an common error type in Rust programs.
+/// Since this struct is also `Send + Sync`, it can be converted into
+/// `Box<dyn std::error::Error + Send + Sync>` with the `?` operator or `into()`.
+///
+/// This struct also implements `Eq` and `Hash`, so that errors can be grouped in a `HashSet` or
+/// a `HashMap`. Two `Err` instances are equal if their reasons are of the same type and they
+/// are created at the same location. The values of their reasons are compared as well only if
+/// the reason type implements `Eq` and `Hash` and it is specified with
+/// [`Err::with_comparable_reason`].
+///
+/// ```rust
+/// use errs::Err;
+/// use std::error::Error;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// fn do_something() -> errs::Result<()> {
+///     Err(Err::new(Reasons::FailToDoSomething))
+/// }
+///
+/// # #[cfg(feature = "std")]
+/// fn run() -> Result<(), Box<dyn Error + Send + Sync>> {
+///     do_something()?;
+///     Ok(())
+/// }
+///
+/// # #[cfg(feature = "std")]
+/// # {
+/// let e = run().unwrap_err();
+/// assert!(e.downcast_ref::<Err>().is_some());
+/// # }
+/// ```
 pub struct Err {
     file: &'static str,
     line: u32,
+    column: u32,
+    is_silent: bool,
+    severity: Severity,
+    meta: Option<alloc::boxed::Box<ErrMeta>>,
     reason_and_source: SendSyncNonNull<ReasonAndSource>,
 }
 
+// The metadata of an `Err` which many errors do not have. This is boxed and held as `None` when
+// it is empty, so that `Err` is kept small enough to be returned in `Result`.
+#[derive(Default, Clone)]
+struct ErrMeta {
+    fields: Vec<(String, String)>,
+    #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[cfg(feature = "std")]
+    correlation_id: Option<sync::Arc<str>>,
+}
+
+/// A builder to create an [`Err`] with a reason, a source, a severity, and fields in one
+/// expression.
+///
+/// Unlike attaching them to a created `Err`, all of them are included in the error notified to
+/// error handlers. The location of the created `Err` is where [`ErrBuilder::build`] is called.
+///
+/// ```rust
+/// use errs::{ErrBuilder, Severity};
+/// use std::io;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToLoadConfig,
+/// }
+///
+/// # #[cfg(feature = "std")]
+/// # {
+/// let err = ErrBuilder::new(Reasons::FailToLoadConfig)
+///     .source(io::Error::other("oh no!"))
+///     .severity(Severity::Fatal)
+///     .field("path", "/etc/app.conf")
+///     .build();
+/// assert_eq!(err.severity(), Severity::Fatal);
+/// assert_eq!(err.to_string(), "FailToLoadConfig [path=/etc/app.conf]");
+/// # }
+/// ```
+pub struct ErrBuilder<R>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    reason: R,
+    #[cfg(feature = "std")]
+    leak_fn: Option<LeakFn<R>>,
+    severity: Severity,
+    fields: Vec<(String, String)>,
+}
+
+// The function to create a `ReasonAndSource` with the source given to `ErrBuilder::source`.
+#[cfg(feature = "std")]
+type LeakFn<R> = Box<dyn FnOnce(R) -> ptr::NonNull<ReasonAndSource> + Send + Sync>;
+
+// The reason of an `Err` created with `Err::here`, which represents only the location.
+struct Located(&'static core::panic::Location<'static>);
+
 #[derive(Debug)]
 struct DummyReason {}
 
@@ -267,19 +517,92 @@ impl fmt::Display for DummyError {
 }
 impl error::Error for DummyError {}
 
+// The source type of `ReasonAndSource` for an `Err` created with `Err::with_sources`.
+#[cfg(feature = "std")]
+struct Sources(Vec<Box<dyn error::Error + Send + Sync>>);
+
+// The source type of `ReasonAndSource` for an `Err` created with `Err::with_source_fn`.
+// The source error is created by `source_fn` when it is accessed for the first time.
+#[cfg(feature = "std")]
+struct LazySource {
+    source: sync::OnceLock<Box<dyn error::Error + Send + Sync>>,
+    source_fn: sync::Mutex<Option<LazySourceFn>>,
+}
+
+// The source type of `ReasonAndSource` for an `Err` whose source is given as a boxed error, e.g.
+// by `Err::map_source`.
+#[cfg(feature = "std")]
+struct BoxedSource(Box<dyn error::Error + Send + Sync>);
+
+// The reason of the copy of an `Err` passed to the error handlers which may run after the `Err`
+// is returned, e.g. the async handlers. It holds a reference to the `ReasonAndSource` of the
+// original `Err`, and the reason of the copy is the reason in it. While it is held, the reason
+// of the original `Err` cannot be borrowed mutably nor moved out.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+struct SharedReason(SendSyncNonNull<ReasonAndSource>);
+
+// The reason of an `Err` in the source chain of the copy of an `Err` passed to the error
+// handlers. It holds the rendered strings of the original reason, because the source chain can
+// be taken out of the original `Err`.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+struct RenderedReason {
+    type_name: &'static str,
+    debug: String,
+    display: String,
+}
+
+// The source error of the copy of an `Err` passed to the error handlers, which holds the rendered
+// strings of the original source error and its source chain.
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+struct RenderedError {
+    debug: String,
+    display: String,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+}
+
+#[cfg(feature = "std")]
+type LazySourceFn = Box<dyn FnOnce() -> Box<dyn error::Error + Send + Sync> + Send>;
+
+// The function to create a `ReasonAndSource` with the reason moved from the given one and the
+// given source, used by `Err::map_source`.
+#[cfg(feature = "std")]
+type ReplaceSourceFn = fn(
+    ptr::NonNull<ReasonAndSource>,
+    Option<Box<dyn error::Error + Send + Sync>>,
+) -> ptr::NonNull<ReasonAndSource>;
+
+// The function to compare the reason values of two `ReasonAndSource`s, set by
+// `Err::with_comparable_reason`.
+type EqReasonFn = fn(ptr::NonNull<ReasonAndSource>, ptr::NonNull<ReasonAndSource>) -> bool;
+
 #[repr(C)]
 struct ReasonAndSource<R = DummyReason, E = DummyError>
 where
     R: fmt::Debug + Send + Sync + 'static,
     E: error::Error + Send + Sync + 'static,
 {
-    is_fn: fn(any::TypeId) -> bool,
+    is_fn: fn(ptr::NonNull<ReasonAndSource>, any::TypeId) -> bool,
     drop_fn: fn(ptr::NonNull<ReasonAndSource>),
     debug_fn: fn(ptr::NonNull<ReasonAndSource>, f: &mut fmt::Formatter<'_>) -> fmt::Result,
+    debug_reason_fn: fn(ptr::NonNull<ReasonAndSource>, f: &mut fmt::Formatter<'_>) -> fmt::Result,
     display_fn: fn(ptr::NonNull<ReasonAndSource>, f: &mut fmt::Formatter<'_>) -> fmt::Result,
+    #[cfg(feature = "std")]
     source_fn: fn(ptr::NonNull<ReasonAndSource>) -> Option<&'static (dyn error::Error + 'static)>,
+    #[cfg(feature = "std")]
+    sources_fn: fn(ptr::NonNull<ReasonAndSource>) -> &'static [Box<dyn error::Error + Send + Sync>],
+    #[cfg(feature = "std")]
+    take_source_fn:
+        fn(ptr::NonNull<ReasonAndSource>) -> Option<Box<dyn error::Error + Send + Sync>>,
+    #[cfg(feature = "std")]
+    replace_source_fn: ReplaceSourceFn,
+    as_any_fn: fn(ptr::NonNull<ReasonAndSource>) -> &'static dyn any::Any,
+    as_any_mut_fn: fn(ptr::NonNull<ReasonAndSource>) -> Option<&'static mut dyn any::Any>,
+    move_reason_fn: fn(ptr::NonNull<ReasonAndSource>, ptr::NonNull<()>) -> bool,
+    type_name_fn: fn(ptr::NonNull<ReasonAndSource>) -> &'static str,
+    eq_fn: Option<EqReasonFn>,
+    hash_fn: Option<fn(ptr::NonNull<ReasonAndSource>, &mut dyn hash::Hasher)>,
     #[cfg(any(feature = "notify", feature = "notify-tokio"))]
-    is_referenced_by_another: atomic::AtomicBool,
+    ref_count: sync::atomic::AtomicUsize,
     reason_and_source: (R, Option<E>),
 }
 
@@ -346,3 +669,513 @@ struct SendSyncNonNull<T: Send + Sync> {
 /// }
 /// ```
 pub type Result<T> = result::Result<T, Err>;
+
+/// The severity of an error, which is used to route errors, e.g. by logging sinks.
+///
+/// The severity of an `Err` instance is [`Severity::Error`] unless it is specified with
+/// [`Err::new_with_severity`], [`Err::builder`], or [`Err::with_severity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Severity {
+    /// An error which does not prevent the program from continuing normally.
+    Warning,
+
+    /// An ordinary error.
+    #[default]
+    Error,
+
+    /// An error which prevents the program from continuing.
+    Fatal,
+}
+
+/// The error returned by [`Err::reason_result`] when the reason of an `Err` is not of the
+/// expected type.
+///
+/// This struct carries the type names of the expected and actual reasons, and implements
+/// `std::error::Error` when the `std` feature is enabled, or `core::error::Error` when the
+/// `core-error` feature is enabled, so that it can be propagated with `?`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReasonMismatchError {
+    expected: &'static str,
+    actual: &'static str,
+}
+
+/// The reason of an `Err` created from a panic payload with [`Err::from_panic_payload`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReason {
+    /// The panic message, or `"unknown panic"` if the payload is not a string.
+    pub message: String,
+}
+
+/// The reason of an `Err` whose original reason is removed with [`Err::redacted`].
+///
+/// This is displayed as `<redacted>` in both the `Debug` and `Display` formats of `Err`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Redacted;
+
+/// An iterator over an error and its chain of source errors.
+///
+/// This iterator is created by the [`Err::chain`] method. The first element is the `Err`
+/// itself, and each subsequent element is the source of the previous one.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct Chain<'a> {
+    next: Option<&'a (dyn error::Error + 'static)>,
+}
+
+/// The information of the reason of an `Err` in a chain of errors.
+///
+/// This struct is yielded by the iterator created by the [`Err::reason_chain`] method.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct ReasonInfo<'a> {
+    /// The `Err` which has the reason.
+    pub err: &'a Err,
+
+    /// The type name of the reason.
+    pub type_name: &'static str,
+
+    /// The reason formatted with its `Debug` implementation.
+    pub debug: String,
+}
+
+/// A wrapper for displaying an error and its chain of source errors on a single line.
+///
+/// This struct is created by the [`Err::display_chain`] or [`Err::display_chain_with`] method.
+/// Its `Display` implementation joins the `Display` output of each error in the chain with
+/// a separator.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct DisplayChain<'a> {
+    err: &'a Err,
+    sep: &'a str,
+}
+
+/// A container which keeps only the first [`Err`] set to it, for fan-out concurrency.
+///
+/// This struct is `Send + Sync`, so it can be shared among threads or tasks which race to set
+/// an error. Since `Err` is not `Clone`, an error set after the first one is dropped.
+///
+/// ```rust
+/// use errs::{Err, FirstErr};
+/// use std::thread;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoTask { id: u32 },
+/// }
+///
+/// let first_err = FirstErr::new();
+/// thread::scope(|s| {
+///     for id in 0..4 {
+///         let first_err = &first_err;
+///         s.spawn(move || first_err.set_if_empty(Err::new(Reasons::FailToDoTask { id })));
+///     }
+/// });
+/// assert!(first_err.take().unwrap().reason::<Reasons>().is_ok());
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Default)]
+pub struct FirstErr {
+    cell: sync::OnceLock<Err>,
+}
+
+/// A collection of [`Err`]s, for collecting all failures and then failing once.
+///
+/// This struct is useful for validating many fields, where each field can cause an error but
+/// `Result` can hold only one. The collected errors are combined into a single `Err` by
+/// [`Errors::into_result`].
+///
+/// ```rust
+/// use errs::{AggregateReason, Err, Errors};
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     EmptyName,
+///     InvalidAge { age: i32 },
+/// }
+///
+/// let mut errors = Errors::new();
+/// errors.push(Err::new(Reasons::EmptyName));
+/// errors.push(Err::new(Reasons::InvalidAge { age: -1 }));
+///
+/// let err = errors.into_result(()).unwrap_err();
+/// match err.reason::<AggregateReason>() {
+///     Ok(AggregateReason { count }) => assert_eq!(*count, 2),
+///     Err(_) => panic!(),
+/// }
+/// assert_eq!(err.sources().len(), 2);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Default)]
+pub struct Errors {
+    errs: Vec<Err>,
+}
+
+/// The reason of an `Err` into which the errors collected in [`Errors`] are combined.
+///
+/// The collected errors are the sources of the `Err`, and can be retrieved with
+/// [`Err::sources`].
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggregateReason {
+    /// The number of the collected errors.
+    pub count: usize,
+}
+
+/// A trait for wrapping the error of a `Result` into an [`Err`] with a context as its reason.
+///
+/// This trait is implemented for `Result<T, E>` where `E` is a standard error. On error, the
+/// context becomes the reason of the created `Err` and the original error becomes its source.
+///
+/// ```rust
+/// use errs::Context;
+/// use std::fs;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToLoadConfig { path: String },
+/// }
+///
+/// fn load_config(path: &str) -> errs::Result<String> {
+///     fs::read_to_string(path).with_context(|| Reasons::FailToLoadConfig {
+///         path: path.to_string(),
+///     })
+/// }
+///
+/// let err = load_config("/not/exist/file").unwrap_err();
+/// assert!(err.reason::<Reasons>().is_ok());
+/// assert!(err.source().unwrap().downcast_ref::<std::io::Error>().is_some());
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub trait Context<T> {
+    /// Wraps the error with the given context as the reason of the created `Err`.
+    ///
+    /// # Parameters
+    /// - `ctx`: The context which becomes the reason of the created `Err`.
+    ///
+    /// # Returns
+    /// - `Ok(T)`: The value if this is `Ok`.
+    /// - `Err(Err)`: An `Err` with the context as its reason and the original error as its
+    ///   source.
+    fn context<C>(self, ctx: C) -> Result<T>
+    where
+        C: fmt::Debug + Send + Sync + 'static;
+
+    /// Wraps the error with a context which is lazily created by the given closure.
+    ///
+    /// The closure is called only if this is an error.
+    ///
+    /// # Parameters
+    /// - `f`: The closure which creates the context.
+    ///
+    /// # Returns
+    /// - `Ok(T)`: The value if this is `Ok`.
+    /// - `Err(Err)`: An `Err` with the context as its reason and the original error as its
+    ///   source.
+    fn with_context<C, F>(self, f: F) -> Result<T>
+    where
+        C: fmt::Debug + Send + Sync + 'static,
+        F: FnOnce() -> C;
+
+    /// Wraps the error into an `Err` which records the location where this method is called.
+    ///
+    /// This is useful for a standard error which has no location, to know the `?` site where it
+    /// was propagated. The reason of the created `Err` is the location like [`Err::here`], and
+    /// the original error becomes its source.
+    ///
+    /// ```rust
+    /// use errs::Context;
+    /// use std::fs;
+    ///
+    /// fn load_config(path: &str) -> errs::Result<String> {
+    ///     let line = line!() + 1;
+    ///     let s = fs::read_to_string(path).locate().inspect_err(|err| {
+    ///         assert_eq!(err.line(), line);
+    ///     })?;
+    ///     Ok(s)
+    /// }
+    ///
+    /// let err = load_config("/not/exist/file").unwrap_err();
+    /// assert_eq!(err.to_string(), format!("{}:{}:{}", err.file(), err.line(), err.column()));
+    /// ```
+    ///
+    /// # Returns
+    /// - `Ok(T)`: The value if this is `Ok`.
+    /// - `Err(Err)`: An `Err` with the location as its reason and the original error as its
+    ///   source.
+    fn locate(self) -> Result<T>;
+}
+
+/// A trait which provides combinators for [`Result`] of this crate.
+///
+/// ```rust
+/// use errs::{Err, ResultExt};
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// fn do_something() -> errs::Result<()> {
+///     Err(Err::new(Reasons::FailToDoSomething))
+/// }
+///
+/// // Prints the error to stderr and returns it unchanged.
+/// let result = do_something().trace_with("do_something");
+/// assert!(result.is_err());
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub trait ResultExt {
+    /// The type of the value of this result.
+    type Value;
+
+    /// Outputs the error and returns this result unchanged.
+    ///
+    /// If this is `Err`, the error is printed to stderr in the `Debug` format, which contains
+    /// the file and line where the error occurred. If the `tracing` feature is enabled, a
+    /// `tracing` event is emitted instead at the level set with `set_tracing_level`.
+    /// If this is `Ok`, nothing is output.
+    ///
+    /// This method can also be used in async fns as it is. To avoid blocking them by the
+    /// output, use `ResultExt::spawn_trace` with the `notify-tokio` feature.
+    fn trace(self) -> Self;
+
+    /// Outputs the error with the given label and returns this result unchanged.
+    ///
+    /// This method is the same as [`ResultExt::trace`], except that the output is prefixed
+    /// with the label.
+    ///
+    /// # Parameters
+    /// - `label`: The label which prefixes the output.
+    fn trace_with(self, label: &str) -> Self;
+
+    /// Outputs the error in a spawned Tokio task and returns this result unchanged.
+    ///
+    /// This method is the same as [`ResultExt::trace`], except that the output is done in a
+    /// task spawned on the current Tokio runtime, so that the caller in an async fn is not
+    /// blocked by it. The contents of the error are prepared in the `Debug` format before
+    /// spawning, because the error itself is returned to the caller. If there is no current
+    /// Tokio runtime, the error is output on the current thread.
+    ///
+    /// This method does not notify the error to the error handlers, which are notified when the
+    /// error is created.
+    ///
+    /// ```rust
+    /// use errs::{Err, ResultExt};
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// async fn do_something() -> errs::Result<()> {
+    ///     Err(Err::new(Reasons::FailToDoSomething))
+    /// }
+    ///
+    /// async fn run() -> errs::Result<()> {
+    ///     do_something().await.spawn_trace()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// assert!(rt.block_on(run()).is_err());
+    /// ```
+    #[cfg(feature = "notify-tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notify-tokio")))]
+    fn spawn_trace(self) -> Self;
+
+    /// Converts this result into `std::io::Result`.
+    ///
+    /// This is useful at the boundaries with `std::io`-centric APIs. The error is wrapped into
+    /// an `io::Error` of `io::ErrorKind::Other` as its inner error, so that no information is
+    /// lost and the original `Err` can be obtained with `io::Error::get_ref` or
+    /// `io::Error::into_inner`.
+    ///
+    /// ```rust
+    /// use errs::{Err, ResultExt};
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// enum Reasons {
+    ///     FailToDoSomething,
+    /// }
+    ///
+    /// fn do_something() -> errs::Result<()> {
+    ///     Err(Err::new(Reasons::FailToDoSomething))
+    /// }
+    ///
+    /// let io_err = do_something().into_io().unwrap_err();
+    /// assert_eq!(io_err.kind(), io::ErrorKind::Other);
+    /// let err = io_err.get_ref().unwrap().downcast_ref::<Err>().unwrap();
+    /// assert!(err.reason::<Reasons>().is_ok());
+    /// ```
+    ///
+    /// # Returns
+    /// - `Ok(T)`: The value if this is `Ok`.
+    /// - `Err(io::Error)`: An `io::Error` which wraps the error if this is `Err`.
+    fn into_io(self) -> std::io::Result<Self::Value>;
+}
+
+/// A trait for converting an [`Option`] into a [`Result`] of this crate with a reason.
+///
+/// This trait mirrors `Option::ok_or` and `Option::ok_or_else`, but the created `Err` is
+/// located at the call site of the method.
+///
+/// ```rust
+/// use errs::OptionExt;
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     UserNotFound { id: u32 },
+/// }
+///
+/// fn find_user(users: &HashMap<u32, String>, id: u32) -> errs::Result<&String> {
+///     let user = users.get(&id).ok_or_reason(Reasons::UserNotFound { id })?;
+///     Ok(user)
+/// }
+///
+/// let err = find_user(&HashMap::new(), 1).unwrap_err();
+/// assert!(err.reason::<Reasons>().is_ok());
+/// ```
+pub trait OptionExt<T> {
+    /// Converts this option into a result with the given reason for `None`.
+    ///
+    /// # Parameters
+    /// - `reason`: The reason of the created `Err` if this is `None`.
+    ///
+    /// # Returns
+    /// - `Ok(T)`: The value if this is `Some`.
+    /// - `Err(Err)`: An `Err` with the given reason if this is `None`.
+    #[track_caller]
+    fn ok_or_reason<R>(self, reason: R) -> Result<T>
+    where
+        R: fmt::Debug + Send + Sync + 'static;
+
+    /// Converts this option into a result with the reason created by the given closure for
+    /// `None`.
+    ///
+    /// The closure is called only if this is `None`.
+    ///
+    /// # Parameters
+    /// - `f`: The closure which creates the reason of the `Err` if this is `None`.
+    ///
+    /// # Returns
+    /// - `Ok(T)`: The value if this is `Some`.
+    /// - `Err(Err)`: An `Err` with the reason created by the closure if this is `None`.
+    #[track_caller]
+    fn ok_or_else_reason<R, F>(self, f: F) -> Result<T>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+        F: FnOnce() -> R;
+}
+
+/// A trait for a reason type which contributes its own structured fields to an [`Err`].
+///
+/// When an `Err` is created with [`Err::new_with_fields`], the fields returned by
+/// [`ReasonFields::fields`] are added to the fields of the `Err`, so that they are included in
+/// [`Err::fields`] and in the structured outputs such as `Err::to_json_line`.
+///
+/// ```rust
+/// use errs::{Err, ReasonFields};
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToReadFile { path: String, size: u64 },
+/// }
+///
+/// impl ReasonFields for Reasons {
+///     fn fields(&self) -> Vec<(String, String)> {
+///         match self {
+///             Reasons::FailToReadFile { path, size } => vec![
+///                 ("path".to_string(), path.clone()),
+///                 ("size".to_string(), size.to_string()),
+///             ],
+///         }
+///     }
+/// }
+///
+/// let err = Err::new_with_fields(Reasons::FailToReadFile {
+///     path: "/aaa/bbb".to_string(),
+///     size: 12,
+/// });
+/// assert_eq!(err.fields()[0], ("path".to_string(), "/aaa/bbb".to_string()));
+/// assert_eq!(err.fields()[1], ("size".to_string(), "12".to_string()));
+/// ```
+pub trait ReasonFields {
+    /// Returns the structured fields of this reason as key-value pairs.
+    fn fields(&self) -> Vec<(String, String)>;
+}
+
+/// A trait for converting a reason into an [`Err`] in a fluent style.
+///
+/// This trait is implemented for all types which can be a reason of `Err`, so that a reason
+/// can be converted at a return site with `reason.into_err()` instead of `Err::new(reason)`.
+///
+/// ```rust
+/// use errs::IntoErr;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     InvalidValue { value: i32 },
+/// }
+///
+/// fn check(value: i32) -> errs::Result<()> {
+///     if value < 0 {
+///         return Err(Reasons::InvalidValue { value }.into_err());
+///     }
+///     Ok(())
+/// }
+///
+/// let err = check(-1).unwrap_err();
+/// assert!(err.reason::<Reasons>().is_ok());
+/// ```
+pub trait IntoErr {
+    /// Creates an `Err` with this value as its reason.
+    ///
+    /// The location of the created `Err` is where this method is called.
+    ///
+    /// # Returns
+    /// An `Err` instance with this value as its reason.
+    #[track_caller]
+    fn into_err(self) -> Err;
+}
+
+/// The information of a reason type registered with
+/// [`register_reason_type!`](crate::register_reason_type!).
+///
+/// This struct is obtained with [`known_reason_types`](crate::known_reason_types()).
+#[cfg(feature = "registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReasonTypeInfo {
+    /// The name of the reason type, prefixed with the module path where it is registered.
+    pub name: &'static str,
+
+    /// The description of the reason type.
+    pub description: &'static str,
+}
+
+/// A snapshot of the counters of this crate, which is obtained with
+/// [`metrics_snapshot`](crate::metrics_snapshot()).
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Metrics {
+    /// The number of `Err` instances created, including silent ones.
+    pub errs_created: u64,
+
+    /// The number of errors dispatched to the error handlers.
+    /// This is always zero unless the `notify` or `notify-tokio` feature is enabled.
+    pub notifications_dispatched: u64,
+
+    /// The number of panics of sync error handlers which were caught.
+    /// This is always zero unless the `notify` feature is enabled.
+    pub handler_panics: u64,
+}
@@ -0,0 +1,71 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Err;
+
+use log::Level;
+
+use std::sync::{atomic, RwLock};
+
+static LOG_LEVEL: atomic::AtomicUsize = atomic::AtomicUsize::new(Level::Error as usize);
+static LOG_TARGET: RwLock<&'static str> = RwLock::new("errs");
+
+/// Sets the target of the log record output when an `Err` instance is created.
+///
+/// The default target is `"errs"`.
+///
+/// # Parameters
+/// - `target`: The target of the log record.
+///
+/// ```rust
+/// errs::set_log_target("myapp::errors");
+/// ```
+pub fn set_log_target(target: &'static str) {
+    match LOG_TARGET.write() {
+        Ok(mut t) => *t = target,
+        Err(e) => *e.into_inner() = target,
+    }
+}
+
+/// Sets the level of the log record output when an `Err` instance is created.
+///
+/// The default level is `Level::Error`.
+///
+/// # Parameters
+/// - `level`: The level of the log record.
+///
+/// ```rust
+/// errs::set_log_level(log::Level::Warn);
+/// ```
+pub fn set_log_level(level: Level) {
+    LOG_LEVEL.store(level as usize, atomic::Ordering::Relaxed);
+}
+
+fn log_level() -> Level {
+    match LOG_LEVEL.load(atomic::Ordering::Relaxed) {
+        n if n == Level::Trace as usize => Level::Trace,
+        n if n == Level::Debug as usize => Level::Debug,
+        n if n == Level::Info as usize => Level::Info,
+        n if n == Level::Warn as usize => Level::Warn,
+        _ => Level::Error,
+    }
+}
+
+fn log_target() -> &'static str {
+    match LOG_TARGET.read() {
+        Ok(t) => *t,
+        Err(e) => *e.into_inner(),
+    }
+}
+
+pub(crate) fn log_err(err: &Err) {
+    log::log!(
+        target: log_target(),
+        log_level(),
+        "{}:{} {}",
+        err.file(),
+        err.line(),
+        err
+    );
+}
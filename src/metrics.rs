@@ -0,0 +1,91 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Metrics;
+
+use core::sync::atomic;
+
+static ERRS_CREATED: atomic::AtomicU64 = atomic::AtomicU64::new(0);
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+static NOTIFICATIONS_DISPATCHED: atomic::AtomicU64 = atomic::AtomicU64::new(0);
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+static HANDLER_PANICS: atomic::AtomicU64 = atomic::AtomicU64::new(0);
+
+/// Gets a snapshot of the counters of this crate.
+///
+/// The counters are incremented without locks, so the values in a snapshot may not be
+/// consistent with each other while errors are being created in other threads.
+///
+/// ```rust
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// let before = errs::metrics_snapshot();
+/// let _err = errs::Err::new(Reasons::FailToDoSomething);
+/// let after = errs::metrics_snapshot();
+/// assert!(after.errs_created > before.errs_created);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub fn metrics_snapshot() -> Metrics {
+    Metrics {
+        errs_created: ERRS_CREATED.load(atomic::Ordering::Relaxed),
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        notifications_dispatched: NOTIFICATIONS_DISPATCHED.load(atomic::Ordering::Relaxed),
+        #[cfg(not(any(feature = "notify", feature = "notify-tokio")))]
+        notifications_dispatched: 0,
+        #[cfg(any(feature = "notify", feature = "notify-tokio"))]
+        handler_panics: HANDLER_PANICS.load(atomic::Ordering::Relaxed),
+        #[cfg(not(any(feature = "notify", feature = "notify-tokio")))]
+        handler_panics: 0,
+    }
+}
+
+pub(crate) fn count_err_created() {
+    ERRS_CREATED.fetch_add(1, atomic::Ordering::Relaxed);
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+pub(crate) fn count_notification_dispatched() {
+    NOTIFICATIONS_DISPATCHED.fetch_add(1, atomic::Ordering::Relaxed);
+}
+
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+pub(crate) fn count_handler_panic() {
+    HANDLER_PANICS.fetch_add(1, atomic::Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests_of_metrics {
+    use super::*;
+    use crate::Err;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn count_created_errs() {
+        let before = metrics_snapshot();
+
+        let _err0 = Err::new(Reasons::FailToDoSomething);
+        let _err1 = Err::new_silent(Reasons::FailToDoSomething);
+        let _err2 = Err::new(Reasons::FailToDoSomething);
+
+        let after = metrics_snapshot();
+        // Other tests may create errors concurrently.
+        assert!(after.errs_created - before.errs_created >= 3);
+    }
+
+    #[test]
+    fn snapshot_is_copy() {
+        let snapshot = metrics_snapshot();
+        let copied = snapshot;
+        assert!(copied.errs_created >= snapshot.errs_created);
+        assert!(copied.notifications_dispatched >= snapshot.notifications_dispatched);
+        assert!(copied.handler_panics >= snapshot.handler_panics);
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2025-2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::{has_handlers, ErrHandlingError, ErrHandlingErrorKind, ErrNotifyBuilder};
+use crate::Err;
+
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "notify")]
+use super::std_handler;
+
+#[cfg(feature = "notify-tokio")]
+use super::tokio_handler;
+#[cfg(feature = "notify-tokio")]
+use std::{future::Future, sync::Arc};
+
+impl ErrNotifyBuilder {
+    /// Creates a new `ErrNotifyBuilder` which has no error handlers.
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "notify")]
+            std_handlers: std_handler::Handlers::new(),
+            #[cfg(feature = "notify-tokio")]
+            tokio_handlers: Vec::new(),
+        }
+    }
+
+    /// Adds a synchronous error handler, as well as [`add_sync_err_handler`](crate::add_sync_err_handler).
+    ///
+    /// The synchronous handlers are executed in the order they are added to this builder.
+    #[cfg(feature = "notify")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+    pub fn sync<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+    {
+        self.std_handlers.push_sync(handler);
+        self
+    }
+
+    /// Adds an asynchronous error handler, as well as [`add_async_err_handler`](crate::add_async_err_handler).
+    #[cfg(feature = "notify")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+    pub fn async_<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+    {
+        self.std_handlers.push_async(handler);
+        self
+    }
+
+    /// Adds a Tokio-based asynchronous error handler, as well as
+    /// [`add_tokio_async_err_handler`](crate::add_tokio_async_err_handler).
+    #[cfg(feature = "notify-tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notify-tokio")))]
+    pub fn tokio_async<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(Arc<Err>, DateTime<Utc>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.tokio_handlers
+            .push(tokio_handler::box_tokio_async_handler(handler));
+        self
+    }
+
+    /// Registers all the error handlers added to this builder, and then fixes the error
+    /// handlers with [`fix_err_handlers`](crate::fix_err_handlers).
+    ///
+    /// # Returns
+    /// - `Ok(())` if the handlers were successfully installed.
+    /// - `Err(ErrHandlingError)` if an error occurred during installation. If the handlers have
+    ///   already been fixed, no handler is registered and the kind of the error is
+    ///   [`ErrHandlingErrorKind::AlreadyFixed`].
+    pub fn install(self) -> Result<(), ErrHandlingError> {
+        if super::is_fixed() {
+            return Err(ErrHandlingError::new(ErrHandlingErrorKind::AlreadyFixed));
+        }
+
+        #[cfg(feature = "notify")]
+        has_handlers::mark_if_ok(
+            &has_handlers::HAS_HANDLERS,
+            std_handler::add_handlers(&std_handler::HANDLERS, self.std_handlers),
+        )?;
+
+        #[cfg(feature = "notify-tokio")]
+        has_handlers::mark_if_ok(
+            &has_handlers::HAS_HANDLERS,
+            tokio_handler::add_tokio_async_handlers(&tokio_handler::HANDLERS, self.tokio_handlers),
+        )?;
+
+        super::fix_err_handlers()
+    }
+}
+
+impl Default for ErrNotifyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
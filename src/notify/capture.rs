@@ -0,0 +1,114 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::CapturedErr;
+use crate::Err;
+
+use std::cell::RefCell;
+
+struct Frame {
+    filter: fn(&Err) -> bool,
+    captured: Vec<CapturedErr>,
+}
+
+thread_local! {
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+struct FrameGuard {
+    depth: usize,
+}
+
+impl Drop for FrameGuard {
+    fn drop(&mut self) {
+        // Also pops the frames on unwinding, so that a panic in the closure does not leave the
+        // capture active.
+        FRAMES.with(|frames| frames.borrow_mut().truncate(self.depth));
+    }
+}
+
+pub(crate) fn capture_with<F>(filter: fn(&Err) -> bool, f: F) -> Vec<CapturedErr>
+where
+    F: FnOnce(),
+{
+    let depth = FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        frames.push(Frame {
+            filter,
+            captured: Vec::new(),
+        });
+        frames.len() - 1
+    });
+    let guard = FrameGuard { depth };
+
+    f();
+
+    let captured = FRAMES.with(|frames| std::mem::take(&mut frames.borrow_mut()[depth].captured));
+    drop(guard);
+    captured
+}
+
+pub(crate) fn capture_err(err: &Err) {
+    FRAMES.with(|frames| {
+        let Ok(mut frames) = frames.try_borrow_mut() else {
+            return;
+        };
+        for frame in frames.iter_mut() {
+            if (frame.filter)(err) {
+                frame.captured.push(CapturedErr {
+                    file: err.file(),
+                    line: err.line(),
+                    reason: err.reason_debug_string(),
+                });
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests_of_capture {
+    use super::*;
+    use std::panic;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    fn any_err(_err: &Err) -> bool {
+        true
+    }
+
+    #[test]
+    fn no_capture_outside_closure() {
+        let _err = Err::new(Reasons::FailToDoSomething);
+        let captured = capture_with(any_err, || {});
+        assert!(captured.is_empty());
+    }
+
+    #[test]
+    fn nested_captures() {
+        let mut inner = Vec::new();
+        let outer = capture_with(any_err, || {
+            let _err = Err::new(Reasons::FailToDoSomething);
+            inner = capture_with(any_err, || {
+                let _err = Err::new("inner".to_string());
+            });
+        });
+        assert_eq!(outer.len(), 2);
+        assert_eq!(outer[0].reason, "FailToDoSomething");
+        assert_eq!(outer[1].reason, "\"inner\"");
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].reason, "\"inner\"");
+    }
+
+    #[test]
+    fn frame_is_popped_on_panic() {
+        let result = panic::catch_unwind(|| {
+            capture_with(any_err, || panic!("boom"));
+        });
+        assert!(result.is_err());
+        FRAMES.with(|frames| assert!(frames.borrow().is_empty()));
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Err;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use std::{any, collections::HashMap, sync::Mutex, time::Duration};
+
+type Key = (any::TypeId, &'static str, u32, u32);
+
+pub(crate) struct Dedup {
+    window: TimeDelta,
+    last_handled: Mutex<HashMap<Key, DateTime<Utc>>>,
+}
+
+impl Dedup {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window: TimeDelta::from_std(window).unwrap_or(TimeDelta::MAX),
+            last_handled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Checks whether the error should be handled, and if so, records the time as the last time
+    // an error with the same reason type and location was handled.
+    pub(crate) fn should_handle(&self, err: &Err, tm: DateTime<Utc>) -> bool {
+        let key = (
+            err.reason_any().type_id(),
+            err.file(),
+            err.line(),
+            err.column(),
+        );
+        let mut last_handled = match self.last_handled.lock() {
+            Ok(m) => m,
+            Err(e) => e.into_inner(),
+        };
+        if let Some(last) = last_handled.get(&key) {
+            if tm.signed_duration_since(*last) < self.window {
+                return false;
+            }
+        }
+        last_handled.insert(key, tm);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests_of_dedup {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    fn create_err() -> Err {
+        Err::new_silent(Reasons::FailToDoSomething)
+    }
+
+    #[test]
+    fn suppress_same_error_within_window() {
+        let dedup = Dedup::new(Duration::from_secs(10));
+        let err = create_err();
+        let tm = Utc::now();
+        assert!(dedup.should_handle(&err, tm));
+        assert!(!dedup.should_handle(&err, tm + TimeDelta::seconds(5)));
+        assert!(dedup.should_handle(&err, tm + TimeDelta::seconds(10)));
+        assert!(!dedup.should_handle(&err, tm + TimeDelta::seconds(15)));
+    }
+
+    #[test]
+    fn not_suppress_errors_at_different_locations() {
+        let dedup = Dedup::new(Duration::from_secs(10));
+        let err0 = create_err();
+        let err1 = Err::new_silent(Reasons::FailToDoSomething);
+        let tm = Utc::now();
+        assert!(dedup.should_handle(&err0, tm));
+        assert!(dedup.should_handle(&err1, tm));
+        assert!(!dedup.should_handle(&create_err(), tm));
+    }
+
+    #[test]
+    fn not_suppress_errors_at_different_columns() {
+        let dedup = Dedup::new(Duration::from_secs(10));
+        #[rustfmt::skip]
+        let errs = [Err::new_silent(Reasons::FailToDoSomething), Err::new_silent(Reasons::FailToDoSomething)];
+        assert_eq!(errs[0].line(), errs[1].line());
+        let tm = Utc::now();
+        assert!(dedup.should_handle(&errs[0], tm));
+        assert!(dedup.should_handle(&errs[1], tm));
+    }
+
+    #[test]
+    fn not_suppress_errors_of_different_reason_types() {
+        let dedup = Dedup::new(Duration::from_secs(10));
+        let errs = [
+            Err::new_silent(Reasons::FailToDoSomething),
+            Err::new_silent("fail".to_string()),
+        ];
+        let tm = Utc::now();
+        assert!(dedup.should_handle(&errs[0], tm));
+        assert!(dedup.should_handle(&errs[1], tm));
+    }
+}
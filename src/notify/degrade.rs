@@ -0,0 +1,87 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::{ErrHandlingError, ErrHandlingErrorKind};
+
+use std::sync::atomic;
+
+pub(crate) static DEGRADED: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+pub(crate) fn is_degraded(degraded: &atomic::AtomicBool) -> bool {
+    degraded.load(atomic::Ordering::Acquire)
+}
+
+// Switches to the degraded state when the mutex of error handlers is poisoned. The error is
+// returned only when the state is switched, so that it is reported only once.
+pub(crate) fn check_poisoned(
+    degraded: &atomic::AtomicBool,
+    result: Result<(), ErrHandlingError>,
+) -> Result<(), ErrHandlingError> {
+    match result {
+        Err(e) if e.kind() == ErrHandlingErrorKind::StdMutexIsPoisoned => {
+            if degraded.swap(true, atomic::Ordering::AcqRel) {
+                Ok(())
+            } else {
+                Err(e)
+            }
+        }
+        _ => result,
+    }
+}
+
+#[cfg(test)]
+mod tests_of_degrade {
+    use super::*;
+
+    #[test]
+    fn not_degraded_by_default() {
+        let degraded = atomic::AtomicBool::new(false);
+        assert!(!is_degraded(&degraded));
+    }
+
+    #[test]
+    fn degraded_once_by_poisoned_mutex() {
+        let degraded = atomic::AtomicBool::new(false);
+
+        let result = check_poisoned(
+            &degraded,
+            Err(ErrHandlingError::new(
+                ErrHandlingErrorKind::StdMutexIsPoisoned,
+            )),
+        );
+        assert_eq!(
+            result.unwrap_err().kind(),
+            ErrHandlingErrorKind::StdMutexIsPoisoned
+        );
+        assert!(is_degraded(&degraded));
+
+        let result = check_poisoned(
+            &degraded,
+            Err(ErrHandlingError::new(
+                ErrHandlingErrorKind::StdMutexIsPoisoned,
+            )),
+        );
+        assert!(result.is_ok());
+        assert!(is_degraded(&degraded));
+    }
+
+    #[test]
+    fn not_degraded_by_other_errors() {
+        let degraded = atomic::AtomicBool::new(false);
+
+        assert!(check_poisoned(&degraded, Ok(())).is_ok());
+
+        let result = check_poisoned(
+            &degraded,
+            Err(ErrHandlingError::new(
+                ErrHandlingErrorKind::InvalidCallTiming,
+            )),
+        );
+        assert_eq!(
+            result.unwrap_err().kind(),
+            ErrHandlingErrorKind::InvalidCallTiming
+        );
+        assert!(!is_degraded(&degraded));
+    }
+}
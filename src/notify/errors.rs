@@ -22,7 +22,21 @@ impl ErrHandlingError {
 
 impl fmt::Display for ErrHandlingError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.kind)
+        let msg = match self.kind {
+            ErrHandlingErrorKind::StdMutexIsPoisoned => {
+                "the mutex of error handlers is poisoned because a thread panicked while holding it"
+            }
+            ErrHandlingErrorKind::InvalidInternalState => {
+                "the internal state of error handlers is invalid, which may be a bug of errs crate"
+            }
+            ErrHandlingErrorKind::InvalidCallTiming => {
+                "cannot access error handlers while they are being fixed; register error handlers before fix_err_handlers() is called or the first Err is created"
+            }
+            ErrHandlingErrorKind::AlreadyFixed => {
+                "cannot register an error handler after fix_err_handlers() was called or the first Err was created"
+            }
+        };
+        write!(f, "{msg}")
     }
 }
 
@@ -52,6 +66,27 @@ mod tests_of_err_handling_error {
     fn test_display() {
         let e = ErrHandlingError::new(ErrHandlingErrorKind::InvalidCallTiming);
         assert_eq!(e.kind(), ErrHandlingErrorKind::InvalidCallTiming);
-        assert_eq!(format!("{e}"), "InvalidCallTiming");
+        assert_eq!(
+            format!("{e}"),
+            "cannot access error handlers while they are being fixed; register error handlers before fix_err_handlers() is called or the first Err is created"
+        );
+
+        let e = ErrHandlingError::new(ErrHandlingErrorKind::AlreadyFixed);
+        assert_eq!(
+            format!("{e}"),
+            "cannot register an error handler after fix_err_handlers() was called or the first Err was created"
+        );
+
+        let e = ErrHandlingError::new(ErrHandlingErrorKind::StdMutexIsPoisoned);
+        assert_eq!(
+            format!("{e}"),
+            "the mutex of error handlers is poisoned because a thread panicked while holding it"
+        );
+
+        let e = ErrHandlingError::new(ErrHandlingErrorKind::InvalidInternalState);
+        assert_eq!(
+            format!("{e}"),
+            "the internal state of error handlers is invalid, which may be a bug of errs crate"
+        );
     }
 }
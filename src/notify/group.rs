@@ -0,0 +1,76 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::sync::{atomic, Arc, Mutex};
+
+pub(crate) type Groups = Mutex<Vec<(String, Arc<atomic::AtomicBool>)>>;
+
+pub(crate) static GROUPS: Groups = Mutex::new(Vec::new());
+
+// Gets the enabled flag of the group, creating an enabled one if the group is not known yet.
+// The flag is shared with the handlers in the group, so that they can check it without locking.
+pub(crate) fn enabled_flag(groups: &Groups, name: &str) -> Arc<atomic::AtomicBool> {
+    let mut gs = match groups.lock() {
+        Ok(gs) => gs,
+        Err(e) => e.into_inner(),
+    };
+    if let Some((_, flag)) = gs.iter().find(|(n, _)| n == name) {
+        return Arc::clone(flag);
+    }
+    let flag = Arc::new(atomic::AtomicBool::new(true));
+    gs.push((name.to_string(), Arc::clone(&flag)));
+    flag
+}
+
+pub(crate) fn set_enabled(groups: &Groups, name: &str, enabled: bool) {
+    enabled_flag(groups, name).store(enabled, atomic::Ordering::Release);
+}
+
+pub(crate) fn is_enabled(flag: &atomic::AtomicBool) -> bool {
+    flag.load(atomic::Ordering::Acquire)
+}
+
+#[cfg(test)]
+mod tests_of_group {
+    use super::*;
+
+    #[test]
+    fn group_is_enabled_by_default() {
+        let groups = Groups::new(Vec::new());
+        let flag = enabled_flag(&groups, "alerts");
+        assert!(is_enabled(&flag));
+    }
+
+    #[test]
+    fn same_group_shares_flag() {
+        let groups = Groups::new(Vec::new());
+        let flag0 = enabled_flag(&groups, "alerts");
+        let flag1 = enabled_flag(&groups, "alerts");
+        let flag2 = enabled_flag(&groups, "metrics");
+        assert!(Arc::ptr_eq(&flag0, &flag1));
+        assert!(!Arc::ptr_eq(&flag0, &flag2));
+    }
+
+    #[test]
+    fn toggle_group() {
+        let groups = Groups::new(Vec::new());
+        let flag = enabled_flag(&groups, "alerts");
+        let other = enabled_flag(&groups, "metrics");
+
+        set_enabled(&groups, "alerts", false);
+        assert!(!is_enabled(&flag));
+        assert!(is_enabled(&other));
+
+        set_enabled(&groups, "alerts", true);
+        assert!(is_enabled(&flag));
+    }
+
+    #[test]
+    fn disable_group_before_adding_handlers() {
+        let groups = Groups::new(Vec::new());
+        set_enabled(&groups, "alerts", false);
+        let flag = enabled_flag(&groups, "alerts");
+        assert!(!is_enabled(&flag));
+    }
+}
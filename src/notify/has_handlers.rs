@@ -0,0 +1,54 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::ErrHandlingError;
+
+use std::sync::atomic;
+
+pub(crate) static HAS_HANDLERS: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+pub(crate) fn has_handlers(flag: &atomic::AtomicBool) -> bool {
+    flag.load(atomic::Ordering::Acquire)
+}
+
+pub(crate) fn mark(flag: &atomic::AtomicBool) {
+    flag.store(true, atomic::Ordering::Release);
+}
+
+pub(crate) fn mark_if_ok(
+    flag: &atomic::AtomicBool,
+    result: Result<(), ErrHandlingError>,
+) -> Result<(), ErrHandlingError> {
+    if result.is_ok() {
+        mark(flag);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests_of_has_handlers {
+    use super::*;
+    use crate::notify::ErrHandlingErrorKind;
+
+    #[test]
+    fn no_handlers_by_default() {
+        let flag = atomic::AtomicBool::new(false);
+        assert!(!has_handlers(&flag));
+    }
+
+    #[test]
+    fn marked_by_successful_registration() {
+        let flag = atomic::AtomicBool::new(false);
+
+        let result = mark_if_ok(
+            &flag,
+            Err(ErrHandlingError::new(ErrHandlingErrorKind::AlreadyFixed)),
+        );
+        assert!(result.is_err());
+        assert!(!has_handlers(&flag));
+
+        assert!(mark_if_ok(&flag, Ok(())).is_ok());
+        assert!(has_handlers(&flag));
+    }
+}
@@ -2,7 +2,18 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
+mod builder;
+mod capture;
+mod dedup;
+mod degrade;
 mod errors;
+#[cfg(feature = "notify")]
+mod group;
+mod has_handlers;
+#[cfg(feature = "notify")]
+mod ring_buffer;
+mod sampling;
+mod thread_count;
 
 #[cfg(feature = "notify")]
 mod std_handler;
@@ -14,12 +25,13 @@ pub use std_handler::{AsyncHandlerRegistration, SyncHandlerRegistration};
 mod tokio_handler;
 
 #[cfg(feature = "notify-tokio")]
-pub use tokio_handler::TokioAsyncHandlerRegistration;
+pub use tokio_handler::{TokioAsyncHandlerRegistration, TokioAsyncRawFn};
 
 use crate::Err;
 use chrono::{DateTime, Utc};
+use setup_read_cleanup::{graceful::GracefulPhasedCellSync, PhasedError, PhasedErrorKind};
 
-use std::sync;
+use std::{fmt, panic, sync, time};
 
 #[cfg(feature = "notify-tokio")]
 use std::future::Future;
@@ -31,6 +43,7 @@ pub enum ErrHandlingErrorKind {
     StdMutexIsPoisoned,
     InvalidInternalState,
     InvalidCallTiming,
+    AlreadyFixed,
 }
 
 /// Represents an error that occurred during the error handling notification process.
@@ -42,32 +55,132 @@ pub struct ErrHandlingError {
     kind: ErrHandlingErrorKind,
 }
 
+/// Collects error handlers and installs them all at once.
+///
+/// This provides a single and ordered setup path for error notification, instead of mixing
+/// the registration macros and the `add_*` functions. The handlers are registered in the
+/// order of the builder method calls, and [`ErrNotifyBuilder::install`] fixes the handlers
+/// after registering them.
+///
+/// ```rust
+/// #[cfg(feature = "notify")]
+/// errs::ErrNotifyBuilder::new()
+///     .sync(|err, tm| println!("[1] {}:{} {} ({})", err.file(), err.line(), err, tm))
+///     .sync(|err, tm| println!("[2] {}:{} {} ({})", err.file(), err.line(), err, tm))
+///     .async_(|err, tm| println!("[async] {}:{} {} ({})", err.file(), err.line(), err, tm))
+///     .install()
+///     .unwrap();
+/// ```
+pub struct ErrNotifyBuilder {
+    #[cfg(feature = "notify")]
+    std_handlers: std_handler::Handlers,
+    #[cfg(feature = "notify-tokio")]
+    tokio_handlers: Vec<tokio_handler::TokioAsyncFn>,
+}
+
+/// Holds the information of an `Err` captured by [`capture_errors_of`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedErr {
+    /// The name of the source file where the error occurred.
+    pub file: &'static str,
+
+    /// The line number in the source file where the error occurred.
+    pub line: u32,
+
+    /// The `Debug` string of the reason of the error.
+    pub reason: String,
+}
+
+/// Holds a lightweight record of an `Err` kept in the ring buffer enabled with
+/// [`enable_ring_buffer`].
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrRecord {
+    /// The name of the source file where the error occurred.
+    pub file: &'static str,
+
+    /// The line number in the source file where the error occurred.
+    pub line: u32,
+
+    /// The `Debug` string of the reason of the error.
+    pub reason: String,
+
+    /// The time when the error was notified.
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Registers an asynchronous error handler.
 ///
 /// This handler will be executed in a separate thread when an `Err` instance is created.
 /// Handlers can only be registered before [`fix_err_handlers`] is called, or before the
 /// first `Err` instance is created.
 ///
+/// Since this handler may run after the `Err` instance is returned to its creation site, it
+/// receives a copy of the error which shares the reason with the original and holds its source
+/// chain rendered into strings. The reason of the copy can be retrieved with [`Err::reason`] and
+/// [`Err::match_reason`], and the copy has the same location, `Display` and `Debug` output,
+/// fields, and source chain as the original. While the copy is kept by the handlers,
+/// [`Err::reason_mut`] and [`Err::downcast_reason`] on the returned `Err` fail, but its source
+/// can still be taken or replaced.
+///
 /// # Parameters
 /// - `handler`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
 ///   timestamp, and performs error handling logic. It must be `Send + Sync + 'static`.
 ///
 /// # Returns
 /// - `Ok(())` if the handler was successfully registered.
-/// - `Err(ErrHandlingError)` if an error occurred during registration (e.g., trying to register
-///   after the handlers have been fixed).
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
 #[cfg(feature = "notify")]
 #[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
 pub fn add_async_err_handler<F>(handler: F) -> Result<(), ErrHandlingError>
 where
     F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
 {
-    std_handler::add_async_handler(&std_handler::HANDLERS, handler)
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_async_handler(&std_handler::HANDLERS, handler),
+    )
+}
+
+/// Registers an asynchronous error handler which runs sequentially with other handlers
+/// registered by this function.
+///
+/// Unlike the handlers registered with [`add_async_err_handler`], which run concurrently,
+/// these handlers are executed one after another in the order of registration on a single
+/// background thread when an `Err` instance is created. Like them, these handlers receive a
+/// copy of the error which shares the reason with the original.
+/// If the handler panics, the panic is caught, `ERROR(errs): handler panicked` is printed to
+/// stderr, and the subsequent handlers are still executed.
+/// Handlers can only be registered before [`fix_err_handlers`] is called, or before the
+/// first `Err` instance is created.
+///
+/// # Parameters
+/// - `handler`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
+///   timestamp, and performs error handling logic. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_sequential_async_err_handler<F>(handler: F) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_sequential_async_handler(&std_handler::HANDLERS, handler),
+    )
 }
 
 /// Registers a synchronous error handler.
 ///
 /// This handler will be executed in the current thread when an `Err` instance is created.
+/// If the handler panics, the panic is caught, `ERROR(errs): handler panicked` is printed to
+/// stderr, and the remaining handlers are still executed.
 /// Handlers can only be registered before [`fix_err_handlers`] is called, or before the
 /// first `Err` instance is created.
 ///
@@ -77,15 +190,455 @@ where
 ///
 /// # Returns
 /// - `Ok(())` if the handler was successfully registered.
-/// - `Err(ErrHandlingError)` if an error occurred during registration (e.g., trying to register
-///   after the handlers have been fixed).
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
 #[cfg(feature = "notify")]
 #[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
 pub fn add_sync_err_handler<F>(handler: F) -> Result<(), ErrHandlingError>
 where
     F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
 {
-    std_handler::add_sync_handler(&std_handler::HANDLERS, handler)
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_sync_handler(&std_handler::HANDLERS, handler),
+    )
+}
+
+/// Registers a synchronous error handler which is executed only for errors whose reasons are
+/// of the specified type.
+///
+/// This handler receives the reason already downcast to `R`, which removes the type-checking
+/// boilerplate from the handler. Except for this, it is the same as the handler registered with
+/// [`add_sync_err_handler`].
+///
+/// # Parameters
+/// - `R`: The type of the reasons of errors which this handler handles.
+/// - `handler`: A closure that takes a reference to the reason, a reference to the `Err`
+///   instance, and a `DateTime<Utc>` timestamp. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+///
+/// ```rust
+/// #[derive(Debug)]
+/// enum DbErr {
+///     ConnectionLost { host: String },
+/// }
+///
+/// errs::add_typed_sync_err_handler::<DbErr, _>(|reason, err, _tm| match reason {
+///     DbErr::ConnectionLost { host } => println!("lost connection to {host}: {err:?}"),
+/// })
+/// .unwrap();
+/// ```
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_typed_sync_err_handler<R, F>(handler: F) -> Result<(), ErrHandlingError>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    F: Fn(&R, &Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_typed_sync_handler(&std_handler::HANDLERS, handler),
+    )
+}
+
+/// Registers a synchronous error handler which belongs to the specified group.
+///
+/// The handlers in a group can be enabled or disabled at runtime with [`set_group_enabled`],
+/// even after the handlers are fixed. A group is enabled by default. Except for this, the
+/// handler is the same as the handler registered with [`add_sync_err_handler`].
+///
+/// # Parameters
+/// - `group`: The name of the group which this handler belongs to.
+/// - `handler`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
+///   timestamp. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+///
+/// ```rust
+/// errs::add_sync_err_handler_in_group("alerts", |err, _tm| {
+///     println!("send an alert: {err}");
+/// })
+/// .unwrap();
+///
+/// // Stops sending alerts.
+/// errs::set_group_enabled("alerts", false);
+/// ```
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_sync_err_handler_in_group<F>(group: &str, handler: F) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_sync_handler_in_group(
+            &std_handler::HANDLERS,
+            group::enabled_flag(&group::GROUPS, group),
+            handler,
+        ),
+    )
+}
+
+/// Enables or disables the error handlers which belong to the specified group.
+///
+/// The handlers in a disabled group are skipped when an error is notified.
+/// This function can be called both before and after the handlers are fixed, and also before
+/// any handler is registered in the group.
+///
+/// # Parameters
+/// - `group`: The name of the group.
+/// - `enabled`: `true` to enable the handlers in the group, or `false` to disable them.
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn set_group_enabled(group: &str, enabled: bool) {
+    group::set_enabled(&group::GROUPS, group, enabled);
+}
+
+/// Enables the ring buffer which keeps the records of the most recent errors.
+///
+/// This is useful for post-mortem debugging: e.g. a crash handler can dump the records taken
+/// with [`drain_ring_buffer`]. When this function is called for the first time, an internal
+/// synchronous handler is registered which pushes a record of each notified error into the
+/// ring buffer. If the ring buffer is full, the oldest record is discarded.
+///
+/// This function can be called again to change the capacity, and setting it to zero stops
+/// recording. Since the internal handler is registered at the first call, the first call must
+/// be before [`fix_err_handlers`] is called.
+///
+/// # Parameters
+/// - `capacity`: The maximum number of the records kept in the ring buffer.
+///
+/// # Returns
+/// - `Ok(())` if the ring buffer was successfully enabled.
+/// - `Err(ErrHandlingError)` if an error occurred during registration of the internal handler.
+///   If the handlers have already been fixed, the kind of the error is
+///   [`ErrHandlingErrorKind::AlreadyFixed`].
+///
+/// ```rust
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// errs::enable_ring_buffer(100).unwrap();
+/// errs::fix_err_handlers().unwrap();
+///
+/// let _err = errs::Err::new(Reasons::FailToDoSomething);
+///
+/// let records = errs::drain_ring_buffer();
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].reason, "FailToDoSomething");
+/// ```
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn enable_ring_buffer(capacity: usize) -> Result<(), ErrHandlingError> {
+    ring_buffer::enable(&ring_buffer::RING, capacity, || {
+        has_handlers::mark_if_ok(
+            &has_handlers::HAS_HANDLERS,
+            std_handler::add_sync_handler(&std_handler::HANDLERS, |err, tm| {
+                ring_buffer::push(&ring_buffer::RING, err, tm);
+            }),
+        )
+    })
+}
+
+/// Takes all the records kept in the ring buffer enabled with [`enable_ring_buffer`].
+///
+/// The ring buffer becomes empty after this function is called.
+///
+/// # Returns
+/// A vector of the records in the order of notification, from the oldest to the newest.
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn drain_ring_buffer() -> Vec<ErrRecord> {
+    ring_buffer::drain(&ring_buffer::RING)
+}
+
+/// Registers a synchronous error handler which receives the `Err` instance as `Arc<Err>`.
+///
+/// This handler can keep the error after it returns, e.g. by pushing it into a queue for later
+/// processing. Except for this, it is the same as the handler registered with
+/// [`add_sync_err_handler`], but it runs after the handlers registered with that function and
+/// is not invoked by [`notify_batch`].
+///
+/// The `Arc<Err>` is a copy of the error which shares the reason with the original, like the
+/// one passed to the handlers registered with [`add_async_err_handler`]. So keeping it does not
+/// prevent the `Err` instance returned to the creation site from taking or replacing its
+/// source, but [`Err::reason_mut`] and [`Err::downcast_reason`] on it fail while it is kept.
+///
+/// # Parameters
+/// - `handler`: A closure that takes an `Arc<Err>` and a `DateTime<Utc>` timestamp. It must be
+///   `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+///
+/// ```rust
+/// use errs::Err;
+/// use std::sync::{Arc, Mutex};
+///
+/// static QUEUE: Mutex<Vec<Arc<Err>>> = Mutex::new(Vec::new());
+///
+/// errs::add_arc_sync_err_handler(|err, _tm| {
+///     QUEUE.lock().unwrap().push(err);
+/// })
+/// .unwrap();
+/// ```
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_arc_sync_err_handler<F>(handler: F) -> Result<(), ErrHandlingError>
+where
+    F: Fn(sync::Arc<Err>, DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_arc_sync_handler(&std_handler::HANDLERS, handler),
+    )
+}
+
+/// Registers a synchronous error handler which is waited for only until the given timeout.
+///
+/// Unlike the handler registered with [`add_sync_err_handler`], which runs inline in the
+/// creation of an `Err`, this handler runs on a worker thread, and the creation of an `Err`
+/// waits for it only until the timeout. If the handler does not return in time,
+/// `handler timed out` is printed to stderr and the notification moves on, while the handler
+/// keeps running on the worker thread. This prevents a handler which deadlocks or loops forever
+/// from hanging the creation of an `Err`.
+///
+/// Each handler has its own worker thread, which is started at the first notification and
+/// runs the handler for one error at a time. Errors are not queued: while the handler is still
+/// running for a previous error, whether it timed out or is running for an `Err` created on
+/// another thread, the notification of a new error to this handler is dropped, and
+/// `handler is still running, skipped` is printed to stderr. The handler is run again for
+/// errors created after the previous call returns.
+///
+/// Since the handler may still be running after the `Err` is returned, it receives a copy of
+/// the error which shares the reason with the original, like the handlers registered with
+/// [`add_async_err_handler`].
+///
+/// Handlers with a timeout run after the other synchronous handlers, and do not affect whether
+/// the handlers registered with [`add_unhandled_err_handler`] run. They are not invoked by
+/// [`notify_batch`].
+///
+/// # Parameters
+/// - `timeout`: The maximum duration to wait for the handler.
+/// - `handler`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
+///   timestamp. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// errs::add_sync_err_handler_timeout(Duration::from_millis(100), |err, _tm| {
+///     println!("send to a remote service: {err}");
+/// })
+/// .unwrap();
+/// ```
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_sync_err_handler_timeout<F>(
+    timeout: time::Duration,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_sync_timeout_handler(&std_handler::HANDLERS, timeout, handler),
+    )
+}
+
+/// Registers a synchronous error handler with a priority.
+///
+/// Synchronous handlers are executed in ascending order of their priorities. Handlers with
+/// the same priority are executed in the order they were registered. Handlers registered with
+/// [`add_sync_err_handler`] or the `add_sync_err_handler!` macro have priority `0`, and
+/// handlers registered with the macro run before the ones registered with functions.
+///
+/// Handlers can only be registered before [`fix_err_handlers`] is called, or before the
+/// first `Err` instance is created.
+///
+/// # Parameters
+/// - `priority`: The priority of the handler. A handler with a lower value runs earlier.
+/// - `handler`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
+///   timestamp, and performs error handling logic. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_sync_err_handler_with_priority<F>(
+    priority: i32,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_sync_handler_with_priority(&std_handler::HANDLERS, priority, handler),
+    )
+}
+
+/// Registers a synchronous error handler which reports whether it handled an error.
+///
+/// This handler will be executed in the current thread when an `Err` instance is created,
+/// like a handler registered with [`add_sync_err_handler`]. The handler returns `true` if it
+/// handled the error, or `false` otherwise. This is useful for routing errors to handlers by
+/// their reason types, together with [`add_unhandled_err_handler`].
+///
+/// Handlers registered with [`add_sync_err_handler`] are regarded as not having handled any
+/// error.
+///
+/// Handlers can only be registered before [`fix_err_handlers`] is called, or before the
+/// first `Err` instance is created.
+///
+/// # Parameters
+/// - `handler`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
+///   timestamp, and returns whether it handled the error. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_sync_err_handler_reporting_handled<F>(handler: F) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) -> bool + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_sync_reporting_handler(&std_handler::HANDLERS, handler),
+    )
+}
+
+/// Registers a fallback error handler for errors which no synchronous handler handled.
+///
+/// This handler will be executed in the current thread after the synchronous handlers, only
+/// if none of the handlers registered with [`add_sync_err_handler_reporting_handled`] reported
+/// that it handled the error.
+///
+/// Handlers can only be registered before [`fix_err_handlers`] is called, or before the
+/// first `Err` instance is created.
+///
+/// # Parameters
+/// - `handler`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
+///   timestamp, and performs error handling logic. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+///
+/// ```rust
+/// use errs::Err;
+///
+/// #[derive(Debug)]
+/// enum DbErr {
+///     ConnectionLost,
+/// }
+///
+/// errs::add_sync_err_handler_reporting_handled(|err, _tm| {
+///     match err.reason::<DbErr>() {
+///         Ok(r) => {
+///             println!("db error: {r:?}");
+///             true
+///         }
+///         Err(_) => false,
+///     }
+/// })
+/// .unwrap();
+///
+/// errs::add_unhandled_err_handler(|err, _tm| {
+///     println!("unhandled error: {err:?}");
+/// })
+/// .unwrap();
+/// ```
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_unhandled_err_handler<F>(handler: F) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_unhandled_handler(&std_handler::HANDLERS, handler),
+    )
+}
+
+/// Registers a synchronous batch error handler.
+///
+/// This handler will be executed in the current thread and receives a slice of `Err`
+/// instances at once. When errors are notified together with [`notify_batch`], the handler
+/// receives all of them in a single call. When an `Err` instance is created individually, the
+/// handler receives a slice containing only that error.
+///
+/// Handlers can only be registered before [`fix_err_handlers`] is called, or before the
+/// first `Err` instance is created.
+///
+/// # Parameters
+/// - `handler`: A closure that takes a slice of `Err` references and a `DateTime<Utc>`
+///   timestamp, and performs error handling logic. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the handler was successfully registered.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn add_sync_batch_err_handler<F>(handler: F) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&[&Err], DateTime<Utc>) + Send + Sync + 'static,
+{
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        std_handler::add_sync_batch_handler(&std_handler::HANDLERS, handler),
+    )
+}
+
+/// Notifies multiple errors together to the synchronous error handlers.
+///
+/// Each synchronous handler registered with [`add_sync_err_handler`] is invoked once per
+/// error, and each batch handler registered with [`add_sync_batch_err_handler`] is invoked
+/// once with the whole slice. The handlers are read with a single access to the handler
+/// registry for all the errors.
+///
+/// Asynchronous handlers are not invoked by this function.
+///
+/// Since errors created with [`Err::new`] or [`Err::with_source`] are already notified at
+/// their creation, this function is typically used with errors created with
+/// [`Err::new_silent`] or [`Err::with_source_silent`].
+///
+/// Like the creation of an `Err` instance, if [`fix_err_handlers`] has not been called yet,
+/// this function implicitly fixes the handlers.
+///
+/// # Parameters
+/// - `errs`: A slice of `Err` references to be notified.
+///
+/// # Returns
+/// - `Ok(())` if the errors were successfully notified.
+/// - `Err(ErrHandlingError)` if an error occurred while reading the handlers.
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+pub fn notify_batch(errs: &[&Err]) -> Result<(), ErrHandlingError> {
+    std_handler::handle_err_batch(&std_handler::HANDLERS, errs, Utc::now())
 }
 
 /// Registers a Tokio-based asynchronous error handler.
@@ -100,12 +653,14 @@ where
 /// # Parameters
 /// - `handler`: An `async` closure that takes an `Arc<Err>` and a `DateTime<Utc>`
 ///   timestamp, and returns a `Future`. The `Arc<Err>` is used to allow the `Err`
-///   instance to be shared across multiple asynchronous handlers. The closure must
-///   be `Send + Sync + 'static`.
+///   instance to be shared across multiple asynchronous handlers. It is a copy of the error
+///   which shares the reason with the original, like the one passed to the handlers registered
+///   with [`add_async_err_handler`]. The closure must be `Send + Sync + 'static`.
 ///
 /// # Returns
 /// - `Ok(())` if the handler was successfully registered.
-/// - `Err(ErrHandlingError)` if an error occurred during registration.
+/// - `Err(ErrHandlingError)` if an error occurred during registration. If the handlers have
+///   already been fixed, the kind of the error is [`ErrHandlingErrorKind::AlreadyFixed`].
 #[cfg(feature = "notify-tokio")]
 #[cfg_attr(docsrs, doc(cfg(feature = "notify-tokio")))]
 pub fn add_tokio_async_err_handler<F, Fut>(handler: F) -> Result<(), ErrHandlingError>
@@ -113,14 +668,43 @@ where
     F: Fn(sync::Arc<Err>, DateTime<Utc>) -> Fut + Send + Sync + 'static,
     Fut: Future<Output = ()> + Send + 'static,
 {
-    tokio_handler::add_tokio_async_handler(&tokio_handler::HANDLERS, handler)
+    has_handlers::mark_if_ok(
+        &has_handlers::HAS_HANDLERS,
+        tokio_handler::add_tokio_async_handler(&tokio_handler::HANDLERS, handler),
+    )
+}
+
+/// Waits for the Tokio-based asynchronous error handlers which are currently running.
+///
+/// This function awaits all handler tasks that have been spawned for the `Err` instances
+/// created so far. Tasks spawned after this function is called are not awaited.
+///
+/// This is useful to ensure that error notifications are handled before a program exits.
+#[cfg(feature = "notify-tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify-tokio")))]
+pub async fn flush_tokio_handlers() {
+    tokio_handler::flush(&tokio_handler::IN_FLIGHTS).await
+}
+
+/// Sets whether the failures of Tokio-based asynchronous error handlers are printed to stderr.
+///
+/// When a Tokio-based handler panics or is cancelled, `ERROR(errs): Fail to run tokio handler`
+/// is printed to stderr by default. This function allows disabling this output, e.g. when the
+/// failures are captured in another way.
+///
+/// # Parameters
+/// - `enabled`: `true` to print the failures, `false` not to print them.
+#[cfg(feature = "notify-tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify-tokio")))]
+pub fn set_tokio_handler_error_logging(enabled: bool) {
+    tokio_handler::set_join_error_logging(&tokio_handler::JOIN_ERROR_LOGGING, enabled);
 }
 
 /// Fixes the set of registered error handlers, preventing any further additions.
 ///
 /// Once this function is called, attempts to register new handlers using
 /// [`add_sync_err_handler`], [`add_async_err_handler`], or [`add_tokio_async_err_handler`]
-/// will fail.
+/// will fail with [`ErrHandlingErrorKind::AlreadyFixed`].
 /// If `Err` instances are created before this function is explicitly called, the handlers
 /// will be implicitly fixed upon the first `Err` notification.
 ///
@@ -142,16 +726,340 @@ pub fn fix_err_handlers() -> Result<(), ErrHandlingError> {
     Ok(())
 }
 
-pub(crate) fn notify_err(err: Err) -> Result<(), ErrHandlingError> {
+type SinkFn = Box<dyn Fn(&Err, DateTime<Utc>) + Send + Sync + 'static>;
+
+static NOTIFY_SINK: sync::OnceLock<SinkFn> = sync::OnceLock::new();
+
+/// Sets a sink which replaces the built-in dispatch of error notifications.
+///
+/// Once a sink is set, every notified `Err` instance is handed to the sink synchronously in
+/// the thread where it is created, instead of the registered error handlers. In this case,
+/// this crate spawns no threads for error notifications.
+/// This is useful for applications which already have a central event bus.
+///
+/// If the sink panics, the panic is caught and `ERROR(errs): handler panicked` is printed to
+/// stderr.
+/// The sink can only be set once, and before [`fix_err_handlers`] is called, or before the
+/// first `Err` instance is created. The sink is set while the handlers are locked, so that it
+/// cannot be set after the handlers are fixed by another thread. However, an `Err` instance
+/// created on another thread at the same time as the sink is set may still be notified to the
+/// registered handlers instead of the sink.
+///
+/// # Parameters
+/// - `sink`: A closure that takes a reference to an `Err` instance and a `DateTime<Utc>`
+///   timestamp. It must be `Send + Sync + 'static`.
+///
+/// # Returns
+/// - `Ok(())` if the sink was successfully set.
+/// - `Err(ErrHandlingError)` if the handlers have already been fixed, in which case the kind of
+///   the error is [`ErrHandlingErrorKind::AlreadyFixed`], or if a sink has already been set, in
+///   which case the kind is [`ErrHandlingErrorKind::InvalidCallTiming`].
+///
+/// ```rust
+/// errs::set_notify_sink(Box::new(|err, tm| {
+///     println!("{}:{} {} ({})", err.file(), err.line(), err, tm);
+/// }))
+/// .unwrap();
+/// ```
+pub fn set_notify_sink(sink: SinkFn) -> Result<(), ErrHandlingError> {
+    let set_sink = move || {
+        NOTIFY_SINK
+            .set(sink)
+            .map_err(|_| ErrHandlingError::new(ErrHandlingErrorKind::InvalidCallTiming))
+    };
+    #[cfg(feature = "notify-tokio")]
+    let set_sink = move || run_before_fixed(&tokio_handler::HANDLERS, set_sink);
+    #[cfg(feature = "notify")]
+    let set_sink = move || run_before_fixed(&std_handler::HANDLERS, set_sink);
+    set_sink()
+}
+
+// Runs the given function while holding the lock of the handlers, so that the handlers are not
+// fixed until it returns.
+fn run_before_fixed<T, F>(
+    handlers: &GracefulPhasedCellSync<T>,
+    f: F,
+) -> Result<(), ErrHandlingError>
+where
+    T: Send + Sync,
+    F: FnOnce() -> Result<(), ErrHandlingError>,
+{
+    match handlers.lock() {
+        Ok(_guard) => f(),
+        Err(e) => to_err_handling_error(e),
+    }
+}
+
+// Maps an error of the phased cell which holds handlers to the corresponding error of this crate.
+// Only `PhaseIsAlreadyRead`, which means the handlers are already fixed, is not an error.
+fn to_err_handling_error(e: PhasedError) -> Result<(), ErrHandlingError> {
+    match e.kind() {
+        PhasedErrorKind::PhaseIsAlreadyRead => Ok(()),
+        PhasedErrorKind::CannotCallOnPhaseRead => {
+            Err(ErrHandlingError::new(ErrHandlingErrorKind::AlreadyFixed))
+        }
+        PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
+            ErrHandlingErrorKind::InvalidInternalState,
+        )),
+        PhasedErrorKind::InternalDataMutexIsPoisoned
+        | PhasedErrorKind::GracefulWaitMutexIsPoisoned => Err(ErrHandlingError::new(
+            ErrHandlingErrorKind::StdMutexIsPoisoned,
+        )),
+        // PhasedErrorKind::FailToRunClosureDuringTransitionToRead => {}, // impossible case
+        _ => Err(ErrHandlingError::new(
+            ErrHandlingErrorKind::InvalidCallTiming,
+        )),
+    }
+}
+
+fn is_fixed() -> bool {
+    #[cfg(feature = "notify")]
+    if std_handler::HANDLERS.read_relaxed().is_ok() {
+        return true;
+    }
+    #[cfg(feature = "notify-tokio")]
+    if tokio_handler::HANDLERS.read_relaxed().is_ok() {
+        return true;
+    }
+    false
+}
+
+/// Sets the sampling rate of error notifications.
+///
+/// When this is set to `n`, error handlers are notified only of the first error and then
+/// every `n`th error, counted for each reason type. The errors which are not sampled are
+/// created normally, but the notification to the handlers is skipped.
+///
+/// The default is `1`, which means the handlers are notified of every error. `0` is treated
+/// as `1`.
+///
+/// # Parameters
+/// - `one_in_n`: The sampling rate, notifying one error out of this number.
+///
+/// ```rust
+/// // Notifies error handlers of one error out of 100 for each reason type.
+/// errs::set_notify_sampling(100);
+/// ```
+pub fn set_notify_sampling(one_in_n: u32) {
+    sampling::set_sampling(&sampling::SAMPLING, one_in_n);
+}
+
+/// Gets the number of `Err` instances created on the current thread.
+///
+/// The count includes silent errors and errors which are not notified due to sampling.
+/// It is held per thread without any locking, so it is cheap to read and complements the
+/// global counters.
+///
+/// ```rust
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// errs::reset_thread_error_count();
+/// let _err = errs::Err::new(Reasons::FailToDoSomething);
+/// assert_eq!(errs::thread_error_count(), 1);
+/// ```
+pub fn thread_error_count() -> u64 {
+    thread_count::get()
+}
+
+/// Resets the number of `Err` instances created on the current thread to zero.
+pub fn reset_thread_error_count() {
+    thread_count::reset();
+}
+
+pub(crate) fn count_thread_err() {
+    thread_count::count_up();
+}
+
+/// Runs the given closure and captures the errors created in it whose reason is of type `R`.
+///
+/// The errors are captured on the current thread, regardless of whether error handlers are
+/// registered or fixed. Silent errors are not captured since they are not notified.
+/// This is useful in tests which trigger many errors but only care about one kind of them.
+///
+/// # Type Parameters
+/// - `R`: The type of the reasons of the errors to be captured.
+///
+/// # Parameters
+/// - `f`: The closure in which errors are created.
+///
+/// # Returns
+/// A vector of the information of the captured errors in the order of their creation.
+///
+/// ```rust
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// let captured = errs::capture_errors_of::<Reasons>(|| {
+///     let _err = errs::Err::new(Reasons::FailToDoSomething);
+///     let _err = errs::Err::new("other reason".to_string());
+/// });
+/// assert_eq!(captured.len(), 1);
+/// assert_eq!(captured[0].reason, "FailToDoSomething");
+/// ```
+pub fn capture_errors_of<R>(f: impl FnOnce()) -> Vec<CapturedErr>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+{
+    capture::capture_with(Err::reason_is::<R>, f)
+}
+
+/// Creates an error handler which suppresses errors already handled within a time window.
+///
+/// The created handler calls the given handler with an error, unless an error with the same
+/// reason type at the same location (file, line and column) was passed to the given handler
+/// within `window` before. The time of the error is the time passed to the created handler.
+/// The created handler can be registered like any other handler, for example with
+/// `errs::add_sync_err_handler`.
+///
+/// # Parameters
+/// - `window`: The time window in which the same errors are suppressed.
+/// - `handler`: The handler called with errors which are not suppressed.
+///
+/// # Returns
+/// An error handler which wraps the given handler.
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// let count = Arc::new(AtomicUsize::new(0));
+/// let counter = count.clone();
+/// let handler = errs::dedup_handler(Duration::from_secs(60), move |err, tm| {
+///     println!("{}:{}:{} - {}", tm, err.file(), err.line(), err);
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+///
+/// let err = errs::Err::new_silent(Reasons::FailToDoSomething);
+/// let tm = chrono::Utc::now();
+/// handler(&err, tm);
+/// handler(&err, tm + Duration::from_secs(30));
+/// assert_eq!(count.load(Ordering::Relaxed), 1);
+/// ```
+pub fn dedup_handler<F>(
+    window: time::Duration,
+    handler: F,
+) -> impl Fn(&Err, DateTime<Utc>) + Send + Sync + 'static
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    let dedup = dedup::Dedup::new(window);
+    move |err, tm| {
+        if dedup.should_handle(err, tm) {
+            handler(err, tm);
+        }
+    }
+}
+
+/// Runs the given closure and panics if any error is notified during it on the current thread.
+///
+/// This is a testing convenience for a code path which must not cause any error. The errors are
+/// captured in the same way as [`capture_errors_of`], regardless of their reason types, and
+/// the panic message lists all of them.
+///
+/// # Parameters
+/// - `f`: The closure to run.
+///
+/// # Panics
+/// Panics if any error is notified during the closure.
+///
+/// ```rust
+/// #[derive(Debug)]
+/// enum Reasons {
+///     FailToDoSomething,
+/// }
+///
+/// errs::assert_no_errors(|| {
+///     let _err = errs::Err::new_silent(Reasons::FailToDoSomething);
+/// });
+/// ```
+#[track_caller]
+pub fn assert_no_errors(f: impl FnOnce()) {
+    let captured = capture::capture_with(|_| true, f);
+    if captured.is_empty() {
+        return;
+    }
+
+    let mut msg = format!(
+        "expected no errors, but {} error(s) notified:",
+        captured.len()
+    );
+    for c in captured {
+        msg.push_str(&format!("\n  {} ({}:{})", c.reason, c.file, c.line));
+    }
+    panic!("{msg}");
+}
+
+/// Checks whether error notification is degraded.
+///
+/// If the mutex of the error handlers is poisoned, error notification is degraded: the error is
+/// printed to stderr only once, and after that, notifications are silently skipped.
+///
+/// # Returns
+/// `true` if error notification is degraded, otherwise `false`.
+pub fn notify_is_degraded() -> bool {
+    degrade::is_degraded(&degrade::DEGRADED)
+}
+
+pub(crate) fn notify_err(err: &Err) -> Result<(), ErrHandlingError> {
+    capture::capture_err(err);
+
+    if degrade::is_degraded(&degrade::DEGRADED) {
+        return Ok(());
+    }
+    // When no handler was registered, there is nothing to notify once the handlers are fixed.
+    // The sink is checked after the handlers are found fixed, since it cannot be set after that.
+    if !has_handlers::has_handlers(&has_handlers::HAS_HANDLERS)
+        && is_fixed()
+        && NOTIFY_SINK.get().is_none()
+    {
+        return Ok(());
+    }
+    degrade::check_poisoned(&degrade::DEGRADED, dispatch_err(err))
+}
+
+fn dispatch_err(err: &Err) -> Result<(), ErrHandlingError> {
+    if !sampling::is_sampled(&sampling::SAMPLING, err.reason_any().type_id()) {
+        return Ok(());
+    }
+
+    #[cfg(feature = "metrics")]
+    crate::metrics::count_notification_dispatched();
+
     let tm = Utc::now();
-    let err = sync::Arc::new(err);
+
+    if let Some(sink) = NOTIFY_SINK.get() {
+        // The handlers are fixed as well as in the built-in dispatch, so that the sink cannot be
+        // replaced after an error has been notified.
+        fix_err_handlers()?;
+        if panic::catch_unwind(panic::AssertUnwindSafe(|| sink(err, tm))).is_err() {
+            #[cfg(feature = "metrics")]
+            crate::metrics::count_handler_panic();
+            eprintln!("ERROR(errs): handler panicked");
+        }
+        return Ok(());
+    }
 
     #[cfg(feature = "notify")]
-    let result_std = std_handler::handle_err(&std_handler::HANDLERS, sync::Arc::clone(&err), tm);
+    let result_std = std_handler::handle_err(&std_handler::HANDLERS, err, tm);
 
     #[cfg(feature = "notify-tokio")]
-    let result_tokio =
-        tokio_handler::handle_err(&tokio_handler::HANDLERS, sync::Arc::clone(&err), tm);
+    let result_tokio = tokio_handler::handle_err(
+        &tokio_handler::HANDLERS,
+        &tokio_handler::IN_FLIGHTS,
+        err,
+        tm,
+    );
 
     #[cfg(feature = "notify")]
     result_std?;
@@ -0,0 +1,167 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::{ErrHandlingError, ErrRecord};
+use crate::Err;
+
+use chrono::{DateTime, Utc};
+
+use std::{collections::VecDeque, sync::Mutex, sync::MutexGuard};
+
+pub(crate) struct RingBuffer {
+    is_registered: bool,
+    capacity: usize,
+    records: VecDeque<ErrRecord>,
+}
+
+pub(crate) type Ring = Mutex<RingBuffer>;
+
+pub(crate) static RING: Ring = Mutex::new(RingBuffer::new());
+
+impl RingBuffer {
+    pub(crate) const fn new() -> Self {
+        Self {
+            is_registered: false,
+            capacity: 0,
+            records: VecDeque::new(),
+        }
+    }
+}
+
+fn lock(ring: &Ring) -> MutexGuard<'_, RingBuffer> {
+    match ring.lock() {
+        Ok(rb) => rb,
+        Err(e) => e.into_inner(),
+    }
+}
+
+// Sets the capacity of the ring buffer, registering the handler which pushes records into it
+// with the given function only at the first time.
+pub(crate) fn enable<F>(ring: &Ring, capacity: usize, register: F) -> Result<(), ErrHandlingError>
+where
+    F: FnOnce() -> Result<(), ErrHandlingError>,
+{
+    let mut rb = lock(ring);
+    if !rb.is_registered {
+        register()?;
+        rb.is_registered = true;
+    }
+    rb.capacity = capacity;
+    while rb.records.len() > capacity {
+        rb.records.pop_front();
+    }
+    Ok(())
+}
+
+pub(crate) fn push(ring: &Ring, err: &Err, tm: DateTime<Utc>) {
+    let mut rb = lock(ring);
+    if rb.capacity == 0 {
+        return;
+    }
+    if rb.records.len() >= rb.capacity {
+        rb.records.pop_front();
+    }
+    rb.records.push_back(ErrRecord {
+        file: err.file(),
+        line: err.line(),
+        reason: err.reason_debug_string(),
+        timestamp: tm,
+    });
+}
+
+pub(crate) fn drain(ring: &Ring) -> Vec<ErrRecord> {
+    lock(ring).records.drain(..).collect()
+}
+
+#[cfg(test)]
+mod tests_of_ring_buffer {
+    use super::*;
+    use crate::notify::ErrHandlingErrorKind;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething(u32),
+    }
+
+    fn reasons(records: &[ErrRecord]) -> Vec<&str> {
+        records.iter().map(|r| r.reason.as_str()).collect()
+    }
+
+    #[test]
+    fn register_only_at_first_time() {
+        let ring = Ring::new(RingBuffer::new());
+        let mut count = 0;
+        assert!(enable(&ring, 2, || {
+            count += 1;
+            Ok(())
+        })
+        .is_ok());
+        assert!(enable(&ring, 3, || {
+            count += 1;
+            Ok(())
+        })
+        .is_ok());
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn fail_to_register() {
+        let ring = Ring::new(RingBuffer::new());
+        let result = enable(&ring, 2, || {
+            Err(ErrHandlingError::new(ErrHandlingErrorKind::AlreadyFixed))
+        });
+        assert!(result.is_err());
+
+        push(
+            &ring,
+            &Err::new_silent(Reasons::FailToDoSomething(0)),
+            Utc::now(),
+        );
+        assert!(drain(&ring).is_empty());
+    }
+
+    #[test]
+    fn retain_only_most_recent_records() {
+        let ring = Ring::new(RingBuffer::new());
+        assert!(enable(&ring, 3, || Ok(())).is_ok());
+
+        for i in 0..5 {
+            push(
+                &ring,
+                &Err::new_silent(Reasons::FailToDoSomething(i)),
+                Utc::now(),
+            );
+        }
+
+        let records = drain(&ring);
+        assert_eq!(
+            reasons(&records),
+            vec![
+                "FailToDoSomething(2)",
+                "FailToDoSomething(3)",
+                "FailToDoSomething(4)"
+            ]
+        );
+        assert!(drain(&ring).is_empty());
+    }
+
+    #[test]
+    fn shrink_capacity() {
+        let ring = Ring::new(RingBuffer::new());
+        assert!(enable(&ring, 3, || Ok(())).is_ok());
+
+        for i in 0..3 {
+            push(
+                &ring,
+                &Err::new_silent(Reasons::FailToDoSomething(i)),
+                Utc::now(),
+            );
+        }
+        assert!(enable(&ring, 1, || Ok(())).is_ok());
+
+        let records = drain(&ring);
+        assert_eq!(reasons(&records), vec!["FailToDoSomething(2)"]);
+    }
+}
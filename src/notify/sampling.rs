@@ -0,0 +1,88 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::any;
+use std::collections::HashMap;
+use std::sync::{atomic, LazyLock, Mutex};
+
+pub(crate) struct Sampling {
+    one_in_n: atomic::AtomicU32,
+    counters: Mutex<HashMap<any::TypeId, u64>>,
+}
+
+impl Sampling {
+    fn new() -> Self {
+        Self {
+            one_in_n: atomic::AtomicU32::new(1),
+            counters: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+pub(crate) static SAMPLING: LazyLock<Sampling> = LazyLock::new(Sampling::new);
+
+pub(crate) fn set_sampling(sampling: &Sampling, one_in_n: u32) {
+    sampling
+        .one_in_n
+        .store(one_in_n.max(1), atomic::Ordering::Relaxed);
+}
+
+// Counts up the errors per reason type, and returns true for the first one and then every Nth
+// one.
+pub(crate) fn is_sampled(sampling: &Sampling, type_id: any::TypeId) -> bool {
+    let n = sampling.one_in_n.load(atomic::Ordering::Relaxed);
+    if n <= 1 {
+        return true;
+    }
+
+    let mut counters = match sampling.counters.lock() {
+        Ok(counters) => counters,
+        Err(e) => e.into_inner(),
+    };
+    let count = counters.entry(type_id).or_insert(0);
+    let is_sampled = *count % u64::from(n) == 0;
+    *count = count.wrapping_add(1);
+    is_sampled
+}
+
+#[cfg(test)]
+mod tests_of_sampling {
+    use super::*;
+
+    struct Reason0;
+    struct Reason1;
+
+    #[test]
+    fn notify_every_time_by_default() {
+        let sampling = Sampling::new();
+        for _ in 0..10 {
+            assert!(is_sampled(&sampling, any::TypeId::of::<Reason0>()));
+        }
+    }
+
+    #[test]
+    fn notify_every_nth_per_reason_type() {
+        let sampling = Sampling::new();
+        set_sampling(&sampling, 5);
+
+        let sampled: Vec<bool> = (0..10)
+            .map(|_| is_sampled(&sampling, any::TypeId::of::<Reason0>()))
+            .collect();
+        assert_eq!(
+            sampled,
+            vec![true, false, false, false, false, true, false, false, false, false]
+        );
+
+        assert!(is_sampled(&sampling, any::TypeId::of::<Reason1>()));
+        assert!(!is_sampled(&sampling, any::TypeId::of::<Reason1>()));
+    }
+
+    #[test]
+    fn zero_is_treated_as_one() {
+        let sampling = Sampling::new();
+        set_sampling(&sampling, 0);
+        assert!(is_sampled(&sampling, any::TypeId::of::<Reason0>()));
+        assert!(is_sampled(&sampling, any::TypeId::of::<Reason0>()));
+    }
+}
@@ -2,99 +2,469 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
-use super::{ErrHandlingError, ErrHandlingErrorKind};
+use super::{group, has_handlers, to_err_handling_error, ErrHandlingError, ErrHandlingErrorKind};
 use crate::Err;
 
 use chrono::{DateTime, Utc};
 use setup_read_cleanup::{graceful::GracefulPhasedCellSync, PhasedErrorKind};
 
-use std::{sync::Arc, thread};
+use std::{
+    cell::OnceCell, fmt, panic, sync::atomic, sync::mpsc, sync::Arc, sync::OnceLock, thread,
+    time::Duration,
+};
 
 type SyncBoxedFn = Box<dyn Fn(&Err, DateTime<Utc>) + Send + Sync + 'static>;
+type SyncReportingBoxedFn = Box<dyn Fn(&Err, DateTime<Utc>) -> bool + Send + Sync + 'static>;
 type AsyncArcFn = Arc<dyn Fn(&Err, DateTime<Utc>) + Send + Sync + 'static>;
+type SyncBatchBoxedFn = Box<dyn Fn(&[&Err], DateTime<Utc>) + Send + Sync + 'static>;
+type ArcSyncBoxedFn = Box<dyn Fn(Arc<Err>, DateTime<Utc>) + Send + Sync + 'static>;
 
-pub(crate) static HANDLERS: GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)> =
-    GracefulPhasedCellSync::new((Vec::new(), Vec::new()));
+pub(crate) struct Handlers {
+    sync_vec: Vec<(i32, SyncReportingBoxedFn)>,
+    async_vec: Vec<AsyncArcFn>,
+    sequential_async_vec: Vec<SyncBoxedFn>,
+    sync_batch_vec: Vec<SyncBatchBoxedFn>,
+    unhandled_vec: Vec<SyncBoxedFn>,
+    sync_timeout_vec: Vec<TimeoutHandler>,
+    arc_sync_vec: Vec<ArcSyncBoxedFn>,
+}
+
+impl Handlers {
+    pub(crate) const fn new() -> Self {
+        Self {
+            sync_vec: Vec::new(),
+            async_vec: Vec::new(),
+            sequential_async_vec: Vec::new(),
+            sync_batch_vec: Vec::new(),
+            unhandled_vec: Vec::new(),
+            sync_timeout_vec: Vec::new(),
+            arc_sync_vec: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push_sync<F>(&mut self, handler: F)
+    where
+        F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+    {
+        self.sync_vec.push((
+            0,
+            Box::new(move |err, tm| {
+                handler(err, tm);
+                false
+            }),
+        ));
+    }
+
+    pub(crate) fn push_async<F>(&mut self, handler: F)
+    where
+        F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+    {
+        self.async_vec.push(Arc::new(handler));
+    }
+}
+
+pub(crate) static HANDLERS: GracefulPhasedCellSync<Handlers> =
+    GracefulPhasedCellSync::new(Handlers::new());
+
+pub(crate) fn add_handlers(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    added: Handlers,
+) -> Result<(), ErrHandlingError> {
+    match handlers.lock() {
+        Ok(mut hs) => {
+            hs.sync_vec.extend(added.sync_vec);
+            hs.async_vec.extend(added.async_vec);
+            hs.sequential_async_vec.extend(added.sequential_async_vec);
+            hs.sync_batch_vec.extend(added.sync_batch_vec);
+            hs.unhandled_vec.extend(added.unhandled_vec);
+            hs.sync_timeout_vec.extend(added.sync_timeout_vec);
+            hs.arc_sync_vec.extend(added.arc_sync_vec);
+            Ok(())
+        }
+        Err(e) => to_err_handling_error(e),
+    }
+}
 
 pub(crate) fn add_sync_handler<F>(
-    handlers: &GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)>,
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    add_sync_handler_with_priority(handlers, 0, handler)
+}
+
+pub(crate) fn add_typed_sync_handler<R, F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    R: fmt::Debug + Send + Sync + 'static,
+    F: Fn(&R, &Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    add_sync_handler(handlers, move |err, tm| {
+        if let Ok(r) = err.reason::<R>() {
+            handler(r, err, tm);
+        }
+    })
+}
+
+pub(crate) fn add_sync_handler_in_group<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    enabled: Arc<atomic::AtomicBool>,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    add_sync_handler(handlers, move |err, tm| {
+        if group::is_enabled(&enabled) {
+            handler(err, tm);
+        }
+    })
+}
+
+pub(crate) fn add_sync_handler_with_priority<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    priority: i32,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    // A handler which does not report whether it handled an error is regarded as not having
+    // handled it.
+    add_sync_reporting_handler_with_priority(handlers, priority, move |err, tm| {
+        handler(err, tm);
+        false
+    })
+}
+
+pub(crate) fn add_sync_reporting_handler<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) -> bool + Send + Sync + 'static,
+{
+    add_sync_reporting_handler_with_priority(handlers, 0, handler)
+}
+
+fn add_sync_reporting_handler_with_priority<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    priority: i32,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) -> bool + Send + Sync + 'static,
+{
+    match handlers.lock() {
+        Ok(mut hs) => {
+            hs.sync_vec.push((priority, Box::new(handler)));
+            Ok(())
+        }
+        Err(e) => to_err_handling_error(e),
+    }
+}
+
+pub(crate) fn add_unhandled_handler<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
     handler: F,
 ) -> Result<(), ErrHandlingError>
 where
     F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
 {
     match handlers.lock() {
-        Ok(mut vv) => {
-            vv.0.push(Box::new(handler));
+        Ok(mut hs) => {
+            hs.unhandled_vec.push(Box::new(handler));
             Ok(())
         }
-        Err(e) => match e.kind() {
-            PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidInternalState,
-            )),
-            PhasedErrorKind::InternalDataMutexIsPoisoned => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::StdMutexIsPoisoned,
-            )),
-            _ => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidCallTiming,
-            )),
-        },
+        Err(e) => to_err_handling_error(e),
+    }
+}
+
+pub(crate) fn add_sync_timeout_handler<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    timeout: Duration,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    match handlers.lock() {
+        Ok(mut hs) => {
+            hs.sync_timeout_vec
+                .push(TimeoutHandler::new(timeout, handler));
+            Ok(())
+        }
+        Err(e) => to_err_handling_error(e),
+    }
+}
+
+pub(crate) fn add_arc_sync_handler<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(Arc<Err>, DateTime<Utc>) + Send + Sync + 'static,
+{
+    match handlers.lock() {
+        Ok(mut hs) => {
+            hs.arc_sync_vec.push(Box::new(handler));
+            Ok(())
+        }
+        Err(e) => to_err_handling_error(e),
     }
 }
 
 pub(crate) fn add_async_handler<F>(
-    handlers: &GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)>,
+    handlers: &GracefulPhasedCellSync<Handlers>,
     handler: F,
 ) -> Result<(), ErrHandlingError>
 where
     F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
 {
     match handlers.lock() {
-        Ok(mut vv) => {
-            vv.1.push(Arc::new(handler));
+        Ok(mut hs) => {
+            hs.async_vec.push(Arc::new(handler));
             Ok(())
         }
-        Err(e) => match e.kind() {
-            PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidInternalState,
-            )),
-            PhasedErrorKind::InternalDataMutexIsPoisoned => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::StdMutexIsPoisoned,
-            )),
-            _ => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidCallTiming,
-            )),
-        },
+        Err(e) => to_err_handling_error(e),
+    }
+}
+
+pub(crate) fn add_sequential_async_handler<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+{
+    match handlers.lock() {
+        Ok(mut hs) => {
+            hs.sequential_async_vec.push(Box::new(handler));
+            Ok(())
+        }
+        Err(e) => to_err_handling_error(e),
+    }
+}
+
+pub(crate) fn add_sync_batch_handler<F>(
+    handlers: &GracefulPhasedCellSync<Handlers>,
+    handler: F,
+) -> Result<(), ErrHandlingError>
+where
+    F: Fn(&[&Err], DateTime<Utc>) + Send + Sync + 'static,
+{
+    match handlers.lock() {
+        Ok(mut hs) => {
+            hs.sync_batch_vec.push(Box::new(handler));
+            Ok(())
+        }
+        Err(e) => to_err_handling_error(e),
     }
 }
 
 pub(crate) fn fix_handlers(
-    handlers: &GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)>,
+    handlers: &GracefulPhasedCellSync<Handlers>,
 ) -> Result<(), ErrHandlingError> {
-    if let Err(e) = handlers.transition_to_read(register_handlers_by_inventory) {
-        match e.kind() {
-            PhasedErrorKind::PhaseIsAlreadyRead => Ok(()),
-            PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidInternalState,
-            )),
-            PhasedErrorKind::InternalDataMutexIsPoisoned => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::StdMutexIsPoisoned,
-            )),
-            // PhasedErrorKind::FailToRunClosureDuringTransitionToRead => {}, // impossible case
-            _ => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidCallTiming,
-            )),
+    handlers
+        .transition_to_read(register_handlers_by_inventory)
+        .or_else(to_err_handling_error)
+}
+
+pub(crate) fn handle_err(
+    handlers: &'static GracefulPhasedCellSync<Handlers>,
+    err: &Err,
+    tm: DateTime<Utc>,
+) -> Result<(), ErrHandlingError> {
+    let hs = read_handlers(handlers)?;
+
+    // The handlers which may run or keep the error after it is returned receive a copy of it
+    // sharing the reason, which is created at most once and only if such a handler is registered.
+    let copy = OnceCell::new();
+    let shared_copy = || Arc::clone(copy.get_or_init(|| Arc::new(err.to_notified())));
+
+    if !hs.async_vec.is_empty() {
+        let err_clone = shared_copy();
+        #[cfg(not(feature = "notify-tokio"))]
+        {
+            thread::spawn(move || {
+                for handle in hs.async_vec.iter() {
+                    let e = Arc::clone(&err_clone);
+                    let h = Arc::clone(handle);
+                    thread::spawn(move || h(&e, tm));
+                }
+            });
         }
-    } else {
-        Ok(())
+        #[cfg(feature = "notify-tokio")]
+        {
+            if let Ok(rt_handle) = tokio::runtime::Handle::try_current() {
+                thread::spawn(move || {
+                    for handle in hs.async_vec.iter() {
+                        let e = Arc::clone(&err_clone);
+                        let h = Arc::clone(handle);
+                        rt_handle.spawn_blocking(move || h(&e, tm));
+                    }
+                });
+            } else {
+                thread::spawn(move || {
+                    for handle in hs.async_vec.iter() {
+                        let e = Arc::clone(&err_clone);
+                        let h = Arc::clone(handle);
+                        thread::spawn(move || h(&e, tm));
+                    }
+                });
+            }
+        }
+    }
+
+    // The sequential handlers run one after another in the order of registration on a single
+    // thread, separately from the async handlers above.
+    if !hs.sequential_async_vec.is_empty() {
+        let e = shared_copy();
+        thread::spawn(move || {
+            for handle in hs.sequential_async_vec.iter() {
+                call_guarded(|| handle(&e, tm));
+            }
+        });
+    }
+
+    run_sync_handlers(hs, err, tm);
+    for handle in hs.arc_sync_vec.iter() {
+        call_guarded(|| handle(shared_copy(), tm));
+    }
+    for handler in hs.sync_timeout_vec.iter() {
+        handler.run(shared_copy, tm);
+    }
+    for handle in hs.sync_batch_vec.iter() {
+        call_guarded(|| handle(&[err], tm));
     }
+    Ok(())
 }
 
-pub(crate) fn handle_err(
-    handlers: &'static GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)>,
-    err: Arc<Err>,
+pub(crate) fn handle_err_batch(
+    handlers: &'static GracefulPhasedCellSync<Handlers>,
+    errs: &[&Err],
     tm: DateTime<Utc>,
 ) -> Result<(), ErrHandlingError> {
+    let hs = read_handlers(handlers)?;
+
+    for err in errs {
+        run_sync_handlers(hs, err, tm);
+    }
+    for handle in hs.sync_batch_vec.iter() {
+        call_guarded(|| handle(errs, tm));
+    }
+    Ok(())
+}
+
+// Runs the sync handlers, and then runs the unhandled handlers only if none of the sync
+// handlers reported that it handled the error.
+fn run_sync_handlers(hs: &Handlers, err: &Err, tm: DateTime<Utc>) {
+    let mut is_handled = false;
+    for (_, handle) in hs.sync_vec.iter() {
+        if call_guarded(|| handle(err, tm)).unwrap_or(false) {
+            is_handled = true;
+        }
+    }
+    if !is_handled {
+        for handle in hs.unhandled_vec.iter() {
+            call_guarded(|| handle(err, tm));
+        }
+    }
+}
+
+type TimeoutJob = (Arc<Err>, DateTime<Utc>, mpsc::Sender<()>);
+
+// A sync handler with a timeout, which runs on its own long-lived worker thread. The worker is
+// started at the first notification, and receives the errors through a queue of one slot.
+struct TimeoutHandler {
+    timeout: Duration,
+    handle: AsyncArcFn,
+    is_running: Arc<atomic::AtomicBool>,
+    worker: OnceLock<mpsc::SyncSender<TimeoutJob>>,
+}
+
+impl TimeoutHandler {
+    fn new<F>(timeout: Duration, handler: F) -> Self
+    where
+        F: Fn(&Err, DateTime<Utc>) + Send + Sync + 'static,
+    {
+        Self {
+            timeout,
+            handle: Arc::new(handler),
+            is_running: Arc::new(atomic::AtomicBool::new(false)),
+            worker: OnceLock::new(),
+        }
+    }
+
+    // Runs the handler on the worker thread and waits for it until the timeout. If the handler
+    // does not return in time, it is left running on the worker thread and the notification
+    // moves on. While the handler is still running for a previous error, it is not run for the
+    // given error, so that a hung handler does not pile up errors or threads.
+    fn run<F: FnOnce() -> Arc<Err>>(&self, get_err: F, tm: DateTime<Utc>) {
+        if self
+            .is_running
+            .compare_exchange(
+                false,
+                true,
+                atomic::Ordering::AcqRel,
+                atomic::Ordering::Acquire,
+            )
+            .is_err()
+        {
+            eprintln!("ERROR(errs): handler is still running, skipped");
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        if self.worker().try_send((get_err(), tm, tx)).is_err() {
+            self.is_running.store(false, atomic::Ordering::Release);
+            eprintln!("ERROR(errs): handler is not available, skipped");
+            return;
+        }
+        if let Err(mpsc::RecvTimeoutError::Timeout) = rx.recv_timeout(self.timeout) {
+            eprintln!("ERROR(errs): handler timed out");
+        }
+    }
+
+    fn worker(&self) -> &mpsc::SyncSender<TimeoutJob> {
+        self.worker.get_or_init(|| {
+            let (tx, rx) = mpsc::sync_channel::<TimeoutJob>(1);
+            let handle = Arc::clone(&self.handle);
+            let is_running = Arc::clone(&self.is_running);
+            thread::spawn(move || {
+                for (err, tm, done) in rx {
+                    call_guarded(|| handle(&err, tm));
+                    drop(err);
+                    is_running.store(false, atomic::Ordering::Release);
+                    let _ = done.send(());
+                }
+            });
+            tx
+        })
+    }
+}
+
+// Runs a sync handler and catches its panic so that the panic does not propagate to the
+// creation site of an `Err` and the remaining handlers are still executed.
+fn call_guarded<T, F: FnOnce() -> T>(f: F) -> Option<T> {
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(v) => Some(v),
+        Err(_) => {
+            #[cfg(feature = "metrics")]
+            crate::metrics::count_handler_panic();
+            eprintln!("ERROR(errs): handler panicked");
+            None
+        }
+    }
+}
+
+fn read_handlers(
+    handlers: &'static GracefulPhasedCellSync<Handlers>,
+) -> Result<&'static Handlers, ErrHandlingError> {
     let mut result = handlers.read_relaxed();
     if result.is_err() {
         result = match handlers.transition_to_read(register_handlers_by_inventory) {
@@ -102,76 +472,22 @@ pub(crate) fn handle_err(
             Err(e) => match e.kind() {
                 PhasedErrorKind::PhaseIsAlreadyRead => handlers.read_relaxed(),
                 PhasedErrorKind::DuringTransitionToRead => handlers.read(),
-                PhasedErrorKind::InternalDataUnavailable => {
-                    return Err(ErrHandlingError::new(
-                        ErrHandlingErrorKind::InvalidInternalState,
-                    ));
-                }
-                PhasedErrorKind::InternalDataMutexIsPoisoned => {
-                    return Err(ErrHandlingError::new(
-                        ErrHandlingErrorKind::StdMutexIsPoisoned,
-                    ));
-                }
-                // PhasedErrorKind::FailToRunClosureDuringTransitionToRead => {}, // impossible case
                 _ => {
-                    return Err(ErrHandlingError::new(
-                        ErrHandlingErrorKind::InvalidCallTiming,
-                    ));
+                    to_err_handling_error(e)?;
+                    handlers.read_relaxed()
                 }
             },
         };
     }
 
     match result {
-        Ok(vv) => {
-            let err_clone = Arc::clone(&err);
-            #[cfg(not(feature = "notify-tokio"))]
-            {
-                thread::spawn(move || {
-                    for handle in vv.1.iter() {
-                        let e = Arc::clone(&err_clone);
-                        let h = Arc::clone(handle);
-                        thread::spawn(move || h(&e, tm));
-                    }
-                });
-            }
-            #[cfg(feature = "notify-tokio")]
-            {
-                if let Ok(rt_handle) = tokio::runtime::Handle::try_current() {
-                    thread::spawn(move || {
-                        for handle in vv.1.iter() {
-                            let e = Arc::clone(&err_clone);
-                            let h = Arc::clone(handle);
-                            rt_handle.spawn_blocking(move || h(&e, tm));
-                        }
-                    });
-                } else {
-                    thread::spawn(move || {
-                        for handle in vv.1.iter() {
-                            let e = Arc::clone(&err_clone);
-                            let h = Arc::clone(handle);
-                            thread::spawn(move || h(&e, tm));
-                        }
-                    });
-                }
-            }
-
-            for handle in vv.0.iter() {
-                handle(&err, tm);
-            }
-            Ok(())
-        }
-        Err(e) => match e.kind() {
-            PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidInternalState,
-            )),
-            PhasedErrorKind::GracefulWaitMutexIsPoisoned => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::StdMutexIsPoisoned,
-            )),
-            _ => Err(ErrHandlingError::new(
+        Ok(hs) => Ok(hs),
+        Err(e) => {
+            to_err_handling_error(e)?;
+            Err(ErrHandlingError::new(
                 ErrHandlingErrorKind::InvalidCallTiming,
-            )),
-        },
+            ))
+        }
     }
 }
 
@@ -272,20 +588,33 @@ macro_rules! add_async_err_handler {
     };
 }
 
-fn register_handlers_by_inventory(
-    vv: &mut (Vec<SyncBoxedFn>, Vec<AsyncArcFn>),
-) -> Result<(), ErrHandlingError> {
-    let vec: Vec<SyncBoxedFn> = inventory::iter::<SyncHandlerRegistration>
+fn register_handlers_by_inventory(hs: &mut Handlers) -> Result<(), ErrHandlingError> {
+    let vec: Vec<(i32, SyncReportingBoxedFn)> = inventory::iter::<SyncHandlerRegistration>
         .into_iter()
-        .map(|reg| Box::new(reg.handler) as SyncBoxedFn)
+        .map(|reg| {
+            let handler = reg.handler;
+            let boxed: SyncReportingBoxedFn = Box::new(move |err, tm| {
+                handler(err, tm);
+                false
+            });
+            (0, boxed)
+        })
         .collect();
-    vv.0.splice(0..0, vec);
+    if !vec.is_empty() {
+        has_handlers::mark(&has_handlers::HAS_HANDLERS);
+    }
+    hs.sync_vec.splice(0..0, vec);
+    // Stable sort, so handlers with the same priority keep their registration order.
+    hs.sync_vec.sort_by_key(|(priority, _)| *priority);
 
     let vec: Vec<AsyncArcFn> = inventory::iter::<AsyncHandlerRegistration>
         .into_iter()
         .map(|reg| Arc::new(reg.handler) as AsyncArcFn)
         .collect();
-    vv.1.splice(0..0, vec);
+    if !vec.is_empty() {
+        has_handlers::mark(&has_handlers::HAS_HANDLERS);
+    }
+    hs.async_vec.splice(0..0, vec);
 
     Ok(())
 }
@@ -303,8 +632,8 @@ mod tests_of_notify {
         use super::*;
         use std::sync::{LazyLock, Mutex};
 
-        static HANDLERS: GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)> =
-            GracefulPhasedCellSync::new((Vec::new(), Vec::new()));
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
 
         static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
 
@@ -329,7 +658,7 @@ mod tests_of_notify {
             .is_err());
 
             let err = Err::new(Errors::FailToDoSomething);
-            assert!(handle_err(&HANDLERS, err.into(), Utc::now()).is_ok());
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
 
             #[cfg(unix)]
             {
@@ -348,12 +677,79 @@ mod tests_of_notify {
         }
     }
 
+    mod tests_of_typed_sync_err_handling {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[derive(Debug)]
+        enum DbErr {
+            ConnectionLost { host: String },
+        }
+
+        #[test]
+        fn handler_fires_only_for_specified_reason_type() {
+            assert!(
+                add_typed_sync_handler::<DbErr, _>(&HANDLERS, |r, err, _tm| {
+                    match r {
+                        DbErr::ConnectionLost { host } => {
+                            LOGGER.lock().unwrap().push(format!("db: {host} ({err})"));
+                        }
+                    }
+                })
+                .is_ok()
+            );
+            assert!(
+                add_typed_sync_handler::<Errors, _>(&HANDLERS, |r, _err, _tm| {
+                    LOGGER.lock().unwrap().push(format!("errors: {r:?}"));
+                })
+                .is_ok()
+            );
+            assert!(add_unhandled_handler(&HANDLERS, |err, _tm| {
+                LOGGER.lock().unwrap().push(format!("unhandled: {err}"));
+            })
+            .is_ok());
+
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e = add_typed_sync_handler::<DbErr, _>(&HANDLERS, |_r, _err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+
+            let err = Err::new(DbErr::ConnectionLost {
+                host: "db0".to_string(),
+            });
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            let err = Err::new("other reason");
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            let vec = LOGGER.lock().unwrap();
+            assert_eq!(
+                *vec,
+                vec![
+                    "db: db0 (ConnectionLost { host: \"db0\" })",
+                    "unhandled: ConnectionLost { host: \"db0\" }",
+                    "errors: FailToDoSomething",
+                    "unhandled: FailToDoSomething",
+                    "unhandled: \"other reason\"",
+                ]
+            );
+        }
+    }
+
     mod tests_of_async_err_handling {
         use super::*;
         use std::sync::{LazyLock, Mutex};
 
-        static HANDLERS: GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)> =
-            GracefulPhasedCellSync::new((Vec::new(), Vec::new()));
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
 
         static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
 
@@ -381,7 +777,7 @@ mod tests_of_notify {
             .is_err());
 
             let err = Err::new(Errors::FailToDoSomething);
-            assert!(handle_err(&HANDLERS, err.into(), Utc::now()).is_ok());
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
 
             {
                 let vec = LOGGER.lock().unwrap();
@@ -407,14 +803,56 @@ mod tests_of_notify {
         }
     }
 
+    mod tests_of_sequential_async_err_handling {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[test]
+        fn handlers_run_in_registration_order() {
+            assert!(add_sequential_async_handler(&HANDLERS, |err, _tm| {
+                thread::sleep(std::time::Duration::from_millis(50));
+                LOGGER.lock().unwrap().push(format!("1: {err}"));
+            })
+            .is_ok());
+            assert!(add_sequential_async_handler(&HANDLERS, |err, _tm| {
+                thread::sleep(std::time::Duration::from_millis(10));
+                LOGGER.lock().unwrap().push(format!("2: {err}"));
+            })
+            .is_ok());
+
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e = add_sequential_async_handler(&HANDLERS, |_err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            {
+                let vec = LOGGER.lock().unwrap();
+                assert_eq!(vec.len(), 0);
+            }
+
+            thread::sleep(std::time::Duration::from_millis(200));
+
+            let vec = LOGGER.lock().unwrap();
+            assert_eq!(*vec, vec!["1: FailToDoSomething", "2: FailToDoSomething"]);
+        }
+    }
+
     #[cfg(feature = "notify-tokio")]
     mod tests_of_async_err_handling_on_tokio {
         use super::*;
         use std::sync::{LazyLock, Mutex};
         use tokio::time::Duration;
 
-        static HANDLERS: GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)> =
-            GracefulPhasedCellSync::new((Vec::new(), Vec::new()));
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
 
         static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
 
@@ -442,7 +880,7 @@ mod tests_of_notify {
             .is_err());
 
             let err = Err::new(Errors::FailToDoSomething);
-            assert!(handle_err(&HANDLERS, Arc::new(err), Utc::now()).is_ok());
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
 
             {
                 let vec = LOGGER.lock().unwrap();
@@ -468,12 +906,112 @@ mod tests_of_notify {
         }
     }
 
+    mod tests_of_sync_batch_err_handling {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        enum Errors {
+            InvalidValue { name: String },
+        }
+
+        #[test]
+        fn add_and_fix_and_notify_batch() {
+            assert!(add_sync_handler(&HANDLERS, |err, _tm| {
+                LOGGER.lock().unwrap().push(format!("sync: {err}"));
+            })
+            .is_ok());
+            assert!(add_sync_batch_handler(&HANDLERS, |errs, _tm| {
+                let v: Vec<String> = errs.iter().map(|e| format!("{e}")).collect();
+                LOGGER
+                    .lock()
+                    .unwrap()
+                    .push(format!("batch: [{}]", v.join(", ")));
+            })
+            .is_ok());
+
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e = add_sync_batch_handler(&HANDLERS, |_errs, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+
+            let err0 = Err::new_silent(Errors::InvalidValue {
+                name: "foo".to_string(),
+            });
+            let err1 = Err::new_silent(Errors::InvalidValue {
+                name: "bar".to_string(),
+            });
+            assert!(handle_err_batch(&HANDLERS, &[&err0, &err1], Utc::now()).is_ok());
+
+            {
+                let vec = LOGGER.lock().unwrap();
+                assert_eq!(vec.len(), 3);
+                assert_eq!(vec[0], "sync: InvalidValue { name: \"foo\" }");
+                assert_eq!(vec[1], "sync: InvalidValue { name: \"bar\" }");
+                assert_eq!(
+                    vec[2],
+                    "batch: [InvalidValue { name: \"foo\" }, InvalidValue { name: \"bar\" }]"
+                );
+            }
+
+            let err2 = Err::new_silent(Errors::InvalidValue {
+                name: "baz".to_string(),
+            });
+            assert!(handle_err(&HANDLERS, &err2, Utc::now()).is_ok());
+
+            {
+                let vec = LOGGER.lock().unwrap();
+                assert_eq!(vec.len(), 5);
+                assert_eq!(vec[3], "sync: InvalidValue { name: \"baz\" }");
+                assert_eq!(vec[4], "batch: [InvalidValue { name: \"baz\" }]");
+            }
+        }
+
+        #[test]
+        fn notify_batch_fixes_handlers_implicitly() {
+            static HANDLERS: GracefulPhasedCellSync<Handlers> =
+                GracefulPhasedCellSync::new(Handlers::new());
+
+            let err = Err::new_silent(Errors::InvalidValue {
+                name: "foo".to_string(),
+            });
+            assert!(handle_err_batch(&HANDLERS, &[&err], Utc::now()).is_ok());
+
+            let e = add_sync_batch_handler(&HANDLERS, |_errs, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+        }
+    }
+
+    mod tests_of_already_fixed {
+        use super::*;
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        #[test]
+        fn add_after_fix_should_fail_with_already_fixed() {
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e = add_sync_handler(&HANDLERS, |_err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+
+            let e = add_async_handler(&HANDLERS, |_err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+        }
+    }
+
     mod tests_of_no_handlers {
         use super::*;
         use std::sync::{LazyLock, Mutex};
 
-        static HANDLERS: GracefulPhasedCellSync<(Vec<SyncBoxedFn>, Vec<AsyncArcFn>)> =
-            GracefulPhasedCellSync::new((Vec::new(), Vec::new()));
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
 
         static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
 
@@ -482,10 +1020,222 @@ mod tests_of_notify {
             assert!(fix_handlers(&HANDLERS).is_ok());
 
             let err = Err::new(Errors::FailToDoSomething);
-            let result = handle_err(&HANDLERS, Arc::new(err), Utc::now());
+            let result = handle_err(&HANDLERS, &err, Utc::now());
 
             assert!(result.is_ok());
             assert!(LOGGER.lock().unwrap().is_empty());
         }
     }
+
+    mod tests_of_sync_err_handling_with_priority {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[test]
+        fn handlers_run_in_ascending_order_of_priority() {
+            assert!(add_sync_handler_with_priority(&HANDLERS, 10, |_err, _tm| {
+                LOGGER.lock().unwrap().push("priority 10".to_string());
+            })
+            .is_ok());
+            assert!(add_sync_handler(&HANDLERS, |_err, _tm| {
+                LOGGER.lock().unwrap().push("default 1".to_string());
+            })
+            .is_ok());
+            assert!(add_sync_handler_with_priority(&HANDLERS, -5, |_err, _tm| {
+                LOGGER.lock().unwrap().push("priority -5".to_string());
+            })
+            .is_ok());
+            assert!(add_sync_handler_with_priority(&HANDLERS, 0, |_err, _tm| {
+                LOGGER.lock().unwrap().push("default 2".to_string());
+            })
+            .is_ok());
+
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e = add_sync_handler_with_priority(&HANDLERS, 1, |_err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            let vec = LOGGER.lock().unwrap();
+            assert_eq!(
+                *vec,
+                vec!["priority -5", "default 1", "default 2", "priority 10"]
+            );
+        }
+    }
+
+    mod tests_of_handler_panic {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[test]
+        fn panicking_handler_should_not_stop_other_handlers() {
+            assert!(add_sync_handler(&HANDLERS, |_err, _tm| {
+                panic!("handler panics");
+            })
+            .is_ok());
+            assert!(add_sync_handler(&HANDLERS, |_err, _tm| {
+                LOGGER.lock().unwrap().push("sync".to_string());
+            })
+            .is_ok());
+            assert!(add_sync_batch_handler(&HANDLERS, |_errs, _tm| {
+                panic!("batch handler panics");
+            })
+            .is_ok());
+            assert!(add_sync_batch_handler(&HANDLERS, |errs, _tm| {
+                LOGGER
+                    .lock()
+                    .unwrap()
+                    .push(format!("batch: {}", errs.len()));
+            })
+            .is_ok());
+
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            let err0 = Err::new_silent(Errors::FailToDoSomething);
+            let err1 = Err::new_silent(Errors::FailToDoSomething);
+            assert!(handle_err_batch(&HANDLERS, &[&err0, &err1], Utc::now()).is_ok());
+
+            let vec = LOGGER.lock().unwrap();
+            assert_eq!(*vec, vec!["sync", "batch: 1", "sync", "sync", "batch: 2"]);
+        }
+    }
+
+    mod tests_of_unhandled_err_handling {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[derive(Debug)]
+        enum DbErr {
+            ConnectionLost,
+        }
+
+        #[test]
+        fn unhandled_handler_runs_only_if_no_handler_handled() {
+            assert!(add_sync_reporting_handler(&HANDLERS, |err, _tm| {
+                match err.reason::<DbErr>() {
+                    Ok(r) => {
+                        LOGGER.lock().unwrap().push(format!("db: {r:?}"));
+                        true
+                    }
+                    Err(_) => false,
+                }
+            })
+            .is_ok());
+            assert!(add_sync_handler(&HANDLERS, |err, _tm| {
+                LOGGER.lock().unwrap().push(format!("log: {err}"));
+            })
+            .is_ok());
+            assert!(add_unhandled_handler(&HANDLERS, |err, _tm| {
+                LOGGER.lock().unwrap().push(format!("unhandled: {err}"));
+            })
+            .is_ok());
+
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e = add_unhandled_handler(&HANDLERS, |_err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+            let e = add_sync_reporting_handler(&HANDLERS, |_err, _tm| true).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+
+            let err = Err::new(DbErr::ConnectionLost);
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+
+            let err0 = Err::new_silent(DbErr::ConnectionLost);
+            let err1 = Err::new_silent(Errors::FailToDoSomething);
+            assert!(handle_err_batch(&HANDLERS, &[&err0, &err1], Utc::now()).is_ok());
+
+            let vec = LOGGER.lock().unwrap();
+            assert_eq!(
+                *vec,
+                vec![
+                    "db: ConnectionLost",
+                    "log: ConnectionLost",
+                    "log: FailToDoSomething",
+                    "unhandled: FailToDoSomething",
+                    "db: ConnectionLost",
+                    "log: ConnectionLost",
+                    "log: FailToDoSomething",
+                    "unhandled: FailToDoSomething",
+                ]
+            );
+        }
+    }
+
+    mod tests_of_sync_timeout_err_handling {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+        use std::time::Instant;
+
+        static HANDLERS: GracefulPhasedCellSync<Handlers> =
+            GracefulPhasedCellSync::new(Handlers::new());
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[test]
+        fn skip_handler_while_it_is_still_running() {
+            assert!(
+                add_sync_timeout_handler(&HANDLERS, Duration::from_millis(20), |err, _tm| {
+                    LOGGER.lock().unwrap().push(format!("start: {err}"));
+                    thread::sleep(Duration::from_millis(300));
+                    LOGGER.lock().unwrap().push(format!("end: {err}"));
+                })
+                .is_ok()
+            );
+
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e =
+                add_sync_timeout_handler(&HANDLERS, Duration::ZERO, |_err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+
+            let start = Instant::now();
+            for _ in 0..5 {
+                let err = Err::new(Errors::FailToDoSomething);
+                assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+            }
+            assert!(start.elapsed() < Duration::from_millis(200));
+
+            thread::sleep(Duration::from_millis(500));
+            {
+                let vec = LOGGER.lock().unwrap();
+                assert_eq!(
+                    *vec,
+                    vec!["start: FailToDoSomething", "end: FailToDoSomething"]
+                );
+            }
+
+            // The same worker runs the handler again after the previous call has returned.
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &err, Utc::now()).is_ok());
+            thread::sleep(Duration::from_millis(500));
+
+            let vec = LOGGER.lock().unwrap();
+            assert_eq!(vec.len(), 4);
+            assert_eq!(vec[3], "end: FailToDoSomething");
+        }
+    }
 }
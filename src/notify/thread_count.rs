@@ -0,0 +1,65 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::cell::Cell;
+
+thread_local! {
+    static THREAD_ERR_COUNT: Cell<u64> = const { Cell::new(0) };
+}
+
+pub(crate) fn count_up() {
+    THREAD_ERR_COUNT.with(|c| c.set(c.get().wrapping_add(1)));
+}
+
+pub(crate) fn get() -> u64 {
+    THREAD_ERR_COUNT.with(|c| c.get())
+}
+
+pub(crate) fn reset() {
+    THREAD_ERR_COUNT.with(|c| c.set(0));
+}
+
+#[cfg(test)]
+mod tests_of_thread_count {
+    use super::*;
+    use crate::Err;
+    use std::thread;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn each_thread_has_its_own_count() {
+        let create_errs = |n: usize| {
+            move || {
+                reset();
+                for _ in 0..n {
+                    let _err = Err::new_silent(Reasons::FailToDoSomething);
+                }
+                get()
+            }
+        };
+
+        let h0 = thread::spawn(create_errs(3));
+        let h1 = thread::spawn(create_errs(5));
+        assert_eq!(h0.join().unwrap(), 3);
+        assert_eq!(h1.join().unwrap(), 5);
+    }
+
+    #[test]
+    fn reset_count() {
+        thread::spawn(|| {
+            assert_eq!(get(), 0);
+            let _err = Err::new_silent(Reasons::FailToDoSomething);
+            let _err = Err::new_silent(Reasons::FailToDoSomething);
+            assert_eq!(get(), 2);
+            reset();
+            assert_eq!(get(), 0);
+        })
+        .join()
+        .unwrap();
+    }
+}
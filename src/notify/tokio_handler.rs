@@ -2,22 +2,63 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
-use super::{ErrHandlingError, ErrHandlingErrorKind};
+use super::{has_handlers, to_err_handling_error, ErrHandlingError, ErrHandlingErrorKind};
 use crate::Err;
 
 use chrono::{DateTime, Utc};
 use setup_read_cleanup::{graceful::GracefulPhasedCellSync, PhasedErrorKind};
 
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{future::Future, io, mem, pin::Pin, sync::atomic, sync::Arc, sync::Mutex, thread};
 
-type TokioAsyncFn =
+pub(crate) type TokioAsyncFn =
     Box<dyn Fn(Arc<Err>, DateTime<Utc>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
 
-type TokioAsyncRawFn = fn(Arc<Err>, DateTime<Utc>) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+#[doc(hidden)]
+pub type TokioAsyncRawFn = fn(Arc<Err>, DateTime<Utc>) -> Pin<Box<dyn Future<Output = ()> + Send>>;
 
 pub(crate) static HANDLERS: GracefulPhasedCellSync<Vec<TokioAsyncFn>> =
     GracefulPhasedCellSync::new(Vec::new());
 
+pub(crate) enum InFlight {
+    Task(tokio::task::JoinHandle<()>),
+    Thread(thread::JoinHandle<()>),
+}
+
+impl InFlight {
+    fn is_finished(&self) -> bool {
+        match self {
+            InFlight::Task(h) => h.is_finished(),
+            InFlight::Thread(h) => h.is_finished(),
+        }
+    }
+}
+
+pub(crate) static IN_FLIGHTS: Mutex<Vec<InFlight>> = Mutex::new(Vec::new());
+
+pub(crate) static JOIN_ERROR_LOGGING: atomic::AtomicBool = atomic::AtomicBool::new(true);
+
+pub(crate) fn set_join_error_logging(logging: &atomic::AtomicBool, enabled: bool) {
+    logging.store(enabled, atomic::Ordering::Relaxed);
+}
+
+fn log_join_error<W: io::Write>(
+    w: &mut W,
+    logging: &atomic::AtomicBool,
+    e: &tokio::task::JoinError,
+) {
+    if logging.load(atomic::Ordering::Relaxed) {
+        let _ = writeln!(w, "ERROR(errs): Fail to run tokio handler: {e:?}");
+    }
+}
+
+pub(crate) fn box_tokio_async_handler<F, Fut>(handler: F) -> TokioAsyncFn
+where
+    F: Fn(Arc<Err>, DateTime<Utc>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    Box::new(move |err, tm| Box::pin(handler(err, tm)))
+}
+
 pub(crate) fn add_tokio_async_handler<F, Fut>(
     handlers: &GracefulPhasedCellSync<Vec<TokioAsyncFn>>,
     handler: F,
@@ -26,50 +67,34 @@ where
     F: Fn(Arc<Err>, DateTime<Utc>) -> Fut + Send + Sync + 'static,
     Fut: Future<Output = ()> + Send + 'static,
 {
+    add_tokio_async_handlers(handlers, vec![box_tokio_async_handler(handler)])
+}
+
+pub(crate) fn add_tokio_async_handlers(
+    handlers: &GracefulPhasedCellSync<Vec<TokioAsyncFn>>,
+    added: Vec<TokioAsyncFn>,
+) -> Result<(), ErrHandlingError> {
     match handlers.lock() {
         Ok(mut v) => {
-            v.push(Box::new(move |err, tm| Box::pin(handler(err, tm))));
+            v.extend(added);
             Ok(())
         }
-        Err(e) => match e.kind() {
-            PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidInternalState,
-            )),
-            PhasedErrorKind::InternalDataMutexIsPoisoned => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::StdMutexIsPoisoned,
-            )),
-            _ => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidCallTiming,
-            )),
-        },
+        Err(e) => to_err_handling_error(e),
     }
 }
 
 pub(crate) fn fix_handlers(
     handlers: &GracefulPhasedCellSync<Vec<TokioAsyncFn>>,
 ) -> Result<(), ErrHandlingError> {
-    if let Err(e) = handlers.transition_to_read(register_handlers_by_inventory) {
-        match e.kind() {
-            PhasedErrorKind::PhaseIsAlreadyRead => Ok(()),
-            PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidInternalState,
-            )),
-            PhasedErrorKind::InternalDataMutexIsPoisoned => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::StdMutexIsPoisoned,
-            )),
-            // PhasedErrorKind::FailToRunClosureDuringTransitionToRead => {}, // impossible case
-            _ => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidCallTiming,
-            )),
-        }
-    } else {
-        Ok(())
-    }
+    handlers
+        .transition_to_read(register_handlers_by_inventory)
+        .or_else(to_err_handling_error)
 }
 
 pub(crate) fn handle_err(
     handlers: &'static GracefulPhasedCellSync<Vec<TokioAsyncFn>>,
-    err: Arc<Err>,
+    in_flights: &Mutex<Vec<InFlight>>,
+    err: &Err,
     tm: DateTime<Utc>,
 ) -> Result<(), ErrHandlingError> {
     let mut result = handlers.read_relaxed();
@@ -79,21 +104,9 @@ pub(crate) fn handle_err(
             Err(e) => match e.kind() {
                 PhasedErrorKind::PhaseIsAlreadyRead => handlers.read_relaxed(),
                 PhasedErrorKind::DuringTransitionToRead => handlers.read(),
-                PhasedErrorKind::InternalDataUnavailable => {
-                    return Err(ErrHandlingError::new(
-                        ErrHandlingErrorKind::InvalidInternalState,
-                    ));
-                }
-                PhasedErrorKind::InternalDataMutexIsPoisoned => {
-                    return Err(ErrHandlingError::new(
-                        ErrHandlingErrorKind::StdMutexIsPoisoned,
-                    ));
-                }
-                // PhasedErrorKind::FailToRunClosureDuringTransitionToRead => {}, // impossible case
                 _ => {
-                    return Err(ErrHandlingError::new(
-                        ErrHandlingErrorKind::InvalidCallTiming,
-                    ));
+                    to_err_handling_error(e)?;
+                    handlers.read_relaxed()
                 }
             },
         };
@@ -101,13 +114,21 @@ pub(crate) fn handle_err(
 
     match result {
         Ok(v) => {
+            if v.is_empty() {
+                return Ok(());
+            }
+            // The handlers run after the error is returned, so they receive a copy of it
+            // which shares the reason.
+            let err = Arc::new(err.to_notified());
             if let Ok(rt_handle) = tokio::runtime::Handle::try_current() {
+                let mut tasks = Vec::with_capacity(v.len());
                 for handle in v.iter() {
                     let e = Arc::clone(&err);
-                    rt_handle.spawn(handle(e, tm));
+                    tasks.push(InFlight::Task(rt_handle.spawn(handle(e, tm))));
                 }
+                push_in_flights(in_flights, tasks);
             } else {
-                std::thread::spawn(move || {
+                let thread_handle = thread::spawn(move || {
                     let rt = match tokio::runtime::Runtime::new() {
                         Ok(rt) => rt,
                         Err(e) => {
@@ -125,25 +146,52 @@ pub(crate) fn handle_err(
 
                         for rt_handle in rt_handles {
                             if let Err(e) = rt_handle.await {
-                                eprintln!("ERROR(errs): Fail to run tokio handler: {e:?}");
+                                log_join_error(&mut io::stderr(), &JOIN_ERROR_LOGGING, &e);
                             }
                         }
                     });
                 });
+                push_in_flights(in_flights, vec![InFlight::Thread(thread_handle)]);
             }
             Ok(())
         }
-        Err(e) => match e.kind() {
-            PhasedErrorKind::InternalDataUnavailable => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::InvalidInternalState,
-            )),
-            PhasedErrorKind::GracefulWaitMutexIsPoisoned => Err(ErrHandlingError::new(
-                ErrHandlingErrorKind::StdMutexIsPoisoned,
-            )),
-            _ => Err(ErrHandlingError::new(
+        Err(e) => {
+            to_err_handling_error(e)?;
+            Err(ErrHandlingError::new(
                 ErrHandlingErrorKind::InvalidCallTiming,
-            )),
-        },
+            ))
+        }
+    }
+}
+
+fn push_in_flights(in_flights: &Mutex<Vec<InFlight>>, mut new_in_flights: Vec<InFlight>) {
+    let mut vec = match in_flights.lock() {
+        Ok(vec) => vec,
+        Err(e) => e.into_inner(),
+    };
+    vec.retain(|f| !f.is_finished());
+    vec.append(&mut new_in_flights);
+}
+
+pub(crate) async fn flush(in_flights: &Mutex<Vec<InFlight>>) {
+    let vec = match in_flights.lock() {
+        Ok(mut vec) => mem::take(&mut *vec),
+        Err(e) => mem::take(&mut *e.into_inner()),
+    };
+
+    for f in vec {
+        match f {
+            InFlight::Task(h) => {
+                if let Err(e) = h.await {
+                    log_join_error(&mut io::stderr(), &JOIN_ERROR_LOGGING, &e);
+                }
+            }
+            InFlight::Thread(h) => {
+                if let Err(e) = tokio::task::spawn_blocking(move || h.join()).await {
+                    eprintln!("ERROR(errs): Fail to wait for tokio handlers: {e:?}");
+                }
+            }
+        }
     }
 }
 
@@ -216,25 +264,34 @@ inventory::collect!(TokioAsyncHandlerRegistration);
 #[macro_export]
 macro_rules! add_tokio_async_err_handler {
     (async | $err:tt , $tm:tt | $body:block ) => {
-        inventory::submit! {
-            $crate::TokioAsyncHandlerRegistration::new(|$err: std::sync::Arc<$crate::Err>, $tm: chrono::DateTime<chrono::Utc>| {
-                std::boxed::Box::pin(async move { $body })
-            })
-        }
+        const _: () = {
+            const HANDLER: $crate::TokioAsyncRawFn =
+                |$err: std::sync::Arc<$crate::Err>, $tm: chrono::DateTime<chrono::Utc>| {
+                    std::boxed::Box::pin(async move { $body })
+                };
+            inventory::submit! {
+                $crate::TokioAsyncHandlerRegistration::new(HANDLER)
+            }
+        };
     };
 
     (async | $err:tt : $errty:ty, $tm:tt : $tmty:ty | $body:block ) => {
-        inventory::submit! {
-            $crate::TokioAsyncHandlerRegistration::new(|$err: $errty, $tm: $tmty| {
-                std::boxed::Box::pin(async move { $body })
-            })
-        }
+        const _: () = {
+            const HANDLER: $crate::TokioAsyncRawFn =
+                |$err: $errty, $tm: $tmty| std::boxed::Box::pin(async move { $body });
+            inventory::submit! {
+                $crate::TokioAsyncHandlerRegistration::new(HANDLER)
+            }
+        };
     };
 
     ($handler:expr) => {
-        inventory::submit! {
-            $crate::TokioAsyncHandlerRegistration::new($handler)
-        }
+        const _: () = {
+            const HANDLER: $crate::TokioAsyncRawFn = $handler;
+            inventory::submit! {
+                $crate::TokioAsyncHandlerRegistration::new(HANDLER)
+            }
+        };
     };
 }
 
@@ -243,6 +300,9 @@ fn register_handlers_by_inventory(v: &mut Vec<TokioAsyncFn>) -> Result<(), ErrHa
         .into_iter()
         .map(|reg| Box::new(reg.handler) as TokioAsyncFn)
         .collect();
+    if !vec.is_empty() {
+        has_handlers::mark(&has_handlers::HAS_HANDLERS);
+    }
     v.splice(0..0, vec);
 
     Ok(())
@@ -315,7 +375,7 @@ mod tests_of_notify {
             //);
 
             let err = Err::new(Errors::FailToDoSomething);
-            assert!(handle_err(&HANDLERS, err.into(), Utc::now()).is_ok());
+            assert!(handle_err(&HANDLERS, &IN_FLIGHTS, &err, Utc::now()).is_ok());
 
             {
                 let vec = LOGGER.lock().unwrap();
@@ -399,7 +459,7 @@ mod tests_of_notify {
             //);
 
             let err = Err::new(Errors::FailToDoSomething);
-            assert!(handle_err(&HANDLERS, err.into(), Utc::now()).is_ok());
+            assert!(handle_err(&HANDLERS, &IN_FLIGHTS, &err, Utc::now()).is_ok());
 
             {
                 let vec = LOGGER.lock().unwrap();
@@ -424,4 +484,116 @@ mod tests_of_notify {
             }
         }
     }
+
+    mod tests_of_flush {
+        use super::*;
+        use std::sync::{LazyLock, Mutex};
+
+        static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+        #[tokio::test]
+        async fn flush_on_tokio_runtime() {
+            static HANDLERS: GracefulPhasedCellSync<Vec<TokioAsyncFn>> =
+                GracefulPhasedCellSync::new(Vec::new());
+            static IN_FLIGHTS: Mutex<Vec<InFlight>> = Mutex::new(Vec::new());
+
+            assert!(add_tokio_async_handler(&HANDLERS, async |err, _tm| {
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+                LOGGER.lock().unwrap().push(format!("runtime: {err}"));
+            })
+            .is_ok());
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &IN_FLIGHTS, &err, Utc::now()).is_ok());
+            assert_eq!(IN_FLIGHTS.lock().unwrap().len(), 1);
+
+            flush(&IN_FLIGHTS).await;
+
+            assert!(IN_FLIGHTS.lock().unwrap().is_empty());
+            assert!(LOGGER
+                .lock()
+                .unwrap()
+                .contains(&"runtime: FailToDoSomething".to_string()));
+        }
+
+        #[test]
+        fn flush_on_thread() {
+            static HANDLERS: GracefulPhasedCellSync<Vec<TokioAsyncFn>> =
+                GracefulPhasedCellSync::new(Vec::new());
+            static IN_FLIGHTS: Mutex<Vec<InFlight>> = Mutex::new(Vec::new());
+
+            assert!(add_tokio_async_handler(&HANDLERS, async |err, _tm| {
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+                LOGGER.lock().unwrap().push(format!("thread: {err}"));
+            })
+            .is_ok());
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let err = Err::new(Errors::FailToDoSomething);
+            assert!(handle_err(&HANDLERS, &IN_FLIGHTS, &err, Utc::now()).is_ok());
+            assert_eq!(IN_FLIGHTS.lock().unwrap().len(), 1);
+
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(flush(&IN_FLIGHTS));
+
+            assert!(IN_FLIGHTS.lock().unwrap().is_empty());
+            assert!(LOGGER
+                .lock()
+                .unwrap()
+                .contains(&"thread: FailToDoSomething".to_string()));
+        }
+    }
+
+    mod tests_of_join_error_logging {
+        use super::*;
+
+        async fn join_error_of_panicking_handler() -> tokio::task::JoinError {
+            let handler =
+                box_tokio_async_handler(async |_err: Arc<Err>, _tm| panic!("handler panicked"));
+            let err = Arc::new(Err::new_silent(Errors::FailToDoSomething));
+            tokio::spawn(handler(err, Utc::now())).await.unwrap_err()
+        }
+
+        #[tokio::test]
+        async fn log_join_error_by_default() {
+            let logging = atomic::AtomicBool::new(true);
+            let e = join_error_of_panicking_handler().await;
+
+            let mut buf = Vec::new();
+            log_join_error(&mut buf, &logging, &e);
+            let s = String::from_utf8(buf).unwrap();
+            assert!(s.starts_with("ERROR(errs): Fail to run tokio handler: "));
+        }
+
+        #[tokio::test]
+        async fn suppress_join_error_log() {
+            let logging = atomic::AtomicBool::new(true);
+            set_join_error_logging(&logging, false);
+            let e = join_error_of_panicking_handler().await;
+
+            let mut buf = Vec::new();
+            log_join_error(&mut buf, &logging, &e);
+            assert!(buf.is_empty());
+
+            set_join_error_logging(&logging, true);
+            log_join_error(&mut buf, &logging, &e);
+            assert!(!buf.is_empty());
+        }
+    }
+
+    mod tests_of_already_fixed {
+        use super::*;
+
+        static HANDLERS: GracefulPhasedCellSync<Vec<TokioAsyncFn>> =
+            GracefulPhasedCellSync::new(Vec::new());
+
+        #[test]
+        fn add_after_fix_should_fail_with_already_fixed() {
+            assert!(fix_handlers(&HANDLERS).is_ok());
+
+            let e = add_tokio_async_handler(&HANDLERS, async |_err, _tm| {}).unwrap_err();
+            assert_eq!(e.kind(), ErrHandlingErrorKind::AlreadyFixed);
+        }
+    }
 }
@@ -0,0 +1,80 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{Err, OptionExt, Result};
+
+use core::fmt;
+
+impl<T> OptionExt<T> for Option<T> {
+    #[track_caller]
+    fn ok_or_reason<R>(self, reason: R) -> Result<T>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+    {
+        match self {
+            Some(v) => Ok(v),
+            None => Err(Err::new(reason)),
+        }
+    }
+
+    #[track_caller]
+    fn ok_or_else_reason<R, F>(self, f: F) -> Result<T>
+    where
+        R: fmt::Debug + Send + Sync + 'static,
+        F: FnOnce() -> R,
+    {
+        match self {
+            Some(v) => Ok(v),
+            None => Err(Err::new(f())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_of_option_ext {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Reasons {
+        NotFound { id: u32 },
+    }
+
+    #[test]
+    fn ok_or_reason_for_some() {
+        let result = Some(123).ok_or_reason(Reasons::NotFound { id: 1 });
+        assert_eq!(result.unwrap(), 123);
+    }
+
+    #[test]
+    fn ok_or_reason_for_none() {
+        let line = line!() + 1;
+        let result = None::<i32>.ok_or_reason(Reasons::NotFound { id: 1 });
+        let err = result.unwrap_err();
+        match err.reason::<Reasons>() {
+            Ok(Reasons::NotFound { id }) => assert_eq!(*id, 1),
+            Err(_) => panic!(),
+        }
+        assert_eq!(err.file(), file!());
+        assert_eq!(err.line(), line);
+    }
+
+    #[test]
+    fn ok_or_else_reason_for_some() {
+        let result = Some(123).ok_or_else_reason(|| -> Reasons { panic!() });
+        assert_eq!(result.unwrap(), 123);
+    }
+
+    #[test]
+    fn ok_or_else_reason_for_none() {
+        let line = line!() + 1;
+        let result = None::<i32>.ok_or_else_reason(|| Reasons::NotFound { id: 2 });
+        let err = result.unwrap_err();
+        match err.reason::<Reasons>() {
+            Ok(Reasons::NotFound { id }) => assert_eq!(*id, 2),
+            Err(_) => panic!(),
+        }
+        assert_eq!(err.file(), file!());
+        assert_eq!(err.line(), line);
+    }
+}
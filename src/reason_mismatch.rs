@@ -0,0 +1,94 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::ReasonMismatchError;
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+#[cfg(not(feature = "std"))]
+use crate::error;
+
+impl ReasonMismatchError {
+    pub(crate) fn new(expected: &'static str, actual: &'static str) -> Self {
+        Self { expected, actual }
+    }
+
+    /// Returns the type name of the expected reason.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Returns the type name of the actual reason.
+    pub fn actual(&self) -> &'static str {
+        self.actual
+    }
+}
+
+impl fmt::Display for ReasonMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the reason is not of the expected type: expected `{}`, actual `{}`",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl error::Error for ReasonMismatchError {}
+
+#[cfg(test)]
+mod tests_of_reason_mismatch_error {
+    use crate::Err;
+    use alloc::string::{String, ToString};
+
+    #[derive(Debug)]
+    enum Reasons {
+        IllegalState { state: String },
+    }
+
+    #[test]
+    fn reason_of_expected_type() {
+        let err = Err::new(Reasons::IllegalState {
+            state: "bad state".to_string(),
+        });
+        match err.reason_result::<Reasons>() {
+            Ok(Reasons::IllegalState { state }) => assert_eq!(state, "bad state"),
+            Err(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn reason_of_unexpected_type() {
+        let err = Err::new(42u32);
+        let e = err.reason_result::<Reasons>().unwrap_err();
+        assert!(e.expected().ends_with("Reasons"));
+        assert_eq!(e.actual(), "u32");
+        assert!(e
+            .to_string()
+            .starts_with("the reason is not of the expected type: expected `"));
+        assert!(e.to_string().ends_with("Reasons`, actual `u32`"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn propagate_with_question_mark() {
+        fn state_of(err: &Err) -> Result<String, Box<dyn std::error::Error>> {
+            let Reasons::IllegalState { state } = err.reason_result::<Reasons>()?;
+            Ok(state.clone())
+        }
+
+        let err = Err::new(Reasons::IllegalState {
+            state: "bad state".to_string(),
+        });
+        assert_eq!(state_of(&err).unwrap(), "bad state");
+
+        let err = Err::new("unknown error");
+        let e = state_of(&err).unwrap_err();
+        let e = e.downcast_ref::<crate::ReasonMismatchError>().unwrap();
+        assert_eq!(e.actual(), "&str");
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::ReasonTypeInfo;
+
+#[doc(hidden)]
+pub struct ReasonTypeRegistration {
+    name: &'static str,
+    description: &'static str,
+}
+impl ReasonTypeRegistration {
+    pub const fn new(name: &'static str, description: &'static str) -> Self {
+        Self { name, description }
+    }
+}
+inventory::collect!(ReasonTypeRegistration);
+
+/// Gets the information of all reason types registered with [`register_reason_type!`].
+///
+/// The order of the returned reason types is unspecified.
+///
+/// # Returns
+/// A vector of [`ReasonTypeInfo`] of the registered reason types.
+pub fn known_reason_types() -> Vec<ReasonTypeInfo> {
+    inventory::iter::<ReasonTypeRegistration>
+        .into_iter()
+        .map(|reg| ReasonTypeInfo {
+            name: reg.name,
+            description: reg.description,
+        })
+        .collect()
+}
+
+/// Statically registers a reason type for documentation.
+///
+/// This macro uses the `inventory` crate to collect reason types at compile time. The
+/// registered reason types can be enumerated with [`known_reason_types`] at runtime, e.g. to
+/// generate a list of all the errors of an application.
+/// The registration is independent of error notification.
+///
+/// The name of the reason type is recorded with the module path where this macro is used.
+///
+/// # Example
+/// ```rust
+/// use errs::register_reason_type;
+///
+/// #[derive(Debug)]
+/// enum ConfigReasons {
+///     FileNotFound { path: String },
+/// }
+///
+/// register_reason_type!(ConfigReasons, "Errors while loading the configuration.");
+///
+/// let types = errs::known_reason_types();
+/// let info = types.iter().find(|t| t.name.ends_with("::ConfigReasons")).unwrap();
+/// assert_eq!(info.description, "Errors while loading the configuration.");
+/// ```
+#[macro_export]
+macro_rules! register_reason_type {
+    ($reason:ty) => {
+        $crate::register_reason_type!($reason, "");
+    };
+    ($reason:ty, $description:expr $(,)?) => {
+        inventory::submit! {
+          $crate::ReasonTypeRegistration::new(
+              concat!(module_path!(), "::", stringify!($reason)),
+              $description,
+          )
+        }
+    };
+}
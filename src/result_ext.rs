@@ -0,0 +1,238 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::{Err, Result, ResultExt};
+
+#[cfg(feature = "tracing")]
+use crate::trace;
+
+use std::io;
+
+impl<T> ResultExt for Result<T> {
+    type Value = T;
+
+    fn trace(self) -> Self {
+        if let Err(ref err) = self {
+            output(err, None);
+        }
+        self
+    }
+
+    fn trace_with(self, label: &str) -> Self {
+        if let Err(ref err) = self {
+            output(err, Some(label));
+        }
+        self
+    }
+
+    #[cfg(feature = "notify-tokio")]
+    fn spawn_trace(self) -> Self {
+        if let Err(ref err) = self {
+            spawn_output(err, output_prepared);
+        }
+        self
+    }
+
+    fn into_io(self) -> io::Result<T> {
+        self.map_err(io::Error::other)
+    }
+}
+
+// The contents of an error prepared for the output in a spawned task, since the error itself is
+// returned to the caller. The text is in the `Debug` format regardless of the features, so that
+// it contains the file and line where the error occurred.
+#[cfg(feature = "notify-tokio")]
+struct Prepared {
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    file: &'static str,
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    line: u32,
+    text: String,
+}
+
+#[cfg(feature = "notify-tokio")]
+impl Prepared {
+    fn new(err: &Err) -> Self {
+        Self {
+            file: err.file(),
+            line: err.line(),
+            text: format!("{err:?}"),
+        }
+    }
+}
+
+#[cfg(feature = "notify-tokio")]
+fn spawn_output<F>(err: &Err, f: F)
+where
+    F: FnOnce(Prepared) + Send + 'static,
+{
+    let prepared = Prepared::new(err);
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move { f(prepared) });
+        }
+        Err(_) => f(prepared),
+    }
+}
+
+#[cfg(all(feature = "notify-tokio", feature = "tracing"))]
+fn output_prepared(prepared: Prepared) {
+    trace::trace_result(prepared.file, prepared.line, &prepared.text, "");
+}
+
+#[cfg(all(feature = "notify-tokio", not(feature = "tracing")))]
+fn output_prepared(prepared: Prepared) {
+    eprintln!("{}", prepared.text);
+}
+
+#[cfg(feature = "tracing")]
+fn output(err: &Err, label: Option<&str>) {
+    trace::trace_result_err(err, label.unwrap_or(""));
+}
+
+#[cfg(not(feature = "tracing"))]
+fn output(err: &Err, label: Option<&str>) {
+    eprintln!("{}", format_err(err, label));
+}
+
+#[cfg(not(feature = "tracing"))]
+fn format_err(err: &Err, label: Option<&str>) -> String {
+    match label {
+        Some(label) => format!("{label}: {err:?}"),
+        None => format!("{err:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests_of_result_ext {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    fn fail() -> Result<u32> {
+        Err(Err::new(Reasons::FailToDoSomething))
+    }
+
+    #[test]
+    fn trace_passes_through_result() {
+        let result: Result<u32> = Ok(1);
+        assert_eq!(result.trace().unwrap(), 1);
+        let result: Result<u32> = Ok(2);
+        assert_eq!(result.trace_with("label").unwrap(), 2);
+
+        let line = line!() + 1;
+        let result: Result<u32> = Err(Err::new(Reasons::FailToDoSomething));
+        let err = result.trace().unwrap_err();
+        assert!(err.reason::<Reasons>().is_ok());
+        assert_eq!(err.line(), line);
+
+        let line = line!() + 1;
+        let result: Result<u32> = Err(Err::new(Reasons::FailToDoSomething));
+        let err = result.trace_with("label").unwrap_err();
+        assert!(err.reason::<Reasons>().is_ok());
+        assert_eq!(err.line(), line);
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[test]
+    fn format_err_with_and_without_label() {
+        let line = line!() + 1;
+        let err = Err::new(Reasons::FailToDoSomething);
+
+        let s = format_err(&err, None);
+        assert!(s.starts_with("errs::Err { reason = errs::result_ext::tests_of_result_ext::Reasons FailToDoSomething, file = "));
+        assert!(s.ends_with(&format!(", line = {line} }}")));
+
+        let s = format_err(&err, Some("load config"));
+        assert!(s.starts_with("load config: errs::Err { reason = "));
+    }
+
+    #[cfg(feature = "tracing")]
+    mod tests_with_tracing {
+        use super::*;
+        use tracing_test::traced_test;
+
+        #[traced_test]
+        #[test]
+        fn trace_emits_event_only_on_error() {
+            let result: Result<u32> = Ok(1);
+            let _ = result.trace_with("ok path");
+            assert!(!logs_contain("errs::Err traced"));
+
+            let _ = fail().trace_with("error path");
+            assert!(logs_contain("errs::Err traced"));
+            assert!(logs_contain("label=\"error path\""));
+            assert!(logs_contain("reason=FailToDoSomething"));
+        }
+    }
+
+    #[cfg(feature = "notify-tokio")]
+    mod tests_with_tokio {
+        use super::*;
+        use std::sync::mpsc;
+
+        #[test]
+        fn spawn_trace_passes_through_result() {
+            let result: Result<u32> = Ok(1);
+            assert_eq!(result.spawn_trace().unwrap(), 1);
+
+            let err = fail().spawn_trace().unwrap_err();
+            assert!(err.reason::<Reasons>().is_ok());
+        }
+
+        #[tokio::test]
+        async fn spawn_trace_in_async_fn() {
+            async fn run() -> Result<u32> {
+                let n = async { fail() }.await.spawn_trace()?;
+                Ok(n)
+            }
+            let err = run().await.unwrap_err();
+            assert!(err.reason::<Reasons>().is_ok());
+        }
+
+        #[tokio::test]
+        async fn output_in_spawned_task_without_blocking_caller() {
+            let err = fail().unwrap_err();
+
+            let (tx, rx) = mpsc::channel();
+            spawn_output(&err, move |prepared| tx.send(prepared.text).unwrap());
+
+            // The runtime of this test is single-threaded, so the spawned task does not run
+            // until this caller yields.
+            assert!(rx.try_recv().is_err());
+
+            tokio::task::yield_now().await;
+            let text = rx.recv_timeout(std::time::Duration::from_secs(1)).unwrap();
+            assert!(text.starts_with("errs::Err { reason = "));
+        }
+
+        #[test]
+        fn output_on_current_thread_without_runtime() {
+            let err = fail().unwrap_err();
+
+            let (tx, rx) = mpsc::channel();
+            spawn_output(&err, move |prepared| tx.send(prepared.text).unwrap());
+            assert!(rx.try_recv().is_ok());
+        }
+    }
+
+    #[test]
+    fn into_io_passes_through_ok() {
+        let result: Result<u32> = Ok(1);
+        assert_eq!(result.into_io().unwrap(), 1);
+    }
+
+    #[test]
+    fn into_io_wraps_err() {
+        let io_err = fail().into_io().unwrap_err();
+        assert_eq!(io_err.kind(), io::ErrorKind::Other);
+        assert_eq!(io_err.to_string(), "FailToDoSomething");
+
+        let err = io_err.into_inner().unwrap().downcast::<Err>().unwrap();
+        assert!(err.reason::<Reasons>().is_ok());
+    }
+}
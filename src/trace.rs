@@ -0,0 +1,127 @@
+// Copyright (C) 2026 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Err;
+
+use tracing::Level;
+
+use std::fmt;
+use std::sync::atomic;
+
+const LEVEL_TRACE: u8 = 0;
+const LEVEL_DEBUG: u8 = 1;
+const LEVEL_INFO: u8 = 2;
+const LEVEL_WARN: u8 = 3;
+const LEVEL_ERROR: u8 = 4;
+
+static TRACING_LEVEL: atomic::AtomicU8 = atomic::AtomicU8::new(LEVEL_ERROR);
+
+/// Sets the level of the `tracing` event emitted when an `Err` instance is created.
+///
+/// The default level is `Level::ERROR`.
+///
+/// # Parameters
+/// - `level`: The level of the `tracing` event.
+///
+/// ```rust
+/// errs::set_tracing_level(tracing::Level::WARN);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub fn set_tracing_level(level: Level) {
+    let n = if level == Level::TRACE {
+        LEVEL_TRACE
+    } else if level == Level::DEBUG {
+        LEVEL_DEBUG
+    } else if level == Level::INFO {
+        LEVEL_INFO
+    } else if level == Level::WARN {
+        LEVEL_WARN
+    } else {
+        LEVEL_ERROR
+    };
+    TRACING_LEVEL.store(n, atomic::Ordering::Relaxed);
+}
+
+macro_rules! emit_event {
+    ($level:expr, $err:expr) => {
+        tracing::event!(
+            $level,
+            file = %$err.file(),
+            line = $err.line(),
+            reason = %$err,
+            "errs::Err created"
+        )
+    };
+}
+
+macro_rules! emit_traced_event {
+    ($level:expr, $file:expr, $line:expr, $reason:expr, $label:expr) => {
+        tracing::event!(
+            $level,
+            file = %$file,
+            line = $line,
+            reason = %$reason,
+            label = $label,
+            "errs::Err traced"
+        )
+    };
+}
+
+pub(crate) fn trace_err(err: &Err) {
+    match TRACING_LEVEL.load(atomic::Ordering::Relaxed) {
+        LEVEL_TRACE => emit_event!(Level::TRACE, err),
+        LEVEL_DEBUG => emit_event!(Level::DEBUG, err),
+        LEVEL_INFO => emit_event!(Level::INFO, err),
+        LEVEL_WARN => emit_event!(Level::WARN, err),
+        _ => emit_event!(Level::ERROR, err),
+    }
+}
+
+pub(crate) fn trace_result_err(err: &Err, label: &str) {
+    trace_result(err.file(), err.line(), err, label);
+}
+
+// Emits the event of a traced error from its contents, so that it can also be emitted after the
+// error itself is gone.
+pub(crate) fn trace_result(file: &str, line: u32, reason: &dyn fmt::Display, label: &str) {
+    match TRACING_LEVEL.load(atomic::Ordering::Relaxed) {
+        LEVEL_TRACE => emit_traced_event!(Level::TRACE, file, line, reason, label),
+        LEVEL_DEBUG => emit_traced_event!(Level::DEBUG, file, line, reason, label),
+        LEVEL_INFO => emit_traced_event!(Level::INFO, file, line, reason, label),
+        LEVEL_WARN => emit_traced_event!(Level::WARN, file, line, reason, label),
+        _ => emit_traced_event!(Level::ERROR, file, line, reason, label),
+    }
+}
+
+#[cfg(test)]
+mod tests_of_trace {
+    use super::*;
+    use tracing_test::traced_test;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+        CacheMiss,
+    }
+
+    #[traced_test]
+    #[test]
+    fn test() {
+        let _err = Err::new(Reasons::FailToDoSomething);
+        assert!(logs_contain("ERROR"));
+        assert!(logs_contain("errs::Err created"));
+        assert!(logs_contain("reason=FailToDoSomething"));
+        #[cfg(unix)]
+        assert!(logs_contain("file=src/trace.rs"));
+
+        let _err = Err::new_silent(Reasons::CacheMiss);
+        assert!(!logs_contain("CacheMiss"));
+
+        set_tracing_level(Level::WARN);
+        let _err = Err::with_source(Reasons::CacheMiss, std::io::Error::other("oh no!"));
+        set_tracing_level(Level::ERROR);
+        assert!(logs_contain("WARN"));
+        assert!(logs_contain("reason=CacheMiss"));
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_arc_sync_handler {
+    use std::sync::{Arc, Mutex};
+
+    static QUEUE: Mutex<Vec<Arc<errs::Err>>> = Mutex::new(Vec::new());
+
+    #[derive(Debug, PartialEq)]
+    enum Reasons {
+        FailToDoSomething { id: u32 },
+    }
+
+    #[test]
+    fn test() {
+        errs::add_arc_sync_err_handler(|err, _tm| {
+            QUEUE.lock().unwrap().push(err);
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        let mut err0 = errs::Err::new(Reasons::FailToDoSomething { id: 0 });
+        let err1 = errs::Err::new(Reasons::FailToDoSomething { id: 1 });
+
+        // The queued error shares the reason with the returned error, so the reason of the
+        // returned error cannot be mutated while the queued error is kept.
+        assert!(err0.reason_mut::<Reasons>().is_none());
+        assert_eq!(
+            err0.reason::<Reasons>().unwrap(),
+            &Reasons::FailToDoSomething { id: 0 }
+        );
+        drop(err0);
+        drop(err1);
+
+        let mut queue = QUEUE.lock().unwrap();
+        assert_eq!(queue.len(), 2);
+        assert_eq!(
+            queue[0].reason::<Reasons>().unwrap(),
+            &Reasons::FailToDoSomething { id: 0 }
+        );
+        assert_eq!(queue[0].to_string(), "FailToDoSomething { id: 0 }");
+        assert_eq!(queue[1].to_string(), "FailToDoSomething { id: 1 }");
+        assert_eq!(
+            queue[0].reason_type_name(),
+            "arc_sync_handler_test::tests_of_arc_sync_handler::Reasons"
+        );
+        #[cfg(unix)]
+        assert_eq!(queue[0].file(), "tests/arc_sync_handler_test.rs");
+
+        // The returned error is already dropped, so the reason can be taken out of the queued one.
+        let err1 = Arc::try_unwrap(queue.pop().unwrap()).unwrap();
+        assert_eq!(
+            err1.downcast_reason::<Reasons>().unwrap(),
+            Reasons::FailToDoSomething { id: 1 }
+        );
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+#[cfg(test)]
+mod tests_of_assert_no_errors {
+    use std::panic;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+        InvalidValue { value: i32 },
+    }
+
+    #[test]
+    fn no_error_is_notified() {
+        errs::fix_err_handlers().unwrap();
+
+        errs::assert_no_errors(|| {
+            let _err = errs::Err::new_silent(Reasons::FailToDoSomething);
+        });
+    }
+
+    #[test]
+    fn errors_are_notified() {
+        errs::fix_err_handlers().unwrap();
+
+        let line = line!() + 3;
+        let result = panic::catch_unwind(|| {
+            errs::assert_no_errors(|| {
+                let _err = errs::Err::new(Reasons::FailToDoSomething);
+                let _err = errs::Err::new(Reasons::InvalidValue { value: 3 });
+            });
+        });
+
+        let payload = result.unwrap_err();
+        let msg = payload.downcast_ref::<String>().unwrap();
+        assert_eq!(
+            *msg,
+            format!(
+                "expected no errors, but 2 error(s) notified:\n  FailToDoSomething ({file}:{})\n  InvalidValue {{ value: 3 }} ({file}:{})",
+                line,
+                line + 1,
+                file = file!(),
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected no errors, but 1 error(s) notified:")]
+    fn panic_if_error_is_notified() {
+        errs::fix_err_handlers().unwrap();
+
+        errs::assert_no_errors(|| {
+            let _err = errs::Err::new(Reasons::FailToDoSomething);
+        });
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(any(feature = "notify", feature = "notify-tokio"))]
+#[cfg(test)]
+mod tests_of_capture_errors_of {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+        InvalidValue { value: i32 },
+    }
+
+    #[derive(Debug)]
+    enum OtherReasons {
+        CacheMiss,
+    }
+
+    #[test]
+    fn test() {
+        errs::fix_err_handlers().unwrap();
+
+        let line = line!() + 3;
+        let captured = errs::capture_errors_of::<Reasons>(|| {
+            let _err = errs::Err::new(OtherReasons::CacheMiss);
+            let _err = errs::Err::new(Reasons::FailToDoSomething);
+            let _err = errs::Err::new("string reason".to_string());
+            let _err = errs::Err::new_silent(Reasons::FailToDoSomething);
+            let _err = errs::Err::new(Reasons::InvalidValue { value: 3 });
+        });
+
+        assert_eq!(
+            captured,
+            vec![
+                errs::CapturedErr {
+                    file: "tests/capture_errors_of_test.rs",
+                    line,
+                    reason: "FailToDoSomething".to_string(),
+                },
+                errs::CapturedErr {
+                    file: "tests/capture_errors_of_test.rs",
+                    line: line + 3,
+                    reason: "InvalidValue { value: 3 }".to_string(),
+                },
+            ]
+        );
+
+        let captured = errs::capture_errors_of::<OtherReasons>(|| {
+            let _err = errs::Err::new(Reasons::FailToDoSomething);
+        });
+        assert!(captured.is_empty());
+    }
+}
@@ -0,0 +1,15 @@
+use errs::Err;
+
+#[derive(Debug)]
+enum Reasons {
+  MyErr { msg: String },
+}
+
+fn main() {
+  let mut msgs = Vec::<String>::new();
+
+  let err = Err::new(Reasons::MyErr { msg: "hello".to_string() });
+  err.match_reason::<Reasons>(|r| match r {
+    Reasons::MyErr { msg } => msgs.push(msg.clone()),
+  });
+}
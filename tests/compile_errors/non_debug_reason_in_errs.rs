@@ -0,0 +1,10 @@
+use errs::Err;
+
+enum Reasons {
+  MyErr { msg: String },
+}
+
+fn main() {
+  let err = Err::new(Reasons::MyErr { msg: "hello".to_string() });
+  println!("{}", err);
+}
@@ -0,0 +1,11 @@
+use errs::reason_from_source;
+
+#[derive(Debug)]
+enum Reasons {
+  Io,
+}
+
+fn main() {
+  let err = reason_from_source!(Reasons::Io, "not an error");
+  println!("{}", err);
+}
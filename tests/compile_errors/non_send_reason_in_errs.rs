@@ -0,0 +1,12 @@
+use errs::Err;
+use std::rc::Rc;
+
+#[derive(Debug)]
+enum Reasons {
+  MyErr { msg: Rc<String> },
+}
+
+fn main() {
+  let err = Err::new(Reasons::MyErr { msg: Rc::new("hello".to_string()) });
+  println!("{}", err);
+}
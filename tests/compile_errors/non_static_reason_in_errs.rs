@@ -0,0 +1,16 @@
+use errs::Err;
+
+#[derive(Debug)]
+enum Reasons<'a> {
+  MyErr { msg: &'a str },
+}
+
+fn create_err(msg: &str) -> Err {
+  Err::new(Reasons::MyErr { msg })
+}
+
+fn main() {
+  let msg = "hello".to_string();
+  let err = create_err(&msg);
+  println!("{}", err);
+}
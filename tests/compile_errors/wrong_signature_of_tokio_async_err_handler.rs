@@ -0,0 +1,10 @@
+use errs::{add_tokio_async_err_handler, Err};
+use chrono::{DateTime, Utc};
+
+fn my_tokio_handler(err: &Err, tm: DateTime<Utc>) {
+  println!("{}: {}", tm, err);
+}
+
+add_tokio_async_err_handler!(my_tokio_handler);
+
+fn main() {}
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests_of_debug_show_reason_type {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething { name: String },
+    }
+
+    #[test]
+    fn test() {
+        let line = line!() + 1;
+        let err = errs::Err::new(Reasons::FailToDoSomething {
+            name: "foo".to_string(),
+        });
+        assert_eq!(
+            format!("{err:?}"),
+            format!(
+                "errs::Err {{ reason = debug_show_reason_type_test::tests_of_debug_show_reason_type::Reasons FailToDoSomething {{ name: \"foo\" }}, file = tests/debug_show_reason_type_test.rs, line = {line} }}",
+            )
+        );
+
+        errs::set_debug_show_reason_type(false);
+        assert_eq!(
+            format!("{err:?}"),
+            format!(
+                "errs::Err {{ reason = FailToDoSomething {{ name: \"foo\" }}, file = tests/debug_show_reason_type_test.rs, line = {line} }}",
+            )
+        );
+
+        errs::set_debug_show_reason_type(true);
+        assert!(format!("{err:?}").starts_with(
+            "errs::Err { reason = debug_show_reason_type_test::tests_of_debug_show_reason_type::Reasons FailToDoSomething"
+        ));
+    }
+}
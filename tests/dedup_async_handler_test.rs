@@ -0,0 +1,52 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_dedup_async_handler {
+    use std::sync::{LazyLock, Mutex};
+    use std::{thread, time};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[derive(Debug)]
+    enum OtherReasons {
+        FailToDoSomething,
+    }
+
+    fn create_err<R>(reason: R) -> errs::Err
+    where
+        R: std::fmt::Debug + Send + Sync + 'static,
+    {
+        errs::Err::new(reason)
+    }
+
+    #[test]
+    fn test() {
+        errs::add_sequential_async_err_handler(errs::dedup_handler(
+            time::Duration::from_secs(60),
+            |err, _tm| {
+                LOGGER
+                    .lock()
+                    .unwrap()
+                    .push(err.reason_type_name().to_string());
+            },
+        ))
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        // The errors are created at the same location, but the async handler can still tell
+        // their reason types apart.
+        let _err = create_err(Reasons::FailToDoSomething);
+        let _err = create_err(OtherReasons::FailToDoSomething);
+        let _err = create_err(Reasons::FailToDoSomething);
+
+        thread::sleep(time::Duration::from_millis(200));
+        let logs = LOGGER.lock().unwrap();
+        assert_eq!(logs.len(), 2);
+        assert!(logs[0].ends_with("::Reasons"));
+        assert!(logs[1].ends_with("::OtherReasons"));
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_dedup_handler {
+    use std::sync::{LazyLock, Mutex};
+    use std::time::Duration;
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething(u32),
+    }
+
+    #[test]
+    fn test() {
+        errs::add_sync_err_handler(errs::dedup_handler(Duration::from_secs(60), |err, _tm| {
+            LOGGER.lock().unwrap().push(format!("{err}"));
+        }))
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        for i in 0..2 {
+            let _err = errs::Err::new(Reasons::FailToDoSomething(i));
+        }
+        let _err = errs::Err::new(Reasons::FailToDoSomething(2));
+
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec!["FailToDoSomething(0)", "FailToDoSomething(2)"]
+        );
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests_of_display_formatter {
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        let err = errs::Err::new(Reasons::FailToDoSomething);
+        assert_eq!(format!("{err}"), "FailToDoSomething");
+
+        assert!(errs::set_display_formatter(|err, f| {
+            write!(f, "{}:{} - {}", err.file(), err.line(), err)
+        }));
+        assert!(!errs::set_display_formatter(|_err, f| write!(f, "ignored")));
+
+        let line = line!() + 1;
+        let err = errs::Err::new(Reasons::FailToDoSomething);
+        #[cfg(unix)]
+        assert_eq!(
+            format!("{err}"),
+            format!("tests/display_formatter_test.rs:{line} - FailToDoSomething"),
+        );
+        #[cfg(windows)]
+        assert_eq!(
+            format!("{err}"),
+            format!("tests\\display_formatter_test.rs:{line} - FailToDoSomething"),
+        );
+
+        let err = err.with_field("user_id", 42);
+        assert!(format!("{err}").ends_with(" - FailToDoSomething [user_id=42]"));
+        assert!(format!("{err:?}").starts_with("errs::Err { reason = "));
+    }
+}
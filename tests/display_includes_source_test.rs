@@ -0,0 +1,42 @@
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests_of_display_includes_source {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToLoadConfig,
+        FailToReadFile { path: String },
+    }
+
+    fn create_err() -> errs::Err {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+        let err = errs::Err::with_source(
+            Reasons::FailToReadFile {
+                path: "config.toml".to_string(),
+            },
+            io_err,
+        );
+        errs::Err::with_source(Reasons::FailToLoadConfig, err)
+    }
+
+    #[test]
+    fn test() {
+        let err = create_err();
+        assert_eq!(format!("{err}"), "FailToLoadConfig");
+
+        errs::set_display_includes_source(true);
+        let err = create_err();
+        assert_eq!(
+            format!("{err}"),
+            "FailToLoadConfig: FailToReadFile { path: \"config.toml\" }: file not found"
+        );
+        let err = err.with_field("id", 1);
+        assert_eq!(
+            format!("{err}"),
+            "FailToLoadConfig [id=1]: FailToReadFile { path: \"config.toml\" }: file not found"
+        );
+
+        errs::set_display_includes_source(false);
+        assert_eq!(format!("{err}"), "FailToLoadConfig [id=1]");
+    }
+}
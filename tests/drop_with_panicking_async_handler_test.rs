@@ -0,0 +1,46 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_drop_with_panicking_async_handler {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::{thread, time};
+
+    static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug)]
+    struct DropCountingReason;
+
+    impl Drop for DropCountingReason {
+        fn drop(&mut self) {
+            DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test() {
+        errs::add_async_err_handler(|_err, _tm| {
+            thread::sleep(time::Duration::from_millis(50));
+            panic!("async handler panicked");
+        })
+        .unwrap();
+        errs::add_sync_err_handler(|_err, _tm| {
+            panic!("sync handler panicked");
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        // The handlers receive a copy sharing the reason, so the reason is dropped once after
+        // both the returned error and the copy are dropped, even if the handlers panic.
+        let err = errs::Err::new(DropCountingReason);
+        drop(err);
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 0);
+
+        thread::sleep(time::Duration::from_millis(200));
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 1);
+
+        let err = errs::Err::new(DropCountingReason);
+        thread::sleep(time::Duration::from_millis(200));
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 1);
+        drop(err);
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 2);
+    }
+}
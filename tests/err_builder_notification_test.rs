@@ -0,0 +1,40 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_err_builder_notification {
+    use errs::{ErrBuilder, Severity};
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    errs::add_sync_err_handler!(|err, _tm| {
+        LOGGER.lock().unwrap().push(format!(
+            "{err}: {:?}, source={}, line={}",
+            err.severity(),
+            err.source().is_some(),
+            err.line()
+        ));
+    });
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToLoadConfig,
+    }
+
+    #[test]
+    fn test() {
+        let builder = ErrBuilder::new(Reasons::FailToLoadConfig)
+            .source(std::io::Error::other("oh no!"))
+            .severity(Severity::Fatal)
+            .field("path", "/etc/app.conf");
+        let line = line!() + 1;
+        let _err = builder.build();
+
+        // The severity and fields set to the builder are notified.
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec![format!(
+                "FailToLoadConfig [path=/etc/app.conf]: Fatal, source=true, line={line}"
+            )]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(test)]
 mod integration_tests_of_err {
+    // Imports the crate explicitly to show that these tests use it as an external crate.
+    #[allow(clippy::single_component_path_imports)]
     use errs;
 
     #[derive(Debug)]
@@ -127,12 +129,12 @@ mod integration_tests_of_err {
         #[cfg(unix)]
         assert_eq!(
             format!("{err:?}"),
-            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs FileNotFound { path: \"/aaa/bbb/ccc\" }, file = tests/errs_test.rs, line = 13 }"
+            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs FileNotFound { path: \"/aaa/bbb/ccc\" }, file = tests/errs_test.rs, line = 15 }"
         );
         #[cfg(windows)]
         assert_eq!(
             format!("{err:?}"),
-            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs FileNotFound { path: \"/aaa/bbb/ccc\" }, file = tests\\errs_test.rs, line = 13 }"
+            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs FileNotFound { path: \"/aaa/bbb/ccc\" }, file = tests\\errs_test.rs, line = 15 }"
         );
 
         let err = read_file().unwrap_err();
@@ -140,12 +142,12 @@ mod integration_tests_of_err {
         #[cfg(unix)]
         assert_eq!(
             format!("{err:?}"),
-            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs NoPermission { path: \"/aaa/bbb/ccc\", mod: (4, 4, 4) }, file = tests/errs_test.rs, line = 20 }"
+            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs NoPermission { path: \"/aaa/bbb/ccc\", mod: (4, 4, 4) }, file = tests/errs_test.rs, line = 22 }"
         );
         #[cfg(windows)]
         assert_eq!(
             format!("{err:?}"),
-            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs NoPermission { path: \"/aaa/bbb/ccc\", mod: (4, 4, 4) }, file = tests\\errs_test.rs, line = 20 }"
+            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs NoPermission { path: \"/aaa/bbb/ccc\", mod: (4, 4, 4) }, file = tests\\errs_test.rs, line = 22 }"
         );
 
         let err = write_file().unwrap_err();
@@ -153,12 +155,12 @@ mod integration_tests_of_err {
         #[cfg(unix)]
         assert_eq!(
             format!("{err:?}"),
-            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs DueToSomeError { path: \"/aaa/bbb/ccc\" }, source = Custom { kind: AlreadyExists, error: \"/aaa/bbb/ccc\" }, file = tests/errs_test.rs, line = 30 }"
+            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs DueToSomeError { path: \"/aaa/bbb/ccc\" }, source = Custom { kind: AlreadyExists, error: \"/aaa/bbb/ccc\" }, file = tests/errs_test.rs, line = 32 }"
         );
         #[cfg(windows)]
         assert_eq!(
             format!("{err:?}"),
-            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs DueToSomeError { path: \"/aaa/bbb/ccc\" }, source = Custom { kind: AlreadyExists, error: \"/aaa/bbb/ccc\" }, file = tests\\errs_test.rs, line = 30 }"
+            "errs::Err { reason = errs_test::integration_tests_of_err::IoErrs DueToSomeError { path: \"/aaa/bbb/ccc\" }, source = Custom { kind: AlreadyExists, error: \"/aaa/bbb/ccc\" }, file = tests\\errs_test.rs, line = 32 }"
         );
     }
 
@@ -200,6 +202,25 @@ mod integration_tests_of_err {
             panic!();
         }
     }
+
+    fn run_with_boxed_error() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        write_file()?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_err_into_boxed_error() {
+        let boxed = run_with_boxed_error().unwrap_err();
+
+        let boxed = std::thread::spawn(move || boxed).join().unwrap();
+
+        let err = boxed.downcast_ref::<errs::Err>().unwrap();
+        match err.reason::<IoErrs>() {
+            Ok(IoErrs::DueToSomeError { path }) => assert_eq!(path, "/aaa/bbb/ccc"),
+            _ => panic!(),
+        }
+        assert!(boxed.source().is_some());
+    }
 }
 
 #[test]
@@ -207,3 +228,17 @@ fn compile_error_check() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compile_errors/*_errs.rs");
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn compile_error_check_of_reason_from_source() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_errors/non_error_source_of_reason_from_source.rs");
+}
+
+#[cfg(feature = "notify-tokio")]
+#[test]
+fn compile_error_check_of_tokio_async_err_handler() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_errors/wrong_signature_of_tokio_async_err_handler.rs");
+}
@@ -0,0 +1,40 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_field_notification {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    errs::add_sync_err_handler!(|err, _tm| {
+        LOGGER
+            .lock()
+            .unwrap()
+            .push(format!("{err}: {:?}", err.fields()));
+    });
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        let err0 = errs::Err::new(Reasons::FailToDoSomething).with_field("user_id", 42);
+        let err1 = errs::Err::builder(Reasons::FailToDoSomething)
+            .field("user_id", 42)
+            .field("request_id", "abc")
+            .build();
+
+        assert_eq!(err0.fields(), &[("user_id".to_string(), "42".to_string())]);
+        assert_eq!(err1.fields().len(), 2);
+
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec![
+                // The fields attached after creation are not notified.
+                "FailToDoSomething: []",
+                r#"FailToDoSomething [user_id=42, request_id=abc]: [("user_id", "42"), ("request_id", "abc")]"#,
+            ]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(feature = "notify-tokio")]
+#[cfg(test)]
+mod tests_of_flush {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    errs::add_tokio_async_err_handler!(async |err, _tm| {
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        LOGGER.lock().unwrap().push(format!("[tokio] {err}"));
+    });
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[tokio::test]
+    async fn test() {
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+        assert!(LOGGER.lock().unwrap().is_empty());
+
+        errs::flush_tokio_handlers().await;
+
+        let logs = LOGGER.lock().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0], "[tokio] FailToDoSomething");
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_handler_group {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething(u32),
+    }
+
+    #[test]
+    fn test() {
+        errs::add_sync_err_handler_in_group("alerts", |err, _tm| {
+            LOGGER.lock().unwrap().push(format!("alerts: {err}"));
+        })
+        .unwrap();
+        errs::add_sync_err_handler_in_group("audit", |err, _tm| {
+            LOGGER.lock().unwrap().push(format!("audit: {err}"));
+        })
+        .unwrap();
+        errs::add_sync_err_handler(|err, _tm| {
+            LOGGER.lock().unwrap().push(format!("default: {err}"));
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        let _err = errs::Err::new(Reasons::FailToDoSomething(1));
+
+        errs::set_group_enabled("alerts", false);
+        let _err = errs::Err::new(Reasons::FailToDoSomething(2));
+
+        errs::set_group_enabled("alerts", true);
+        errs::set_group_enabled("audit", false);
+        let _err = errs::Err::new(Reasons::FailToDoSomething(3));
+
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec![
+                "alerts: FailToDoSomething(1)",
+                "audit: FailToDoSomething(1)",
+                "default: FailToDoSomething(1)",
+                "audit: FailToDoSomething(2)",
+                "default: FailToDoSomething(2)",
+                "alerts: FailToDoSomething(3)",
+                "default: FailToDoSomething(3)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_here_notification {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[test]
+    fn test() {
+        errs::add_sync_err_handler(|err, _tm| {
+            LOGGER
+                .lock()
+                .unwrap()
+                .push(format!("{} ({}:{})", err, err.file(), err.line()));
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        let line = line!() + 1;
+        let _err = errs::Err::here();
+
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec![format!("{}:{}:20 ({}:{})", file!(), line, file!(), line)]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "log")]
+#[cfg(test)]
+mod tests_of_log {
+    use std::sync::Mutex;
+
+    static RECORDS: Mutex<Vec<(log::Level, String, String)>> = Mutex::new(Vec::new());
+
+    struct Logger;
+
+    impl log::Log for Logger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            RECORDS.lock().unwrap().push((
+                record.level(),
+                record.target().to_string(),
+                record.args().to_string(),
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: Logger = Logger;
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+        CacheMiss,
+    }
+
+    #[test]
+    fn test() {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let line0 = line!() + 1;
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+
+        let _err = errs::Err::new_silent(Reasons::CacheMiss);
+
+        errs::set_log_target("myapp::errors");
+        errs::set_log_level(log::Level::Warn);
+        let line1 = line!() + 1;
+        let _err = errs::Err::new(Reasons::CacheMiss);
+
+        let records = RECORDS.lock().unwrap();
+        assert_eq!(
+            *records,
+            vec![
+                (
+                    log::Level::Error,
+                    "errs".to_string(),
+                    format!("tests/log_test.rs:{line0} FailToDoSomething"),
+                ),
+                (
+                    log::Level::Warn,
+                    "myapp::errors".to_string(),
+                    format!("tests/log_test.rs:{line1} CacheMiss"),
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_map_source {
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::{thread, time};
+
+    static LOGGER: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToReadFile,
+    }
+
+    #[test]
+    fn test() {
+        errs::add_async_err_handler(|err, _tm| {
+            thread::sleep(time::Duration::from_millis(50));
+            LOGGER
+                .lock()
+                .unwrap()
+                .push(format!("{err}: {}", err.source().unwrap()));
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        let called = AtomicUsize::new(0);
+        for _ in 0..10 {
+            // The async handler is running while the source is mapped.
+            let err = errs::Err::with_source(
+                Reasons::FailToReadFile,
+                io::Error::other("/home/alice/secret.txt is not found"),
+            );
+            let err = err.map_source(|src| {
+                called.fetch_add(1, Ordering::SeqCst);
+                src.map(|_| io::Error::other("file is not found").into())
+            });
+            assert_eq!(err.source().unwrap().to_string(), "file is not found");
+            assert!(err.reason::<Reasons>().is_ok());
+        }
+        assert_eq!(called.load(Ordering::SeqCst), 10);
+
+        thread::sleep(time::Duration::from_millis(200));
+        let logs = LOGGER.lock().unwrap();
+        assert_eq!(logs.len(), 10);
+        assert_eq!(
+            logs[0],
+            "FailToReadFile: /home/alice/secret.txt is not found"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_notified_copy {
+    use std::sync::Mutex;
+    use std::{thread, time};
+
+    static LOGGER: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToReadFile { path: String },
+    }
+
+    #[test]
+    fn test() {
+        errs::add_async_err_handler(|err, _tm| {
+            thread::sleep(time::Duration::from_millis(50));
+            let source = match err.source() {
+                Some(src) => src.to_string(),
+                None => "none".to_string(),
+            };
+            let path = match err.reason::<Reasons>() {
+                Ok(Reasons::FailToReadFile { path }) => path.as_str(),
+                Err(_) => "unknown",
+            };
+            LOGGER
+                .lock()
+                .unwrap()
+                .push(format!("{err}, path={path}, source={source}"));
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        // The async handler is still running. It shares the reason with the returned error, but
+        // has its own copy of the source.
+        let mut err = errs::Err::with_source(
+            Reasons::FailToReadFile {
+                path: "/etc/app.conf".to_string(),
+            },
+            std::io::Error::other("oh no!"),
+        );
+        assert_eq!(err.take_source().unwrap().to_string(), "oh no!");
+        assert!(err.source().is_none());
+        let err = match err.downcast_reason::<Reasons>() {
+            Ok(_) => panic!(),
+            Err(err) => err,
+        };
+
+        thread::sleep(time::Duration::from_millis(200));
+        let logs = LOGGER.lock().unwrap();
+        assert_eq!(
+            *logs,
+            vec!["FailToReadFile { path: \"/etc/app.conf\" }, path=/etc/app.conf, source=oh no!"]
+        );
+
+        // The async handler has finished, so the reason can be taken out.
+        match err.downcast_reason::<Reasons>() {
+            Ok(Reasons::FailToReadFile { path }) => assert_eq!(path, "/etc/app.conf"),
+            Err(_) => panic!(),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_notify_after_adding_handler {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        errs::add_sync_err_handler(|err, _tm| {
+            LOGGER.lock().unwrap().push(format!("{err}"));
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec!["FailToDoSomething", "FailToDoSomething"]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_notify_builder {
+    use std::sync::{LazyLock, Mutex};
+    use std::{thread, time};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        errs::ErrNotifyBuilder::new()
+            .sync(|err, _tm| {
+                LOGGER.lock().unwrap().push(format!("[sync 1] {err}"));
+            })
+            .async_(|err, _tm| {
+                thread::sleep(time::Duration::from_millis(20));
+                LOGGER.lock().unwrap().push(format!("[async] {err}"));
+            })
+            .sync(|err, _tm| {
+                LOGGER.lock().unwrap().push(format!("[sync 2] {err}"));
+            })
+            .sync(|err, _tm| {
+                LOGGER.lock().unwrap().push(format!("[sync 3] {err}"));
+            })
+            .install()
+            .unwrap();
+
+        let e = errs::add_sync_err_handler(|_err, _tm| {}).unwrap_err();
+        assert_eq!(e.kind(), errs::ErrHandlingErrorKind::AlreadyFixed);
+
+        let e = errs::ErrNotifyBuilder::new()
+            .sync(|err, _tm| {
+                LOGGER.lock().unwrap().push(format!("[late] {err}"));
+            })
+            .install()
+            .unwrap_err();
+        assert_eq!(e.kind(), errs::ErrHandlingErrorKind::AlreadyFixed);
+
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+        thread::sleep(time::Duration::from_millis(200));
+
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec![
+                "[sync 1] FailToDoSomething",
+                "[sync 2] FailToDoSomething",
+                "[sync 3] FailToDoSomething",
+                "[async] FailToDoSomething",
+            ]
+        );
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_notify_sampling {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    errs::add_sync_err_handler!(|err, _tm| {
+        LOGGER.lock().unwrap().push(format!("[sync] {err}"));
+    });
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething(u32),
+    }
+
+    #[test]
+    fn test() {
+        errs::set_notify_sampling(5);
+
+        for i in 0..10 {
+            let err = errs::Err::new(Reasons::FailToDoSomething(i));
+            match err.reason::<Reasons>() {
+                Ok(Reasons::FailToDoSomething(n)) => assert_eq!(*n, i),
+                Err(_) => panic!(),
+            }
+        }
+
+        let logs = LOGGER.lock().unwrap();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0], "[sync] FailToDoSomething(0)");
+        assert_eq!(logs[1], "[sync] FailToDoSomething(5)");
+    }
+}
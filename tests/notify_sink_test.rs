@@ -0,0 +1,48 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_notify_sink {
+    use std::sync::{LazyLock, Mutex};
+    use std::thread;
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        let test_thread_id = thread::current().id();
+
+        errs::add_sync_err_handler(|err, _tm| {
+            LOGGER.lock().unwrap().push(format!("[sync] {err}"));
+        })
+        .unwrap();
+        errs::add_async_err_handler(|err, _tm| {
+            LOGGER.lock().unwrap().push(format!("[async] {err}"));
+        })
+        .unwrap();
+
+        errs::set_notify_sink(Box::new(move |err, _tm| {
+            assert_eq!(thread::current().id(), test_thread_id);
+            LOGGER.lock().unwrap().push(format!("[sink] {err}"));
+        }))
+        .unwrap();
+
+        let e = errs::set_notify_sink(Box::new(|_err, _tm| {})).unwrap_err();
+        assert_eq!(e.kind(), errs::ErrHandlingErrorKind::InvalidCallTiming);
+
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+
+        // The sink is called synchronously.
+        assert_eq!(*LOGGER.lock().unwrap(), vec!["[sink] FailToDoSomething"]);
+
+        // The built-in dispatch, including the async handler threads, is not run.
+        thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(*LOGGER.lock().unwrap(), vec!["[sink] FailToDoSomething"]);
+
+        let e = errs::add_sync_err_handler(|_err, _tm| {}).unwrap_err();
+        assert_eq!(e.kind(), errs::ErrHandlingErrorKind::AlreadyFixed);
+    }
+}
@@ -0,0 +1,44 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_notify_sink_with_fixing {
+    use std::sync::{Barrier, LazyLock, Mutex};
+    use std::thread;
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        errs::add_sync_err_handler(|err, _tm| {
+            LOGGER.lock().unwrap().push(format!("[sync] {err}"));
+        })
+        .unwrap();
+
+        let barrier = Barrier::new(2);
+        let result = thread::scope(|s| {
+            s.spawn(|| {
+                barrier.wait();
+                errs::fix_err_handlers().unwrap();
+            });
+            barrier.wait();
+            errs::set_notify_sink(Box::new(|err, _tm| {
+                LOGGER.lock().unwrap().push(format!("[sink] {err}"));
+            }))
+        });
+
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+
+        // Whichever of them is first, the sink is never set after the handlers are fixed.
+        match result {
+            Ok(()) => assert_eq!(*LOGGER.lock().unwrap(), vec!["[sink] FailToDoSomething"]),
+            Err(e) => {
+                assert_eq!(e.kind(), errs::ErrHandlingErrorKind::AlreadyFixed);
+                assert_eq!(*LOGGER.lock().unwrap(), vec!["[sync] FailToDoSomething"]);
+            }
+        }
+    }
+}
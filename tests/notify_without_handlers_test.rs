@@ -0,0 +1,32 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_notify_without_handlers {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        errs::fix_err_handlers().unwrap();
+
+        // No handler is registered, so notifications are skipped.
+        for _ in 0..3 {
+            let err = errs::Err::new(Reasons::FailToDoSomething);
+            assert!(err.reason_is::<Reasons>());
+        }
+
+        let e = errs::add_sync_err_handler(|err, _tm| {
+            LOGGER.lock().unwrap().push(format!("{err}"));
+        })
+        .unwrap_err();
+        assert_eq!(e.kind(), errs::ErrHandlingErrorKind::AlreadyFixed);
+
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+        assert!(LOGGER.lock().unwrap().is_empty());
+    }
+}
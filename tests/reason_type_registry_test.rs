@@ -0,0 +1,42 @@
+#[cfg(feature = "registry")]
+#[cfg(test)]
+mod tests_of_reason_type_registry {
+    use errs::register_reason_type;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum ConfigReasons {
+        FileNotFound { path: String },
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum DbReasons {
+        FailToConnect,
+    }
+
+    register_reason_type!(ConfigReasons, "Errors while loading the configuration.");
+    register_reason_type!(DbReasons);
+
+    #[test]
+    fn test() {
+        let mut types = errs::known_reason_types();
+        types.sort_by_key(|t| t.name);
+        assert_eq!(types.len(), 2);
+
+        assert_eq!(
+            types[0].name,
+            "reason_type_registry_test::tests_of_reason_type_registry::ConfigReasons"
+        );
+        assert_eq!(
+            types[0].description,
+            "Errors while loading the configuration."
+        );
+
+        assert_eq!(
+            types[1].name,
+            "reason_type_registry_test::tests_of_reason_type_registry::DbReasons"
+        );
+        assert_eq!(types[1].description, "");
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_ring_buffer {
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething(u32),
+    }
+
+    #[test]
+    fn test() {
+        errs::enable_ring_buffer(3).unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        let line = line!() + 2;
+        for i in 0..5 {
+            let _err = errs::Err::new(Reasons::FailToDoSomething(i));
+        }
+
+        let records = errs::drain_ring_buffer();
+        assert_eq!(
+            records
+                .iter()
+                .map(|r| r.reason.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "FailToDoSomething(2)",
+                "FailToDoSomething(3)",
+                "FailToDoSomething(4)"
+            ]
+        );
+        assert!(records.iter().all(|r| r.line == line));
+        assert!(records.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+
+        assert!(errs::drain_ring_buffer().is_empty());
+    }
+}
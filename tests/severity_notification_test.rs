@@ -0,0 +1,50 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_severity_notification {
+    use errs::Severity;
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    errs::add_sync_err_handler!(|err, _tm| {
+        LOGGER
+            .lock()
+            .unwrap()
+            .push(format!("{err}: {:?}", err.severity()));
+    });
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+        OutOfMemory,
+        CacheMiss,
+        Timeout,
+    }
+
+    #[test]
+    fn test() {
+        let err0 = errs::Err::new(Reasons::FailToDoSomething);
+        let err1 = errs::Err::new_with_severity(Reasons::OutOfMemory, Severity::Fatal);
+        let err2 = errs::Err::new(Reasons::CacheMiss).with_severity(Severity::Warning);
+        let err3 = errs::Err::builder(Reasons::Timeout)
+            .severity(Severity::Warning)
+            .build();
+
+        assert_eq!(err0.severity(), Severity::Error);
+        assert_eq!(err1.severity(), Severity::Fatal);
+        assert_eq!(err2.severity(), Severity::Warning);
+        assert_eq!(err3.severity(), Severity::Warning);
+
+        let logs = LOGGER.lock().unwrap();
+        assert_eq!(
+            *logs,
+            vec![
+                "FailToDoSomething: Error",
+                "OutOfMemory: Fatal",
+                // The severity set after creation is not notified.
+                "CacheMiss: Error",
+                "Timeout: Warning",
+            ]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests_of_notification {
+    use std::sync::{LazyLock, Mutex};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[cfg(feature = "notify")]
+    errs::add_sync_err_handler!(|err, _tm| {
+        LOGGER.lock().unwrap().push(format!("[sync] {err}"));
+    });
+
+    #[derive(Debug)]
+    enum Reasons {
+        CacheMiss,
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        let err = errs::Err::new_silent(Reasons::CacheMiss);
+        assert!(err.is_silent());
+
+        let err = errs::Err::with_source_silent(Reasons::CacheMiss, std::io::Error::other("x"));
+        assert!(err.is_silent());
+
+        assert!(LOGGER.lock().unwrap().is_empty());
+
+        let err = errs::Err::new(Reasons::FailToDoSomething);
+        assert!(!err.is_silent());
+
+        #[cfg(feature = "notify")]
+        {
+            let logs = LOGGER.lock().unwrap();
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0], "[sync] FailToDoSomething");
+        }
+        #[cfg(not(feature = "notify"))]
+        {
+            assert!(LOGGER.lock().unwrap().is_empty());
+        }
+    }
+}
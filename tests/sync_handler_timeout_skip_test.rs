@@ -0,0 +1,40 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_sync_handler_timeout_skip {
+    use std::sync::{LazyLock, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        First,
+        Second,
+        Third,
+    }
+
+    #[test]
+    fn test() {
+        errs::add_sync_err_handler_timeout(Duration::from_millis(50), |err, _tm| {
+            thread::sleep(Duration::from_millis(500));
+            LOGGER.lock().unwrap().push(format!("{err}"));
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        // Times out, and the handler keeps running for this error.
+        let _err = errs::Err::new(Reasons::First);
+        // Dropped because the handler is still running for the first error.
+        let _err = errs::Err::new(Reasons::Second);
+        assert!(LOGGER.lock().unwrap().is_empty());
+
+        thread::sleep(Duration::from_secs(1));
+        assert_eq!(*LOGGER.lock().unwrap(), vec!["First"]);
+
+        // Runs again since the previous call has returned.
+        let _err = errs::Err::new(Reasons::Third);
+        thread::sleep(Duration::from_secs(1));
+        assert_eq!(*LOGGER.lock().unwrap(), vec!["First", "Third"]);
+    }
+}
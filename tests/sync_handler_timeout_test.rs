@@ -0,0 +1,41 @@
+#[cfg(feature = "notify")]
+#[cfg(test)]
+mod tests_of_sync_handler_timeout {
+    use std::sync::{LazyLock, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    static LOGGER: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+    #[derive(Debug)]
+    enum Reasons {
+        FailToDoSomething,
+    }
+
+    #[test]
+    fn test() {
+        errs::add_sync_err_handler_timeout(Duration::from_millis(50), |err, _tm| {
+            thread::sleep(Duration::from_secs(3));
+            LOGGER.lock().unwrap().push(format!("slow: {err}"));
+        })
+        .unwrap();
+        errs::add_sync_err_handler_timeout(Duration::from_secs(3), |err, _tm| {
+            LOGGER.lock().unwrap().push(format!("fast: {err}"));
+        })
+        .unwrap();
+        errs::add_sync_err_handler(|err, _tm| {
+            LOGGER.lock().unwrap().push(format!("inline: {err}"));
+        })
+        .unwrap();
+        errs::fix_err_handlers().unwrap();
+
+        let start = Instant::now();
+        let _err = errs::Err::new(Reasons::FailToDoSomething);
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        assert_eq!(
+            *LOGGER.lock().unwrap(),
+            vec!["inline: FailToDoSomething", "fast: FailToDoSomething"]
+        );
+    }
+}